@@ -0,0 +1,93 @@
+//! Watches `config.toml` for edits made after startup and reloads it live,
+//! so changes to the high-contrast, font-scale and reduce-animations display
+//! settings and to entity-name overrides take effect without restarting the
+//! panel. See [`Message::ConfigReloaded`] for what actually gets re-applied
+//! on each reload. `display.scale`, the network proxy, and the sound and
+//! cache settings keep the values read at startup, since their `configure`
+//! functions only store the first value they are given. Anything baked into
+//! the room list or [`crate::oracle::Oracle`] at startup (rooms, routines,
+//! new entities, ...) still needs a restart, the same as a change to Home
+//! Assistant's own registries would.
+//!
+//! [`Message::ConfigReloaded`]: crate::Message::ConfigReloaded
+
+use std::{path::Path, time::Duration};
+
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use crate::config::Config;
+
+const CONFIG_PATH: &str = "./config.toml";
+
+/// Emits a freshly reloaded [`Config`] every time `config.toml` is written
+/// to after this subscription starts. A save that doesn't parse (a mid-edit
+/// save, a typo) is logged to stderr and otherwise ignored: the panel just
+/// keeps running on whatever config it last loaded successfully until the
+/// next save fixes it.
+pub fn subscription() -> iced::Subscription<Config> {
+    struct ConfigWatchSubscription;
+
+    iced::subscription::channel(
+        std::any::TypeId::of::<ConfigWatchSubscription>(),
+        16,
+        move |mut output| async move {
+            let (tx, mut rx) = mpsc::channel(16);
+
+            let config_path = Path::new(CONFIG_PATH);
+            let Some(config_dir) = config_path.parent() else {
+                eprintln!("config: couldn't determine config.toml's parent directory");
+                return;
+            };
+            let config_name = config_path.file_name();
+
+            let mut watcher =
+                match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                    let is_config_event = matches!(&res, Ok(event)
+                        if (event.kind.is_modify() || event.kind.is_create())
+                            && event.paths.iter().any(|path| path.file_name() == config_name));
+
+                    if is_config_event {
+                        let _res = tx.blocking_send(());
+                    }
+                }) {
+                    Ok(watcher) => watcher,
+                    Err(err) => {
+                        eprintln!("config: couldn't start config.toml watcher: {err}");
+                        return;
+                    }
+                };
+
+            // Watching `config.toml` itself only lasts until the first save:
+            // editors commonly save via a temp-file-plus-rename, and the
+            // rename swaps in a new inode, which silently detaches an
+            // inotify watch on the old one. Watch the parent directory
+            // instead and filter down to events on `config.toml` by name.
+            if watcher
+                .watch(config_dir, RecursiveMode::NonRecursive)
+                .is_err()
+            {
+                // No config directory to watch, e.g. --demo mode. Nothing to do.
+                return;
+            }
+
+            while rx.recv().await.is_some() {
+                // Editors commonly save via a temp-file-plus-rename, which
+                // fires a burst of modify events in quick succession; wait a
+                // beat and drain the channel so only the final write of a
+                // save gets reloaded.
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                while rx.try_recv().is_ok() {}
+
+                match crate::try_load_config().await {
+                    Ok(config) => {
+                        let _res = iced::futures::SinkExt::send(&mut output, config).await;
+                    }
+                    Err(err) => {
+                        eprintln!("config: reload failed, keeping previous config: {err}");
+                    }
+                }
+            }
+        },
+    )
+}
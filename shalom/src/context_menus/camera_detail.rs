@@ -0,0 +1,55 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, image, row, scrollable, text},
+    Element, Font, Length, Renderer,
+};
+
+/// A camera's current snapshot plus a scrollable strip of recent history, so
+/// you can see what triggered recent motion. Read-only: there's nothing here
+/// to act on, so [`Message`] has no variants.
+#[derive(Debug, Clone)]
+pub struct CameraDetail {
+    name: Box<str>,
+    history: Vec<image::Handle>,
+}
+
+impl CameraDetail {
+    pub fn new(name: Box<str>, history: Vec<image::Handle>) -> Self {
+        Self { name, history }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let history = self
+            .history
+            .iter()
+            .skip(1)
+            .fold(row![].spacing(10), |row, handle| {
+                row.push(image(handle.clone()).width(128.).height(72.))
+            });
+
+        let content = column![text(&self.name).size(40).font(Font {
+            weight: Weight::Bold,
+            stretch: Stretch::Condensed,
+            ..Font::with_name("Helvetica Neue")
+        })]
+        .spacing(20);
+
+        let content = if let Some(current) = self.history.first() {
+            content.push(image(current.clone()).width(768.).height(432.))
+        } else {
+            content
+        };
+
+        container(content.push(
+            scrollable(history).direction(scrollable::Direction::Horizontal(
+                scrollable::Properties::default(),
+            )),
+        ))
+        .width(Length::Fill)
+        .padding(40)
+        .into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {}
@@ -1,10 +1,26 @@
 use iced::{
     font::{Stretch, Weight},
-    widget::{column, container, row, text},
-    Alignment, Element, Font, Length, Renderer,
+    widget::{column, container, mouse_area, row, slider, text, vertical_space},
+    Alignment, Background, Element, Font, Length, Renderer, Theme,
 };
 
-use crate::{oracle::Light, widgets::colour_picker::ColourPicker};
+use crate::{
+    config::LightPresetConfig,
+    hass_client::responses::ColorMode,
+    oracle::{Light, RecentColour, Sensor},
+    widgets::{
+        colour_picker::{colour_from_hsb, ColourPicker, ColourWheelPicker},
+        toggle_card::toggle_card,
+    },
+};
+
+/// Built-in colour presets shown before any user-defined ones from
+/// `config.toml`. Hue is in degrees (0-360); saturation/brightness are 0-1.
+const BUILTIN_PRESETS: [(&str, f32, f32, f32); 3] = [
+    ("Warm White", 30.0, 0.3, 1.0),
+    ("Relax", 20.0, 0.6, 0.4),
+    ("Concentrate", 200.0, 0.1, 1.0),
+];
 
 #[derive(Debug, Clone)]
 pub struct LightControl {
@@ -13,19 +29,95 @@ pub struct LightControl {
     hue: f32,
     saturation: f32,
     brightness: f32,
+    presets: Vec<Preset>,
+    recent: Vec<RecentColour>,
+    power: Option<Sensor>,
+    energy: Option<Sensor>,
+    circular_colour_picker: bool,
+    /// The white channel level (0-255), for RGBW/RGBWW bulbs' white-level
+    /// slider. `None` if the light doesn't support either colour mode, in
+    /// which case [`Self::view`] doesn't show the slider at all.
+    white: Option<u8>,
+    /// Whether `light.supported_color_modes` is `[brightness]`: dimmable but
+    /// not colour-capable. [`Self::view`] shows just a brightness slider for
+    /// these instead of the full colour picker, which would otherwise send
+    /// an `hs_color` Home Assistant rejects.
+    brightness_only: bool,
+    /// Whether the light is currently cycling through colours, either via
+    /// Home Assistant's native `colorloop` effect or (for bulbs lacking that
+    /// effect) the Oracle's client-side hue-stepping loop. Set from the
+    /// caller at [`Self::new`], since neither is derivable from `Light`
+    /// alone for the client-side case.
+    colour_loop: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Preset {
+    name: Box<str>,
+    hue: f32,
+    saturation: f32,
+    brightness: f32,
 }
 
 impl LightControl {
-    pub fn new(id: &'static str, light: Light) -> Self {
+    pub fn new(
+        id: &'static str,
+        light: Light,
+        presets: Vec<LightPresetConfig>,
+        recent: Vec<RecentColour>,
+        power: Option<Sensor>,
+        energy: Option<Sensor>,
+        circular_colour_picker: bool,
+        colour_loop: bool,
+    ) -> Self {
         let (hue, saturation) = light.hs_color.unwrap_or_default();
         let brightness = light.brightness.unwrap_or_default();
 
+        let white = if light.supported_color_modes.contains(&ColorMode::Rgbww) {
+            light.rgbww_color.map(|(.., white)| white)
+        } else if light.supported_color_modes.contains(&ColorMode::Rgbw) {
+            light.rgbw_color.map(|(.., white)| white)
+        } else {
+            None
+        }
+        .or(light
+            .supported_color_modes
+            .iter()
+            .any(|mode| matches!(mode, ColorMode::Rgbw | ColorMode::Rgbww))
+            .then_some(0));
+
+        let brightness_only = light.supported_color_modes == [ColorMode::Brightness];
+
+        let presets = BUILTIN_PRESETS
+            .into_iter()
+            .map(|(name, hue, saturation, brightness)| Preset {
+                name: Box::from(name),
+                hue,
+                saturation,
+                brightness,
+            })
+            .chain(presets.into_iter().map(|preset| Preset {
+                name: Box::from(preset.name.as_str()),
+                hue: preset.hue,
+                saturation: preset.saturation,
+                brightness: preset.brightness,
+            }))
+            .collect();
+
         Self {
             id,
             name: light.friendly_name,
             hue,
             saturation: saturation / 100.,
             brightness: brightness / 255.,
+            presets,
+            recent,
+            power,
+            energy,
+            circular_colour_picker,
+            white,
+            brightness_only,
+            colour_loop,
         }
     }
 
@@ -45,31 +137,193 @@ impl LightControl {
                 saturation: self.saturation,
                 brightness: self.brightness,
             }),
+            Message::ApplyColour(hue, saturation, brightness) => {
+                self.hue = hue;
+                self.saturation = saturation;
+                self.brightness = brightness;
+
+                Some(Event::UpdateLightColour {
+                    id: self.id,
+                    hue: self.hue,
+                    saturation: self.saturation,
+                    brightness: self.brightness,
+                })
+            }
+            Message::OnWhiteChange(white) => {
+                self.white = Some(white);
+                None
+            }
+            Message::OnWhiteRelease => Some(Event::UpdateLightWhite {
+                id: self.id,
+                white: self.white.unwrap_or_default(),
+            }),
+            Message::OnBrightnessChange(brightness) => {
+                self.brightness = brightness;
+                None
+            }
+            Message::OnBrightnessRelease => Some(Event::UpdateLightBrightness {
+                id: self.id,
+                brightness: self.brightness,
+            }),
+            Message::OnColourLoopToggle => {
+                self.colour_loop = !self.colour_loop;
+
+                Some(Event::SetColourLoop {
+                    id: self.id,
+                    enabled: self.colour_loop,
+                })
+            }
         }
     }
 
     pub fn view(&self) -> Element<'_, Message, Renderer> {
-        let colour_picker = ColourPicker::new(
-            self.hue,
-            self.saturation,
-            self.brightness,
-            Message::OnColourChange,
-            Message::OnMouseUp,
+        let mut content = column![text(&self.name).size(40).font(Font {
+            weight: Weight::Bold,
+            stretch: Stretch::Condensed,
+            ..Font::with_name("Helvetica Neue")
+        })]
+        .spacing(20);
+
+        if !self.brightness_only {
+            let presets = self.presets.iter().fold(row![].spacing(10), |row, preset| {
+                let colour = colour_from_hsb(preset.hue, preset.saturation, preset.brightness);
+
+                let swatch = mouse_area(container(vertical_space(0)).width(40).height(40).style(
+                    move |_theme: &Theme| container::Appearance {
+                        background: Some(Background::Color(colour)),
+                        ..container::Appearance::default()
+                    },
+                ))
+                .on_press(Message::ApplyColour(
+                    preset.hue,
+                    preset.saturation,
+                    preset.brightness,
+                ));
+
+                row.push(
+                    column![swatch, text(preset.name.as_ref()).size(14)]
+                        .align_items(Alignment::Center)
+                        .spacing(4),
+                )
+            });
+
+            content = content.push(presets);
+        }
+
+        if !self.brightness_only && !self.recent.is_empty() {
+            let recent = self.recent.iter().fold(row![].spacing(10), |row, colour| {
+                let rgb = colour_from_hsb(colour.hue, colour.saturation, colour.brightness);
+
+                let swatch = mouse_area(container(vertical_space(0)).width(32).height(32).style(
+                    move |_theme: &Theme| container::Appearance {
+                        background: Some(Background::Color(rgb)),
+                        ..container::Appearance::default()
+                    },
+                ))
+                .on_press(Message::ApplyColour(
+                    colour.hue,
+                    colour.saturation,
+                    colour.brightness,
+                ));
+
+                row.push(swatch)
+            });
+
+            content = content.push(column![text("Recent").size(14), recent].spacing(4));
+        }
+
+        if self.power.is_some() || self.energy.is_some() {
+            let mut usage = row![].spacing(20);
+
+            if let Some(power) = &self.power {
+                usage = usage.push(text(format!(
+                    "{} {}",
+                    power.state,
+                    power.unit_of_measurement.as_deref().unwrap_or("")
+                )));
+            }
+
+            if let Some(energy) = &self.energy {
+                usage = usage.push(text(format!(
+                    "{} {} today",
+                    energy.state,
+                    energy.unit_of_measurement.as_deref().unwrap_or("")
+                )));
+            }
+
+            content = content.push(usage);
+        }
+
+        if self.brightness_only {
+            content = content.push(
+                row![
+                    text("Brightness").size(18),
+                    slider(0.0..=1.0, self.brightness, Message::OnBrightnessChange)
+                        .on_release(Message::OnBrightnessRelease)
+                        .width(Length::Fill),
+                ]
+                .spacing(20)
+                .align_items(Alignment::Center),
+            );
+
+            return container(content).width(Length::Fill).padding(40).into();
+        }
+
+        content = content.push(
+            row![
+                text("Colour Loop").size(18),
+                toggle_card(
+                    if self.colour_loop { "On" } else { "Off" },
+                    self.colour_loop,
+                    false
+                )
+                .on_press(Message::OnColourLoopToggle)
+                .width(Length::Fixed(80.0)),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
         );
 
-        container(column![
-            text(&self.name).size(40).font(Font {
-                weight: Weight::Bold,
-                stretch: Stretch::Condensed,
-                ..Font::with_name("Helvetica Neue")
-            }),
+        let colour_picker: Element<'_, Message, Renderer> = if self.circular_colour_picker {
+            ColourWheelPicker::new(
+                self.hue,
+                self.saturation,
+                self.brightness,
+                Message::OnColourChange,
+                Message::OnMouseUp,
+            )
+            .into()
+        } else {
+            ColourPicker::new(
+                self.hue,
+                self.saturation,
+                self.brightness,
+                Message::OnColourChange,
+                Message::OnMouseUp,
+            )
+            .into()
+        };
+
+        content = content.push(
             row![colour_picker,]
                 .align_items(Alignment::Center)
+                .spacing(20),
+        );
+
+        if let Some(white) = self.white {
+            content = content.push(
+                row![
+                    text("White").size(18),
+                    slider(0..=255, white, Message::OnWhiteChange)
+                        .on_release(Message::OnWhiteRelease)
+                        .width(Length::Fill),
+                ]
                 .spacing(20)
-        ])
-        .width(Length::Fill)
-        .padding(40)
-        .into()
+                .align_items(Alignment::Center),
+            );
+        }
+
+        container(content).width(Length::Fill).padding(40).into()
     }
 }
 
@@ -80,10 +334,28 @@ pub enum Event {
         saturation: f32,
         brightness: f32,
     },
+    UpdateLightWhite {
+        id: &'static str,
+        white: u8,
+    },
+    UpdateLightBrightness {
+        id: &'static str,
+        brightness: f32,
+    },
+    SetColourLoop {
+        id: &'static str,
+        enabled: bool,
+    },
 }
 
 #[derive(Clone, Debug)]
 pub enum Message {
     OnColourChange(f32, f32, f32),
     OnMouseUp,
+    ApplyColour(f32, f32, f32),
+    OnWhiteChange(u8),
+    OnWhiteRelease,
+    OnBrightnessChange(f32),
+    OnBrightnessRelease,
+    OnColourLoopToggle,
 }
@@ -1 +1,7 @@
+pub mod camera_detail;
 pub mod light_control;
+pub mod pin_pad;
+pub mod quick_settings;
+pub mod room_summary;
+pub mod save_scene;
+pub mod timer_finished;
@@ -0,0 +1,80 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, mouse_area, row, text},
+    Alignment, Element, Font, Length, Renderer,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct PinPad {
+    entered: String,
+}
+
+impl PinPad {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Digit(digit) => {
+                self.entered.push_str(&digit.to_string());
+                None
+            }
+            Message::Clear => {
+                self.entered.clear();
+                None
+            }
+            Message::Submit => Some(Event::PinEntered(std::mem::take(&mut self.entered))),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let digit = |value: u8| {
+            mouse_area(
+                container(text(value).size(28))
+                    .width(Length::Fixed(70.0))
+                    .height(Length::Fixed(70.0))
+                    .align_x(iced::alignment::Horizontal::Center)
+                    .align_y(iced::alignment::Vertical::Center),
+            )
+            .on_press(Message::Digit(value))
+        };
+
+        container(
+            column![
+                text("Enter PIN").size(32).font(Font {
+                    weight: Weight::Bold,
+                    stretch: Stretch::Condensed,
+                    ..Font::with_name("Helvetica Neue")
+                }),
+                text("*".repeat(self.entered.len())).size(24),
+                row![digit(1), digit(2), digit(3)].spacing(10),
+                row![digit(4), digit(5), digit(6)].spacing(10),
+                row![digit(7), digit(8), digit(9)].spacing(10),
+                row![
+                    mouse_area(text("Clear").size(20)).on_press(Message::Clear),
+                    digit(0),
+                    mouse_area(text("OK").size(20)).on_press(Message::Submit),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(15)
+            .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding(40)
+        .into()
+    }
+}
+
+pub enum Event {
+    PinEntered(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Digit(u8),
+    Clear,
+    Submit,
+}
@@ -0,0 +1,61 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, row, slider, text},
+    Alignment, Element, Font, Length, Renderer,
+};
+
+#[derive(Debug, Clone)]
+pub struct QuickSettings {
+    backlight: u8,
+}
+
+impl QuickSettings {
+    pub fn new(backlight: u8) -> Self {
+        Self { backlight }
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::OnBacklightChange(brightness) => {
+                self.backlight = brightness;
+                None
+            }
+            Message::OnBacklightRelease => Some(Event::SetBacklight(self.backlight)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        container(
+            column![
+                text("Quick Settings").size(40).font(Font {
+                    weight: Weight::Bold,
+                    stretch: Stretch::Condensed,
+                    ..Font::with_name("Helvetica Neue")
+                }),
+                row![
+                    text("Backlight").size(18),
+                    slider(0..=100, self.backlight, Message::OnBacklightChange)
+                        .on_release(Message::OnBacklightRelease)
+                        .width(Length::Fill),
+                    text(format!("{}%", self.backlight)).size(18),
+                ]
+                .spacing(20)
+                .align_items(Alignment::Center),
+            ]
+            .spacing(20),
+        )
+        .width(Length::Fill)
+        .padding(40)
+        .into()
+    }
+}
+
+pub enum Event {
+    SetBacklight(u8),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    OnBacklightChange(u8),
+    OnBacklightRelease,
+}
@@ -0,0 +1,115 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, row, text},
+    Element, Font, Length, Renderer,
+};
+
+use crate::widgets;
+
+/// A room's key states, shown in a [`crate::widgets::context_menu::ContextMenu`]
+/// on long-press of its card on the omni page, with a quick light toggle so
+/// the room doesn't need to be opened just to flip a switch.
+#[derive(Debug, Clone)]
+pub struct RoomSummary {
+    name: Box<str>,
+    lights: Vec<(&'static str, Light)>,
+    temperature: Option<f64>,
+    now_playing: Option<Box<str>>,
+}
+
+#[derive(Debug, Clone)]
+struct Light {
+    friendly_name: Box<str>,
+    on: bool,
+}
+
+impl RoomSummary {
+    pub fn new(
+        name: Box<str>,
+        lights: Vec<(&'static str, crate::oracle::Light)>,
+        temperature: Option<f64>,
+        now_playing: Option<Box<str>>,
+    ) -> Self {
+        let lights = lights
+            .into_iter()
+            .map(|(id, light)| {
+                (
+                    id,
+                    Light {
+                        friendly_name: light.friendly_name,
+                        on: light.on.unwrap_or(false),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            name,
+            lights,
+            temperature,
+            now_playing,
+        }
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::ToggleLight(id) => {
+                let light = self
+                    .lights
+                    .iter_mut()
+                    .find(|(light_id, _)| *light_id == id)?;
+                light.1.on = !light.1.on;
+
+                Some(Event::SetLightState(id, light.1.on))
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let lights_on = self.lights.iter().filter(|(_, light)| light.on).count();
+
+        let mut content = column![
+            text(&self.name).size(40).font(Font {
+                weight: Weight::Bold,
+                stretch: Stretch::Condensed,
+                ..Font::with_name("Helvetica Neue")
+            }),
+            text(format!("{lights_on} of {} lights on", self.lights.len())).size(18),
+        ]
+        .spacing(20);
+
+        if let Some(temperature) = self.temperature {
+            content = content.push(text(format!("{temperature}°")).size(18));
+        }
+
+        if let Some(now_playing) = &self.now_playing {
+            content = content.push(text(format!("Playing: {now_playing}")).size(18));
+        }
+
+        if !self.lights.is_empty() {
+            let toggles = self
+                .lights
+                .iter()
+                .fold(row![].spacing(10), |row, (id, light)| {
+                    row.push(
+                        widgets::toggle_card::toggle_card(&light.friendly_name, light.on, false)
+                            .width(Length::Shrink)
+                            .on_press(Message::ToggleLight(*id)),
+                    )
+                });
+
+            content = content.push(toggles);
+        }
+
+        container(content).width(Length::Fill).padding(40).into()
+    }
+}
+
+pub enum Event {
+    SetLightState(&'static str, bool),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    ToggleLight(&'static str),
+}
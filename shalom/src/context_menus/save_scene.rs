@@ -0,0 +1,67 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, mouse_area, row, text, text_input},
+    Alignment, Element, Font, Length, Renderer,
+};
+
+#[derive(Debug, Clone)]
+pub struct SaveScene {
+    room_id: &'static str,
+    name: String,
+}
+
+impl SaveScene {
+    pub fn new(room_id: &'static str) -> Self {
+        Self {
+            room_id,
+            name: String::new(),
+        }
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::NameChanged(name) => {
+                self.name = name;
+                None
+            }
+            Message::Submit if !self.name.trim().is_empty() => Some(Event::SaveScene {
+                room_id: self.room_id,
+                name: std::mem::take(&mut self.name),
+            }),
+            Message::Submit => None,
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        container(
+            column![
+                text("Save as Scene").size(32).font(Font {
+                    weight: Weight::Bold,
+                    stretch: Stretch::Condensed,
+                    ..Font::with_name("Helvetica Neue")
+                }),
+                text_input("Scene name...", &self.name)
+                    .on_input(Message::NameChanged)
+                    .on_submit(Message::Submit)
+                    .size(20)
+                    .width(Length::Fixed(400.0)),
+                row![mouse_area(text("Save").size(20)).on_press(Message::Submit),].spacing(10),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding(40)
+        .into()
+    }
+}
+
+pub enum Event {
+    SaveScene { room_id: &'static str, name: String },
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    NameChanged(String),
+    Submit,
+}
@@ -0,0 +1,40 @@
+use iced::{
+    font::{Stretch, Weight},
+    widget::{column, container, text},
+    Alignment, Element, Font, Length, Renderer,
+};
+
+/// The visual half of the alarm when a [`crate::oracle::LocalTimer`] hits
+/// zero. Read-only, dismissed the same way as [`super::camera_detail`] —
+/// tapping outside closes the [`crate::widgets::context_menu::ContextMenu`].
+#[derive(Debug, Clone)]
+pub struct TimerFinished {
+    label: Box<str>,
+}
+
+impl TimerFinished {
+    pub fn new(label: Box<str>) -> Self {
+        Self { label }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        container(
+            column![
+                text("Timer finished").size(40).font(Font {
+                    weight: Weight::Bold,
+                    stretch: Stretch::Condensed,
+                    ..Font::with_name("Helvetica Neue")
+                }),
+                text(self.label.as_ref()).size(28),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center),
+        )
+        .width(Length::Fill)
+        .padding(40)
+        .into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {}
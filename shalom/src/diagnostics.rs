@@ -0,0 +1,55 @@
+//! Exportable diagnostics bundle for bug reports, triggered from the
+//! maintenance page. Bundles a sanitized copy of the config (secrets
+//! redacted), the recent Home Assistant connection log (see
+//! [`crate::hass_client::Client::diagnostic_log`]), and nothing else — there's
+//! no persistent log file to include beyond that connection log, since the
+//! app otherwise only logs to stderr via `eprintln!`.
+
+use std::{io::Write, path::PathBuf};
+
+use time::OffsetDateTime;
+use zip::{write::FileOptions, ZipWriter};
+
+use crate::config::Config;
+
+/// Writes a `shalom-diagnostics-<timestamp>.zip` file to the current
+/// directory and returns its path.
+pub fn export_bundle(config: &Config, log: &[Box<str>]) -> Result<PathBuf, String> {
+    let path = PathBuf::from(format!(
+        "shalom-diagnostics-{}.zip",
+        OffsetDateTime::now_utc().unix_timestamp()
+    ));
+
+    let file = std::fs::File::create(&path).map_err(|e| e.to_string())?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("config.txt", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(sanitized_config(config).as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    zip.start_file("connection.log", options)
+        .map_err(|e| e.to_string())?;
+    zip.write_all(log.join("\n").as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    zip.finish().map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// A copy of `config` with every secret (Home Assistant/Spotify tokens, the
+/// PIN) replaced with a placeholder, formatted for humans rather than
+/// round-tripped back through toml.
+fn sanitized_config(config: &Config) -> String {
+    let mut config = config.clone();
+
+    config.home_assistant.token = "<redacted>".to_string();
+    config.spotify.token = "<redacted>".to_string();
+    if config.security.pin.is_some() {
+        config.security.pin = Some("<redacted>".to_string());
+    }
+
+    format!("{config:#?}")
+}
@@ -0,0 +1,83 @@
+//! Discovers Home Assistant instances advertising themselves on the LAN via
+//! mDNS (`_home-assistant._tcp`), so a fresh install doesn't need `uri` typed
+//! in by hand. Only compiled in with the `discovery` feature; see
+//! [`crate::pages::discovery`] for the first-run selection screen this feeds.
+
+use std::time::Duration;
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+
+const SERVICE_TYPE: &str = "_home-assistant._tcp.local.";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscoveredInstance {
+    pub name: Box<str>,
+    pub uri: Box<str>,
+}
+
+/// Streams a [`DiscoveredInstance`] every time mDNS resolves a new Home
+/// Assistant instance on the LAN. Never completes on its own; the caller's
+/// UI decides when browsing is done.
+pub fn subscription() -> iced::Subscription<DiscoveredInstance> {
+    struct DiscoverySubscription;
+
+    iced::subscription::channel(
+        std::any::TypeId::of::<DiscoverySubscription>(),
+        16,
+        move |mut output| async move {
+            loop {
+                let Ok(daemon) = ServiceDaemon::new() else {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                };
+
+                let Ok(receiver) = daemon.browse(SERVICE_TYPE) else {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                };
+
+                while let Ok(event) = receiver.recv_async().await {
+                    if let ServiceEvent::ServiceResolved(info) = event {
+                        let Some(instance) = to_instance(&info) else {
+                            continue;
+                        };
+
+                        let _res = iced::futures::SinkExt::send(&mut output, instance).await;
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        },
+    )
+}
+
+/// Home Assistant's zeroconf advertisement carries the panel-facing URL in a
+/// `base_url` TXT record, of which only the host and port are kept, since
+/// `config.toml`'s `uri` takes no scheme; fall back to `<address>:<port>` for
+/// advertisements that only expose the raw socket.
+fn to_instance(info: &ServiceInfo) -> Option<DiscoveredInstance> {
+    let name = info
+        .get_property_val_str("friendly_name")
+        .unwrap_or_else(|| info.get_hostname());
+
+    let uri = match info.get_property_val_str("base_url") {
+        Some(base_url) => {
+            let base_url = url::Url::parse(base_url).ok()?;
+            let host = base_url.host_str()?;
+            match base_url.port() {
+                Some(port) => format!("{host}:{port}"),
+                None => host.to_string(),
+            }
+        }
+        None => {
+            let address = info.get_addresses().iter().next()?;
+            format!("{address}:{}", info.get_port())
+        }
+    };
+
+    Some(DiscoveredInstance {
+        name: Box::from(name),
+        uri: Box::from(uri),
+    })
+}
@@ -1,8 +1,15 @@
 #![allow(clippy::forget_non_drop, dead_code)]
 
-use std::{borrow::Cow, collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use iced::futures::{SinkExt, StreamExt};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
 use serde_with::serde_as;
@@ -12,16 +19,79 @@ use tokio_tungstenite::tungstenite::Message;
 use url::Url;
 use yoke::{Yoke, Yokeable};
 
-use crate::config::HomeAssistantConfig;
+use crate::{config::HomeAssistantConfig, network, tls};
+
+/// How many lines [`DiagnosticLog`] keeps around, for the diagnostics bundle
+/// exported from the maintenance page.
+const DIAGNOSTIC_LOG_CAPACITY: usize = 200;
+
+/// How often a keepalive ping is sent on the websocket connection.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A pong more than this long overdue means the connection is considered
+/// dead, since a healthy connection answers within one round trip.
+const PING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait between reconnect attempts while the websocket
+/// connection is down.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(5);
+
+type WsStream =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// A rolling window of connection-level events (raw websocket frames, auth
+/// failures, reconnects) for troubleshooting, since nothing here is written
+/// to a log file otherwise.
+#[derive(Debug, Default)]
+struct DiagnosticLog(Mutex<VecDeque<Box<str>>>);
+
+impl DiagnosticLog {
+    fn push(&self, line: impl Into<Box<str>>) {
+        let mut log = self.0.lock();
+
+        if log.len() >= DIAGNOSTIC_LOG_CAPACITY {
+            log.pop_front();
+        }
+
+        log.push_back(line.into());
+    }
+
+    fn snapshot(&self) -> Vec<Box<str>> {
+        self.0.lock().iter().cloned().collect()
+    }
+}
+
+/// Whether the websocket connection is currently up, broadcast whenever the
+/// background task in [`create`] notices it go down (a missed pong past
+/// [`PING_TIMEOUT`], or the server closing the connection) or come back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionStatus {
+    Connected,
+    Disconnected,
+}
 
 #[derive(Clone, Debug)]
 pub struct Client {
     pub base: url::Url,
+    /// Long-lived access token, kept around for REST calls (e.g.
+    /// [`Self::upload_local_media`]) that fall outside the websocket API.
+    token: Arc<str>,
+    /// Shared REST client for calls outside the websocket API. Built once at
+    /// [`create`] so a configured `tls-fingerprint` only has to be wired up
+    /// in one place.
+    http: reqwest::Client,
+    /// Requests made while the connection is down simply block on this
+    /// channel filling up rather than being dropped: the background task in
+    /// [`create`] doesn't drain it while it's busy reconnecting, so a caller
+    /// awaiting [`Client::request`] just waits and is served once the
+    /// connection comes back.
     sender: mpsc::Sender<(
         HassRequestKind,
         oneshot::Sender<Yoke<&'static RawValue, String>>,
     )>,
     broadcast_channel: broadcast::Sender<Arc<Yoke<Event<'static>, String>>>,
+    connection_status: broadcast::Sender<ConnectionStatus>,
+    diagnostics: Arc<DiagnosticLog>,
 }
 
 impl Client {
@@ -53,29 +123,252 @@ impl Client {
         .await
     }
 
+    /// Escape hatch for calling a service on a domain that doesn't have a
+    /// typed [`CallServiceRequestData`] variant yet (e.g. covers, scenes,
+    /// climate). Prefer a typed variant where one exists.
+    pub async fn call_service_generic(
+        &self,
+        domain: impl Into<String>,
+        service: impl Into<String>,
+        entity_id: &'static str,
+        service_data: serde_json::Value,
+    ) -> Yoke<responses::CallServiceResponse, String> {
+        self.request::<responses::CallServiceResponse>(HassRequestKind::CallServiceGeneric(
+            CallServiceRequestGeneric {
+                domain: domain.into(),
+                service: service.into(),
+                service_data: (!service_data.is_null()).then_some(service_data),
+                target: Some(CallServiceRequestTarget { entity_id }),
+            },
+        ))
+        .await
+    }
+
+    /// Like [`Self::call_service_generic`], but for domain-level services
+    /// (e.g. `homeassistant.restart`) that don't target a specific entity.
+    pub async fn call_service_domain(
+        &self,
+        domain: impl Into<String>,
+        service: impl Into<String>,
+    ) -> Yoke<responses::CallServiceResponse, String> {
+        self.request::<responses::CallServiceResponse>(HassRequestKind::CallServiceGeneric(
+            CallServiceRequestGeneric {
+                domain: domain.into(),
+                service: service.into(),
+                service_data: None,
+                target: None,
+            },
+        ))
+        .await
+    }
+
+    /// Like [`Self::call_service_domain`], but for domain-level services that
+    /// take `service_data` (e.g. `scene.create`'s `scene_id`/`entities`).
+    pub async fn call_service_domain_with_data(
+        &self,
+        domain: impl Into<String>,
+        service: impl Into<String>,
+        service_data: serde_json::Value,
+    ) -> Yoke<responses::CallServiceResponse, String> {
+        self.request::<responses::CallServiceResponse>(HassRequestKind::CallServiceGeneric(
+            CallServiceRequestGeneric {
+                domain: domain.into(),
+                service: service.into(),
+                service_data: (!service_data.is_null()).then_some(service_data),
+                target: None,
+            },
+        ))
+        .await
+    }
+
     pub fn subscribe(&self) -> broadcast::Receiver<Arc<Yoke<Event<'static>, String>>> {
         self.broadcast_channel.subscribe()
     }
+
+    /// For a status indicator: fires whenever the websocket connection goes
+    /// down or comes back up. See [`ConnectionStatus`].
+    pub fn subscribe_connection_status(&self) -> broadcast::Receiver<ConnectionStatus> {
+        self.connection_status.subscribe()
+    }
+
+    /// The most recent [`DIAGNOSTIC_LOG_CAPACITY`] connection-level log
+    /// lines, oldest first, for the exportable diagnostics bundle.
+    pub fn diagnostic_log(&self) -> Vec<Box<str>> {
+        self.diagnostics.snapshot()
+    }
+
+    pub async fn shopping_list_items(&self) -> Yoke<responses::ShoppingListItems<'static>, String> {
+        self.request::<responses::ShoppingListItems>(HassRequestKind::ShoppingListItems)
+            .await
+    }
+
+    pub async fn shopping_list_add_item(&self, name: String) {
+        let _res = self
+            .request::<responses::Ack>(HassRequestKind::ShoppingListItemsAdd { name })
+            .await;
+    }
+
+    pub async fn shopping_list_set_complete(&self, item_id: String, complete: bool) {
+        let _res = self
+            .request::<responses::Ack>(HassRequestKind::ShoppingListItemsUpdate {
+                item_id,
+                complete,
+            })
+            .await;
+    }
+
+    pub async fn shopping_list_remove_item(&self, item_id: String) {
+        let _res = self
+            .request::<responses::Ack>(HassRequestKind::ShoppingListItemsRemove { item_id })
+            .await;
+    }
+
+    pub async fn browse_media(
+        &self,
+        entity_id: &'static str,
+        media_content_id: Option<String>,
+        media_content_type: Option<String>,
+    ) -> Yoke<responses::BrowseMediaResult<'static>, String> {
+        self.request::<responses::BrowseMediaResult>(HassRequestKind::BrowseMedia {
+            entity_id,
+            media_content_id,
+            media_content_type,
+        })
+        .await
+    }
+
+    /// Uploads `bytes` (e.g. a WAV clip recorded by [`crate::intercom`]) to
+    /// Home Assistant's local media source via its REST upload endpoint,
+    /// which has no websocket equivalent. Returns the resulting
+    /// `media_content_id`, playable via [`crate::oracle::EloquentSpeaker::play_media`].
+    pub async fn upload_local_media(
+        &self,
+        filename: &str,
+        bytes: Vec<u8>,
+    ) -> Result<String, String> {
+        let url = self
+            .base
+            .join("api/media_source/local_source/upload")
+            .map_err(|e| e.to_string())?;
+
+        let form = reqwest::multipart::Form::new()
+            .text("media_content_id", "media-source://media_source/local/")
+            .part(
+                "file",
+                reqwest::multipart::Part::bytes(bytes).file_name(filename.to_string()),
+            );
+
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(&*self.token)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?
+            .json::<responses::UploadLocalMediaResponse>()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(response.media_content_id)
+    }
+
+    /// Calls the `weather.get_forecasts` service, which newer Home Assistant
+    /// versions require for forecast data now that `forecast` is no longer
+    /// included in `weather` entity attributes.
+    pub async fn weather_get_forecasts(
+        &self,
+        entity_id: &'static str,
+        forecast_type: ForecastType,
+    ) -> Yoke<responses::WeatherGetForecastsResponse<'static>, String> {
+        self.request::<responses::WeatherGetForecastsResponse>(
+            HassRequestKind::WeatherGetForecasts {
+                domain: "weather",
+                service: "get_forecasts",
+                service_data: WeatherGetForecastsData { forecast_type },
+                target: CallServiceRequestTarget { entity_id },
+                return_response: true,
+            },
+        )
+        .await
+    }
 }
 
 #[allow(clippy::too_many_lines)]
-pub async fn create(config: HomeAssistantConfig) -> Client {
+pub async fn create(config: HomeAssistantConfig) -> Result<Client, String> {
     let (sender, mut recv) = mpsc::channel(10);
 
-    let uri = format!("wss://{}/api/websocket", config.uri);
-    let (mut connection, _response) = tokio_tungstenite::connect_async(&uri).await.unwrap();
+    let ws_scheme = if config.tls { "wss" } else { "ws" };
+    let uri = format!("{ws_scheme}://{}/api/websocket", config.uri);
+
+    let (mut connection, _response) = match &config.tls_fingerprint {
+        Some(fingerprint) if config.tls => tokio_tungstenite::connect_async_tls_with_config(
+            &uri,
+            None,
+            false,
+            Some(tokio_tungstenite::Connector::Rustls(
+                tls::pinned_client_config(fingerprint)?,
+            )),
+        )
+        .await
+        .unwrap(),
+        _ => tokio_tungstenite::connect_async(&uri).await.unwrap(),
+    };
+
+    let reconnect_uri = uri.clone();
+    let reconnect_tls = config.tls;
+    let reconnect_tls_fingerprint = config.tls_fingerprint.clone();
 
-    let (ready_send, ready_recv) = oneshot::channel();
+    let (ready_send, ready_recv) = oneshot::channel::<Result<(), String>>();
     let mut ready_send = Some(ready_send);
 
     let (broadcast_channel, _broadcast_recv) = broadcast::channel(10);
+    let (connection_status, _connection_status_recv) = broadcast::channel(4);
+    let diagnostics = Arc::new(DiagnosticLog::default());
+
+    if config.compression {
+        // See the doc comment on `HomeAssistantConfig::compression`: safely
+        // negotiating permessage-deflate needs frame-level support from
+        // `tungstenite` that doesn't exist yet, so this is a no-op for now
+        // rather than a half-working negotiation that would corrupt frames.
+        eprintln!("compression requested but not supported by this build; ignoring");
+        diagnostics.push("compression requested but not supported by this build; ignoring");
+    }
+
+    if network::is_configured() {
+        // See the doc comment on `NetworkConfig::proxy`: `tokio-tungstenite`
+        // has no proxy-dialing support, so the connection above was made
+        // directly. Only the REST calls in this module (and the rest of the
+        // app) are actually routed through the configured proxy.
+        eprintln!("network.proxy is set but the websocket connection does not support it");
+        diagnostics.push("network.proxy is set but the websocket connection does not support it");
+    }
 
     let broadcast_send = broadcast_channel.clone();
+    let connection_status_send = connection_status.clone();
+    let diagnostics_task = diagnostics.clone();
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(Duration::from_secs(10));
+        let diagnostics = diagnostics_task;
+        let mut interval = tokio::time::interval(PING_INTERVAL);
         let mut counter: u64 = 0;
-        let mut pending: HashMap<u64, oneshot::Sender<Yoke<&'static RawValue, String>>> =
+        // Keeps the originally-sent `Message` alongside the reply channel so
+        // requests still in flight when the connection drops can be resent
+        // once `AuthOk` comes back in on the new one, instead of leaving
+        // their caller waiting on a reply that will never arrive.
+        let mut pending: HashMap<u64, (Message, oneshot::Sender<Yoke<&'static RawValue, String>>)> =
             HashMap::new();
+        let mut entity_cache: HashMap<String, EntityCacheEntry> = HashMap::new();
+
+        // The id `subscribe_entities` was sent with, so `HassResponseType::Event`
+        // can tell its compressed pushes apart from the other `subscribe_events`
+        // subscriptions below, which arrive as plain `Event`s instead.
+        let mut entities_subscription_id: Option<u64> = None;
+
+        // Set while a ping is outstanding, cleared on the matching pong;
+        // still set past `PING_TIMEOUT` means the connection is dead.
+        let mut awaiting_pong: Option<Instant> = None;
 
         loop {
             tokio::select! {
@@ -85,6 +378,8 @@ pub async fn create(config: HomeAssistantConfig) -> Client {
                     #[allow(clippy::match_same_arms)]
                     match message {
                         Message::Pong(ts) => {
+                            awaiting_pong = None;
+
                             let ts = i128::from_be_bytes(ts.try_into().unwrap());
                             let ts = OffsetDateTime::from_unix_timestamp_nanos(ts).unwrap();
 
@@ -92,6 +387,7 @@ pub async fn create(config: HomeAssistantConfig) -> Client {
                         }
                         Message::Text(payload) => {
                             // eprintln!("{payload}");
+                            diagnostics.push(payload.as_str());
 
                             let yoked_payload: Yoke<HassResponse, String> = Yoke::attach_to_cart(payload, |s| serde_json::from_str(s).unwrap());
 
@@ -99,6 +395,7 @@ pub async fn create(config: HomeAssistantConfig) -> Client {
 
                             if let Some(error) = &payload.error {
                                 eprintln!("error: {error:?}");
+                                diagnostics.push(format!("error: {error:?}"));
                             }
 
                             match payload.type_ {
@@ -118,40 +415,105 @@ pub async fn create(config: HomeAssistantConfig) -> Client {
                                 }
                                 HassResponseType::AuthInvalid => {
                                     eprintln!("invalid auth");
+                                    diagnostics.push("invalid auth");
+
+                                    if let Some(ready_send) = ready_send.take() {
+                                        let _res = ready_send.send(Err("invalid auth".to_string()));
+                                    }
                                 }
                                 HassResponseType::AuthOk => {
-                                    ready_send.take().unwrap().send(()).unwrap();
+                                    if let Some(ready_send) = ready_send.take() {
+                                        let _res = ready_send.send(Ok(()));
+                                    }
 
                                     counter += 1;
-                                    let counter = counter;
+                                    entities_subscription_id = Some(counter);
 
                                     connection
                                         .send(HassRequest {
                                             id: Some(counter),
-                                            inner: HassRequestKind::SubscribeEvents {
-                                                event_type: Some("state_changed".to_string()),
-                                            },
+                                            inner: HassRequestKind::SubscribeEntities,
                                         }.to_request())
                                         .await
                                         .unwrap();
+
+                                    // Area/device/entity registry changes (rooms
+                                    // renamed, entities moved between devices, a
+                                    // whole device removed, ...) aren't reflected
+                                    // in `subscribe_entities` at all, so they need
+                                    // their own `subscribe_events` subscriptions.
+                                    for event_type in [
+                                        "area_registry_updated",
+                                        "device_registry_updated",
+                                        "entity_registry_updated",
+                                    ] {
+                                        counter += 1;
+
+                                        connection
+                                            .send(HassRequest {
+                                                id: Some(counter),
+                                                inner: HassRequestKind::SubscribeEvents {
+                                                    event_type: Some(event_type.to_string()),
+                                                },
+                                            }.to_request())
+                                            .await
+                                            .unwrap();
+                                    }
+
+                                    // Requests sent before a disconnect are still
+                                    // sitting in `pending` waiting on a reply that
+                                    // will never come on the old connection, so
+                                    // resend them now that we're authenticated
+                                    // again rather than leaving their caller
+                                    // hanging forever.
+                                    for request in pending.values().map(|(request, _)| request.clone()).collect::<Vec<_>>() {
+                                        connection.send(request).await.unwrap();
+                                    }
                                 }
                                 HassResponseType::Result => {
                                     let id = payload.id.unwrap();
                                     let payload = yoked_payload.try_map_project(move |yk, _| yk.result.ok_or(()));
 
-                                    if let (Some(channel), Ok(payload)) = (pending.remove(&id), payload) {
+                                    if let (Some((_, channel)), Ok(payload)) = (pending.remove(&id), payload) {
                                         let _res = channel.send(payload);
                                     }
                                 }
                                 HassResponseType::Event => {
-                                    let payload = yoked_payload.map_project(move |yk, _| yk.event.unwrap());
-                                    let _res = broadcast_send.send(Arc::new(payload));
+                                    let raw = payload.event.unwrap();
+
+                                    if payload.id == entities_subscription_id {
+                                        for event in apply_compressed_state_event(raw, &mut entity_cache) {
+                                            let _res = broadcast_send.send(event);
+                                        }
+                                    } else {
+                                        // A plain `subscribe_events` push (not
+                                        // the compressed `subscribe_entities`
+                                        // shape above), already the wire format
+                                        // `Event` deserializes from directly.
+                                        let event: Yoke<Event<'static>, String> = Yoke::attach_to_cart(
+                                            raw.get().to_string(),
+                                            |s| serde_json::from_str(s).unwrap(),
+                                        );
+                                        let _res = broadcast_send.send(Arc::new(event));
+                                    }
                                 }
                             }
                         }
                         Message::Close(_) => {
-                            // eprintln!("Reconnecting...");
-                            // connection = tokio_tungstenite::connect_async(&uri).await.unwrap().0;
+                            eprintln!("connection closed, reconnecting");
+                            diagnostics.push("connection closed, reconnecting");
+                            let _res = connection_status_send.send(ConnectionStatus::Disconnected);
+
+                            connection = connect_with_retry(
+                                &reconnect_uri,
+                                reconnect_tls,
+                                reconnect_tls_fingerprint.as_deref(),
+                            )
+                            .await;
+                            awaiting_pong = None;
+
+                            diagnostics.push("reconnected");
+                            let _res = connection_status_send.send(ConnectionStatus::Connected);
                         }
                         _ => {}
                     }
@@ -160,26 +522,100 @@ pub async fn create(config: HomeAssistantConfig) -> Client {
                     counter += 1;
                     let counter = counter;
 
-                    connection.send(HassRequest {
+                    let request = HassRequest {
                         id: Some(counter),
                         inner,
-                    }.to_request()).await.unwrap();
+                    }.to_request();
+
+                    connection.send(request.clone()).await.unwrap();
 
-                    pending.insert(counter, reply);
+                    pending.insert(counter, (request, reply));
                 }
                 _ = interval.tick() => {
+                    if awaiting_pong.is_some_and(|sent_at| sent_at.elapsed() > PING_TIMEOUT) {
+                        eprintln!("no pong within {PING_TIMEOUT:?}, reconnecting");
+                        diagnostics.push(format!("no pong within {PING_TIMEOUT:?}, reconnecting"));
+                        let _res = connection_status_send.send(ConnectionStatus::Disconnected);
+
+                        connection = connect_with_retry(
+                            &reconnect_uri,
+                            reconnect_tls,
+                            reconnect_tls_fingerprint.as_deref(),
+                        )
+                        .await;
+                        awaiting_pong = None;
+
+                        diagnostics.push("reconnected");
+                        let _res = connection_status_send.send(ConnectionStatus::Connected);
+
+                        continue;
+                    }
+
+                    // Only start the clock on the first unanswered ping, so
+                    // it keeps counting across later ones until a pong.
+                    awaiting_pong.get_or_insert_with(Instant::now);
                     connection.send(Message::Ping(OffsetDateTime::now_utc().unix_timestamp_nanos().to_be_bytes().to_vec())).await.unwrap();
                 }
             }
         }
     });
 
-    ready_recv.await.unwrap();
+    ready_recv.await.unwrap()?;
 
-    Client {
-        base: Url::parse(&format!("https://{}/", config.uri)).unwrap(),
+    let http_scheme = if config.tls { "https" } else { "http" };
+
+    let mut http_builder = network::client_builder();
+    if config.tls_fingerprint.is_some() {
+        // reqwest has no built-in hook for fingerprint-only pinning, so the
+        // REST calls (just `upload_local_media`) fall back to skipping
+        // certificate validation entirely; the websocket connection above is
+        // what actually enforces the pin. Only reachable once someone has
+        // already opted into `tls-fingerprint` for a trusted self-signed
+        // instance.
+        http_builder = http_builder.danger_accept_invalid_certs(true);
+    }
+
+    Ok(Client {
+        base: Url::parse(&format!("{http_scheme}://{}/", config.uri)).unwrap(),
+        token: Arc::from(config.token.as_str()),
+        http: http_builder.build().unwrap(),
         sender,
         broadcast_channel,
+        connection_status,
+        diagnostics,
+    })
+}
+
+/// Reconnects to `uri`, retrying every [`RECONNECT_BACKOFF`] until it
+/// succeeds, for the background task in [`create`] to fall back on once it's
+/// declared the current connection dead.
+async fn connect_with_retry(uri: &str, tls: bool, tls_fingerprint: Option<&str>) -> WsStream {
+    loop {
+        let attempt = match tls_fingerprint {
+            Some(fingerprint) if tls => {
+                // Already validated by `create` before this reconnect loop
+                // was ever spawned, so this can't fail here.
+                let tls_config = tls::pinned_client_config(fingerprint)
+                    .expect("tls-fingerprint was already validated in create");
+
+                tokio_tungstenite::connect_async_tls_with_config(
+                    uri,
+                    None,
+                    false,
+                    Some(tokio_tungstenite::Connector::Rustls(tls_config)),
+                )
+                .await
+            }
+            _ => tokio_tungstenite::connect_async(uri).await,
+        };
+
+        match attempt {
+            Ok((connection, _response)) => return connection,
+            Err(err) => {
+                eprintln!("reconnect attempt failed: {err}; retrying in {RECONNECT_BACKOFF:?}");
+                tokio::time::sleep(RECONNECT_BACKOFF).await;
+            }
+        }
     }
 }
 
@@ -192,8 +628,8 @@ struct HassResponse<'a> {
     result: Option<&'a RawValue>,
     #[serde(borrow)]
     error: Option<Error<'a>>,
-    #[serde(borrow, bound(deserialize = "'a: 'de"))]
-    event: Option<Event<'a>>,
+    #[serde(borrow)]
+    event: Option<&'a RawValue>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -208,6 +644,134 @@ pub struct Error<'a> {
 #[serde(rename_all = "snake_case", tag = "event_type", content = "data")]
 pub enum Event<'a> {
     StateChanged(#[serde(borrow, bound(deserialize = "'a: 'de"))] events::StateChanged<'a>),
+    ShoppingListUpdated(serde::de::IgnoredAny),
+    AreaRegistryUpdated(serde::de::IgnoredAny),
+    DeviceRegistryUpdated(serde::de::IgnoredAny),
+    EntityRegistryUpdated(serde::de::IgnoredAny),
+    #[serde(other)]
+    Other,
+}
+
+/// A `subscribe_entities` push, which reports the current state of every
+/// entity on subscribe (`a`, "added") and, from then on, only the keys that
+/// changed per entity (`c`, "changed") rather than a full `state_changed`
+/// payload. This is what lets `subscribe_entities` send far less JSON per
+/// update than `subscribe_events`.
+#[derive(Deserialize, Debug, Default)]
+struct CompressedStateEvent<'a> {
+    #[serde(rename = "a", default, borrow)]
+    added: HashMap<Cow<'a, str>, CompressedState<'a>>,
+    #[serde(rename = "c", default, borrow)]
+    changed: HashMap<Cow<'a, str>, CompressedStateDiff<'a>>,
+    #[serde(rename = "r", default, borrow)]
+    removed: Vec<Cow<'a, str>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CompressedState<'a> {
+    #[serde(rename = "s", borrow)]
+    state: Cow<'a, str>,
+    #[serde(rename = "a", default)]
+    attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct CompressedStateDiff<'a> {
+    #[serde(rename = "+", default, borrow)]
+    plus: Option<CompressedStateDiffAdd<'a>>,
+    #[serde(rename = "-", default, borrow)]
+    minus: Option<CompressedStateDiffRemove<'a>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CompressedStateDiffAdd<'a> {
+    #[serde(rename = "s", default, borrow)]
+    state: Option<Cow<'a, str>>,
+    #[serde(rename = "a", default)]
+    attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct CompressedStateDiffRemove<'a> {
+    #[serde(rename = "a", default, borrow)]
+    attributes: Vec<Cow<'a, str>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct EntityCacheEntry {
+    state: String,
+    attributes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Applies one `subscribe_entities` push to `cache` and reconstructs a full
+/// before/after [`Event::StateChanged`] for every entity that actually
+/// changed, so the rest of the client (and the `Oracle`) can keep consuming
+/// whole-state events without knowing the wire format sent by HA.
+fn apply_compressed_state_event(
+    raw: &RawValue,
+    cache: &mut HashMap<String, EntityCacheEntry>,
+) -> Vec<Arc<Yoke<Event<'static>, String>>> {
+    let event: CompressedStateEvent = serde_json::from_str(raw.get()).unwrap();
+    let mut broadcasts = Vec::new();
+
+    for (entity_id, state) in event.added {
+        cache.insert(
+            entity_id.into_owned(),
+            EntityCacheEntry {
+                state: state.state.into_owned(),
+                attributes: state.attributes,
+            },
+        );
+    }
+
+    for (entity_id, diff) in event.changed {
+        let Some(old) = cache.get(entity_id.as_ref()).cloned() else {
+            continue;
+        };
+
+        let mut new = old.clone();
+        if let Some(plus) = diff.plus {
+            if let Some(state) = plus.state {
+                new.state = state.into_owned();
+            }
+            new.attributes.extend(plus.attributes);
+        }
+        if let Some(minus) = diff.minus {
+            for key in minus.attributes {
+                new.attributes.remove(key.as_ref());
+            }
+        }
+
+        let synthetic = serde_json::json!({
+            "event_type": "state_changed",
+            "data": {
+                "entity_id": entity_id.as_ref(),
+                "old_state": {
+                    "entity_id": entity_id.as_ref(),
+                    "state": old.state,
+                    "attributes": old.attributes,
+                },
+                "new_state": {
+                    "entity_id": entity_id.as_ref(),
+                    "state": new.state,
+                    "attributes": new.attributes,
+                },
+            },
+        })
+        .to_string();
+
+        cache.insert(entity_id.into_owned(), new);
+
+        let event: Yoke<Event<'static>, String> =
+            Yoke::attach_to_cart(synthetic, |s| serde_json::from_str(s).unwrap());
+        broadcasts.push(Arc::new(event));
+    }
+
+    for entity_id in event.removed {
+        cache.remove(entity_id.as_ref());
+    }
+
+    broadcasts
 }
 
 #[derive(Deserialize, Copy, Clone, Debug)]
@@ -244,7 +808,41 @@ pub enum HassRequestKind {
     SubscribeEvents {
         event_type: Option<String>,
     },
+    SubscribeEntities,
     CallService(CallServiceRequest),
+    #[serde(rename = "call_service")]
+    CallServiceGeneric(CallServiceRequestGeneric),
+    #[serde(rename = "shopping_list/items")]
+    ShoppingListItems,
+    #[serde(rename = "shopping_list/items/add")]
+    ShoppingListItemsAdd {
+        name: String,
+    },
+    #[serde(rename = "shopping_list/items/update")]
+    ShoppingListItemsUpdate {
+        item_id: String,
+        complete: bool,
+    },
+    #[serde(rename = "shopping_list/items/remove")]
+    ShoppingListItemsRemove {
+        item_id: String,
+    },
+    #[serde(rename = "media_player/browse_media")]
+    BrowseMedia {
+        entity_id: &'static str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        media_content_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        media_content_type: Option<String>,
+    },
+    #[serde(rename = "call_service")]
+    WeatherGetForecasts {
+        domain: &'static str,
+        service: &'static str,
+        service_data: WeatherGetForecastsData,
+        target: CallServiceRequestTarget,
+        return_response: bool,
+    },
 }
 
 impl HassRequest {
@@ -265,11 +863,67 @@ pub struct CallServiceRequestTarget {
     pub entity_id: &'static str,
 }
 
+#[derive(Serialize)]
+pub struct WeatherGetForecastsData {
+    #[serde(rename = "type")]
+    pub forecast_type: ForecastType,
+}
+
+#[derive(Copy, Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastType {
+    Daily,
+    Hourly,
+}
+
+#[derive(Serialize)]
+pub struct CallServiceRequestGeneric {
+    pub domain: String,
+    pub service: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_data: Option<serde_json::Value>,
+    pub target: Option<CallServiceRequestTarget>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case", tag = "domain")]
 pub enum CallServiceRequestData {
     Light(CallServiceRequestLight),
+    Number(CallServiceRequestNumber),
+    Switch(CallServiceRequestSwitch),
     MediaPlayer(CallServiceRequestMediaPlayer),
+    Timer(CallServiceRequestTimer),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "service", content = "service_data")]
+pub enum CallServiceRequestSwitch {
+    TurnOn,
+    TurnOff,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "service", content = "service_data")]
+pub enum CallServiceRequestNumber {
+    SetValue(CallServiceRequestNumberSetValue),
+}
+
+#[derive(Serialize)]
+pub struct CallServiceRequestNumberSetValue {
+    pub value: f32,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case", tag = "service", content = "service_data")]
+pub enum CallServiceRequestTimer {
+    Start(CallServiceRequestTimerStart),
+    Cancel,
+}
+
+#[derive(Serialize)]
+pub struct CallServiceRequestTimerStart {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -285,6 +939,12 @@ pub struct CallServiceRequestLightTurnOn {
     pub brightness: Option<u8>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hs_color: Option<(f32, f32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rgbw_color: Option<(u8, u8, u8, u8)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rgbww_color: Option<(u8, u8, u8, u8, u8)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effect: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -332,19 +992,13 @@ pub struct CallServiceRequestMediaPlayerRepeatSet {
 #[derive(Serialize)]
 pub struct CallServiceRequestMediaPlayerPlayMedia {
     pub media_content_id: String,
-    pub media_content_type: CallServiceRequestMediaPlayerPlayMediaType,
+    pub media_content_type: String,
     pub enqueue: CallServiceRequestMediaPlayerPlayMediaEnqueue,
-}
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum CallServiceRequestMediaPlayerPlayMediaType {
-    Music,
-    Tvshow,
-    Video,
-    Episode,
-    Channel,
-    Playlist,
+    /// Set on a chime played ahead of a `tts.speak` announcement, so
+    /// Sonos/cast targets duck rather than interrupt whatever's already
+    /// playing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub announce: Option<bool>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -393,6 +1047,7 @@ pub mod events {
 pub mod responses {
     use std::{
         borrow::Cow,
+        collections::HashMap,
         fmt::{Display, Formatter},
     };
 
@@ -410,6 +1065,86 @@ pub mod responses {
     #[derive(Deserialize, Yokeable, Debug)]
     pub struct CallServiceResponse {}
 
+    #[derive(Deserialize, Yokeable, Debug)]
+    pub struct Ack {}
+
+    /// Response from `POST /api/media_source/local_source/upload`. Fetched
+    /// via a plain REST call rather than the websocket API, so it's owned
+    /// rather than [`Yokeable`].
+    #[derive(Deserialize, Debug)]
+    pub struct UploadLocalMediaResponse {
+        pub media_content_id: String,
+    }
+
+    #[derive(Deserialize, Yokeable, Debug)]
+    pub struct ShoppingListItems<'a>(#[serde(borrow)] pub Vec<ShoppingListItem<'a>>);
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct ShoppingListItem<'a> {
+        #[serde(borrow)]
+        pub id: Cow<'a, str>,
+        #[serde(borrow)]
+        pub name: Cow<'a, str>,
+        pub complete: bool,
+    }
+
+    #[derive(Deserialize, Yokeable, Debug)]
+    pub struct BrowseMediaResult<'a> {
+        #[serde(borrow)]
+        pub title: Cow<'a, str>,
+        #[serde(borrow)]
+        pub media_content_id: Cow<'a, str>,
+        #[serde(borrow)]
+        pub media_content_type: Cow<'a, str>,
+        pub can_play: bool,
+        pub can_expand: bool,
+        #[serde(borrow, default)]
+        pub thumbnail: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub children: Vec<BrowseMediaItem<'a>>,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct BrowseMediaItem<'a> {
+        #[serde(borrow)]
+        pub title: Cow<'a, str>,
+        #[serde(borrow)]
+        pub media_content_id: Cow<'a, str>,
+        #[serde(borrow)]
+        pub media_content_type: Cow<'a, str>,
+        pub can_play: bool,
+        pub can_expand: bool,
+        #[serde(borrow, default)]
+        pub thumbnail: Option<Cow<'a, str>>,
+    }
+
+    /// Response to a `weather.get_forecasts` service call with
+    /// `return_response: true`, keyed by the entity id the forecast was
+    /// requested for.
+    #[derive(Deserialize, Yokeable, Debug)]
+    pub struct WeatherGetForecastsResponse<'a>(
+        #[serde(borrow)] pub HashMap<Cow<'a, str>, WeatherForecastList<'a>>,
+    );
+
+    #[derive(Deserialize, Debug)]
+    pub struct WeatherForecastList<'a> {
+        #[serde(borrow)]
+        pub forecast: Vec<ForecastEntry<'a>>,
+    }
+
+    /// A single daily or hourly forecast entry. Unlike the `forecast` field
+    /// that older Home Assistant versions embedded in `weather` entity
+    /// attributes, hourly entries here may omit `templow`, so it stays
+    /// optional.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct ForecastEntry<'a> {
+        #[serde(borrow)]
+        pub condition: Cow<'a, str>,
+        pub temperature: f32,
+        #[serde(default, rename = "templow")]
+        pub temperature_low: Option<f32>,
+    }
+
     #[derive(Deserialize, Yokeable, Debug)]
     pub struct AreaRegistryList<'a>(#[serde(borrow)] pub Vec<Area<'a>>);
 
@@ -576,6 +1311,45 @@ pub mod responses {
                     StateAttributes::Weather(serde_json::from_str(attributes.get()).unwrap())
                 }
                 "light" => StateAttributes::Light(serde_json::from_str(attributes.get()).unwrap()),
+                "binary_sensor" => {
+                    StateAttributes::BinarySensor(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "sensor" => {
+                    StateAttributes::Sensor(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "person" => {
+                    StateAttributes::Person(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "device_tracker" => {
+                    StateAttributes::DeviceTracker(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "timer" => StateAttributes::Timer(serde_json::from_str(attributes.get()).unwrap()),
+                "vacuum" => {
+                    StateAttributes::Vacuum(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "cover" => StateAttributes::Cover(serde_json::from_str(attributes.get()).unwrap()),
+                "humidifier" => {
+                    StateAttributes::Humidifier(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "climate" => {
+                    StateAttributes::Climate(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "update" => {
+                    StateAttributes::Update(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "plant" => StateAttributes::Plant(serde_json::from_str(attributes.get()).unwrap()),
+                "remote" => {
+                    StateAttributes::Remote(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "button" => {
+                    StateAttributes::Button(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "number" => {
+                    StateAttributes::Number(serde_json::from_str(attributes.get()).unwrap())
+                }
+                "switch" => {
+                    StateAttributes::Switch(serde_json::from_str(attributes.get()).unwrap())
+                }
                 _ => StateAttributes::Unknown,
             };
 
@@ -595,9 +1369,213 @@ pub mod responses {
         Camera(#[serde(borrow)] StateCameraAttributes<'a>),
         Weather(#[serde(borrow)] StateWeatherAttributes<'a>),
         Light(#[serde(borrow)] StateLightAttributes<'a>),
+        BinarySensor(#[serde(borrow)] StateBinarySensorAttributes<'a>),
+        Sensor(#[serde(borrow)] StateSensorAttributes<'a>),
+        Person(#[serde(borrow)] StateDeviceTrackerAttributes<'a>),
+        DeviceTracker(#[serde(borrow)] StateDeviceTrackerAttributes<'a>),
+        Timer(#[serde(borrow)] StateTimerAttributes<'a>),
+        Vacuum(#[serde(borrow)] StateVacuumAttributes<'a>),
+        Cover(#[serde(borrow)] StateCoverAttributes<'a>),
+        Humidifier(#[serde(borrow)] StateHumidifierAttributes<'a>),
+        Climate(#[serde(borrow)] StateClimateAttributes<'a>),
+        Update(#[serde(borrow)] StateUpdateAttributes<'a>),
+        Plant(#[serde(borrow)] StatePlantAttributes<'a>),
+        Remote(#[serde(borrow)] StateRemoteAttributes<'a>),
+        Button(#[serde(borrow)] StateButtonAttributes<'a>),
+        Number(#[serde(borrow)] StateNumberAttributes<'a>),
+        Switch(#[serde(borrow)] StateSwitchAttributes<'a>),
         Unknown,
     }
 
+    /// Attributes of a `number` entity, e.g. a Sonos speaker's bass/treble
+    /// controls (companion entities on the same device as a `media_player`,
+    /// see [`crate::oracle::MediaPlayerSpeaker::bass_entity`]). The current
+    /// value is the entity's `state`, not an attribute.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateNumberAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        pub min: f32,
+        pub max: f32,
+        pub step: f32,
+    }
+
+    /// Attributes of a `switch` entity, e.g. a Sonos speaker's loudness or
+    /// night sound toggle (companion entities on the same device as a
+    /// `media_player`, see
+    /// [`crate::oracle::MediaPlayerSpeaker::loudness_entity`]). Whether the
+    /// switch is on is the entity's `state`, not an attribute.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateSwitchAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub device_class: Option<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `cover` entity. `device_class` distinguishes a garage
+    /// door from blinds, shutters, etc.; only `garage` has a dedicated card
+    /// today.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateCoverAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub device_class: Option<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `humidifier` entity. `device_class` is `humidifier` or
+    /// `dehumidifier`; both are controlled the same way (a target humidity
+    /// and a mode), so they share this struct and card.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateHumidifierAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub device_class: Option<Cow<'a, str>>,
+        pub min_humidity: u8,
+        pub max_humidity: u8,
+        pub humidity: Option<u8>,
+        #[serde(borrow)]
+        pub mode: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub available_modes: Vec<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `climate` (thermostat) entity.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateClimateAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub hvac_modes: Vec<Cow<'a, str>>,
+        pub current_temperature: Option<f64>,
+        pub temperature: Option<f64>,
+        #[serde(borrow)]
+        pub fan_mode: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub fan_modes: Vec<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub preset_mode: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub preset_modes: Vec<Cow<'a, str>>,
+    }
+
+    /// Attributes of an `update` entity. `state` is `"on"` while an update is
+    /// available and `"off"` once up to date.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateUpdateAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub installed_version: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub latest_version: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub release_summary: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub release_url: Option<Cow<'a, str>>,
+        #[serde(default)]
+        pub in_progress: bool,
+    }
+
+    /// Attributes of a `button` entity, e.g. a `wake_on_lan` "Wake" button.
+    /// Pressing it has no state of its own to track; the entity just exists
+    /// to trigger `button.press`.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateButtonAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateBinarySensorAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub device_class: Option<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `sensor` entity. The sensor's numeric (or otherwise)
+    /// reading itself is the entity's `state`, not an attribute.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateSensorAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub unit_of_measurement: Option<Cow<'a, str>>,
+        /// Today's per-hour rate curve, exposed as a plain array attribute by
+        /// electricity price integrations such as Nordpool. `None` for
+        /// sensors that don't expose one (including most non-price sensors).
+        #[serde(default)]
+        pub today: Option<Vec<f64>>,
+        /// e.g. `moisture` for a standalone soil moisture sensor not attached
+        /// to a `plant` entity.
+        #[serde(borrow, default)]
+        pub device_class: Option<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `plant` entity. Home Assistant's plant monitor
+    /// integration reports these as a bundle on one entity rather than
+    /// separate sensors; `state` is `ok` or `problem`.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StatePlantAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        pub moisture: Option<u8>,
+    }
+
+    /// Attributes of a `person` or `device_tracker` entity. Home Assistant
+    /// gives both domains the same shape: a zone name (`home`, `not_home`, or
+    /// a custom zone) as the entity's `state`, and GPS coordinates when the
+    /// entity is a `gps`-source tracker rather than a router-presence one.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateDeviceTrackerAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        pub latitude: Option<f64>,
+        pub longitude: Option<f64>,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateTimerAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub duration: Cow<'a, str>,
+        #[serde(with = "time::serde::iso8601::option", default)]
+        pub finishes_at: Option<time::OffsetDateTime>,
+    }
+
+    /// Attributes of a `vacuum.*` entity. Robot position and room segments
+    /// aren't part of the core `vacuum` domain, so a targeted clean is done
+    /// by segment id (see [`crate::config::VacuumRoomConfig`]) rather than by
+    /// tapping a point on the map.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateVacuumAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        pub battery_level: Option<u8>,
+        #[serde(borrow)]
+        pub fan_speed: Option<Cow<'a, str>>,
+        #[serde(borrow)]
+        pub entity_picture: Option<Cow<'a, str>>,
+    }
+
+    /// Attributes of a `remote.*` entity (e.g. a Harmony hub or Broadlink
+    /// blaster). `current_activity` is only populated by activity-based
+    /// integrations like Harmony; plain IR blasters just have `activity_list`
+    /// empty and no current activity.
+    #[derive(Deserialize, Debug, Clone)]
+    pub struct StateRemoteAttributes<'a> {
+        #[serde(borrow)]
+        pub friendly_name: Option<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub activity_list: Vec<Cow<'a, str>>,
+        #[serde(borrow, default)]
+        pub current_activity: Option<Cow<'a, str>>,
+    }
+
     #[derive(Deserialize, Debug, Clone, Copy)]
     pub struct StateSunAttributes {
         // next_dawn: time::OffsetDateTime,
@@ -797,12 +1775,331 @@ pub mod responses {
         pub color_temp_kelvin: Option<u16>,
         pub color_temp: Option<u16>,
         pub hs_color: Option<(f32, f32)>,
+        #[serde(default)]
+        pub rgbw_color: Option<(u8, u8, u8, u8)>,
+        #[serde(default)]
+        pub rgbww_color: Option<(u8, u8, u8, u8, u8)>,
+        #[serde(default)]
+        pub effect_list: Option<Vec<String>>,
+        #[serde(default)]
+        pub effect: Option<String>,
     }
 
-    #[derive(Deserialize, Debug, Clone, Copy)]
+    #[derive(Default, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
     #[serde(rename_all = "snake_case")]
     pub enum ColorMode {
         ColorTemp,
         Xy,
+        Rgbw,
+        Rgbww,
+        Brightness,
+        /// Any color mode this app doesn't have dedicated handling for --
+        /// `onoff`, `hs`, `rgb`, `white`, and anything HA adds in the future.
+        /// Treated the same as no color control at all, rather than failing
+        /// to deserialize and panicking the client task.
+        #[default]
+        #[serde(other)]
+        Unsupported,
+    }
+}
+
+/// A [`Client`] backed by a small canned dataset instead of a live Home
+/// Assistant websocket. Used by demo mode (`--demo`) and lets pages, the
+/// `Oracle`, and its update logic be exercised without a real HA instance.
+pub mod mock {
+    use std::sync::Arc;
+
+    use serde_json::{json, value::RawValue};
+    use tokio::sync::{broadcast, mpsc, oneshot};
+    use yoke::Yoke;
+
+    use super::{Client, Event, HassRequestKind};
+
+    /// Spawns a [`Client`] whose requests are answered from the fixtures
+    /// below rather than over a websocket. No `state_changed` events are
+    /// ever broadcast, so the mock is static: the panel shows a snapshot of
+    /// the fixture data rather than a live-updating home.
+    pub async fn create() -> Client {
+        let (sender, mut recv) = mpsc::channel(10);
+        let (broadcast_channel, _broadcast_recv) =
+            broadcast::channel::<Arc<Yoke<Event<'static>, String>>>(10);
+        let (connection_status, _connection_status_recv) = broadcast::channel(4);
+
+        tokio::spawn(async move {
+            while let Some((request, reply)) = recv.recv().await {
+                let _res = reply.send(response_for(&request));
+            }
+        });
+
+        Client {
+            base: url::Url::parse("https://demo.local/").unwrap(),
+            token: Arc::from(""),
+            http: reqwest::Client::new(),
+            sender,
+            broadcast_channel,
+            connection_status,
+            diagnostics: Arc::default(),
+        }
+    }
+
+    fn response_for(request: &HassRequestKind) -> Yoke<&'static RawValue, String> {
+        let json = match request {
+            HassRequestKind::AreaRegistry => json!([{
+                "aliases": [],
+                "area_id": "living_room",
+                "name": "Living Room",
+                "picture": null,
+            }])
+            .to_string(),
+            HassRequestKind::DeviceRegistry => json!([{
+                "area_id": "living_room",
+                "configuration_url": null,
+                "config_entries": [],
+                "connections": [],
+                "disabled_by": null,
+                "entry_type": null,
+                "hw_version": null,
+                "id": "demo_device",
+                "identifiers": [],
+                "manufacturer": null,
+                "model": null,
+                "name_by_user": null,
+                "name": "Demo Hub",
+                "sw_version": null,
+                "via_device_id": null,
+            }])
+            .to_string(),
+            HassRequestKind::EntityRegistry => json!([
+                {
+                    "area_id": null,
+                    "config_entry_id": null,
+                    "device_id": "demo_device",
+                    "disabled_by": null,
+                    "entity_category": null,
+                    "entity_id": "light.living_room_ceiling",
+                    "has_entity_name": true,
+                    "hidden_by": null,
+                    "icon": null,
+                    "id": "demo_light",
+                    "name": null,
+                    "original_name": "Ceiling Light",
+                    "platform": "demo",
+                    "translation_key": null,
+                    "unique_id": "demo_light",
+                },
+                {
+                    "area_id": null,
+                    "config_entry_id": null,
+                    "device_id": "demo_device",
+                    "disabled_by": null,
+                    "entity_category": null,
+                    "entity_id": "media_player.living_room_speaker",
+                    "has_entity_name": true,
+                    "hidden_by": null,
+                    "icon": null,
+                    "id": "demo_speaker",
+                    "name": null,
+                    "original_name": "Speaker",
+                    "platform": "demo",
+                    "translation_key": null,
+                    "unique_id": "demo_speaker",
+                },
+            ])
+            .to_string(),
+            HassRequestKind::GetStates => json!([
+                {
+                    "entity_id": "sun.sun",
+                    "state": "above_horizon",
+                    "attributes": {
+                        "elevation": 45.0,
+                        "azimuth": 180.0,
+                        "rising": false,
+                    },
+                },
+                {
+                    "entity_id": "light.living_room_ceiling",
+                    "state": "on",
+                    "attributes": {
+                        "min_color_temp_kelvin": 2000,
+                        "max_color_temp_kelvin": 6500,
+                        "min_mireds": 153,
+                        "max_mireds": 500,
+                        "supported_color_modes": ["color_temp"],
+                        "mode": null,
+                        "dynamics": null,
+                        "friendly_name": "Living Room Ceiling Light",
+                        "color_mode": "color_temp",
+                        "brightness": 200.0,
+                        "color_temp_kelvin": 3000,
+                        "color_temp": 333,
+                        "hs_color": null,
+                    },
+                },
+                {
+                    "entity_id": "media_player.living_room_speaker",
+                    "state": "playing",
+                    "attributes": {
+                        "source_list": [],
+                        "group_members": [],
+                        "volume_level": 0.4,
+                        "is_volume_muted": false,
+                        "media_content_id": null,
+                        "media_content_type": "music",
+                        "media_duration": 210.0,
+                        "media_position": 42.0,
+                        "media_title": "Demo Song",
+                        "media_artist": "Demo Artist",
+                        "media_album_name": "Demo Album",
+                        "source": null,
+                        "shuffle": false,
+                        "repeat": "off",
+                        "queue_position": null,
+                        "queue_size": null,
+                        "device_class": null,
+                        "friendly_name": "Living Room Speaker",
+                        "entity_picture": null,
+                    },
+                },
+                {
+                    "entity_id": "vacuum.robot",
+                    "state": "docked",
+                    "attributes": {
+                        "friendly_name": "Robot Vacuum",
+                        "battery_level": 100,
+                        "fan_speed": "balanced",
+                        "entity_picture": null,
+                    },
+                },
+                {
+                    "entity_id": "cover.garage_door",
+                    "state": "closed",
+                    "attributes": {
+                        "friendly_name": "Garage Door",
+                        "device_class": "garage",
+                    },
+                },
+                {
+                    "entity_id": "humidifier.basement",
+                    "state": "on",
+                    "attributes": {
+                        "friendly_name": "Basement Dehumidifier",
+                        "device_class": "dehumidifier",
+                        "min_humidity": 30,
+                        "max_humidity": 80,
+                        "humidity": 45,
+                        "mode": "auto",
+                        "available_modes": ["normal", "auto", "away"],
+                    },
+                },
+                {
+                    "entity_id": "climate.living_room",
+                    "state": "heat",
+                    "attributes": {
+                        "friendly_name": "Living Room Thermostat",
+                        "hvac_modes": ["off", "heat", "cool", "auto"],
+                        "current_temperature": 21.0,
+                        "temperature": 22.0,
+                        "fan_mode": "auto",
+                        "fan_modes": ["auto", "low", "medium", "high"],
+                        "preset_mode": "comfort",
+                        "preset_modes": ["eco", "comfort", "away", "boost"],
+                    },
+                },
+                {
+                    "entity_id": "update.esp_firmware",
+                    "state": "on",
+                    "attributes": {
+                        "friendly_name": "Kitchen Sensor Firmware",
+                        "installed_version": "1.2.0",
+                        "latest_version": "1.3.0",
+                        "release_summary": "Bug fixes and improved wifi stability.",
+                        "release_url": "https://example.com/releases/1.3.0",
+                        "in_progress": false,
+                    },
+                },
+                {
+                    "entity_id": "sensor.processor_use",
+                    "state": "12.3",
+                    "attributes": {
+                        "friendly_name": "Processor Use",
+                        "unit_of_measurement": "%",
+                    },
+                },
+                {
+                    "entity_id": "sensor.memory_use_percent",
+                    "state": "48.1",
+                    "attributes": {
+                        "friendly_name": "Memory Use",
+                        "unit_of_measurement": "%",
+                    },
+                },
+                {
+                    "entity_id": "sensor.disk_use_percent_home",
+                    "state": "63.5",
+                    "attributes": {
+                        "friendly_name": "Disk Use",
+                        "unit_of_measurement": "%",
+                    },
+                },
+                {
+                    "entity_id": "sensor.processor_temperature",
+                    "state": "52.0",
+                    "attributes": {
+                        "friendly_name": "Processor Temperature",
+                        "unit_of_measurement": "°C",
+                    },
+                },
+                {
+                    "entity_id": "person.alex",
+                    "state": "home",
+                    "attributes": {
+                        "friendly_name": "Alex",
+                        "latitude": 51.5074,
+                        "longitude": -0.1278,
+                    },
+                },
+                {
+                    "entity_id": "device_tracker.alex_phone",
+                    "state": "home",
+                    "attributes": {
+                        "friendly_name": "Alex's Phone",
+                        "latitude": 51.5074,
+                        "longitude": -0.1278,
+                    },
+                },
+            ])
+            .to_string(),
+            HassRequestKind::CallService(_) | HassRequestKind::CallServiceGeneric(_) => {
+                "{}".to_string()
+            }
+            HassRequestKind::ShoppingListItems => "[]".to_string(),
+            HassRequestKind::ShoppingListItemsAdd { .. }
+            | HassRequestKind::ShoppingListItemsUpdate { .. }
+            | HassRequestKind::ShoppingListItemsRemove { .. } => "{}".to_string(),
+            HassRequestKind::BrowseMedia { .. } => json!({
+                "title": "Demo Library",
+                "media_content_id": "demo",
+                "media_content_type": "directory",
+                "can_play": false,
+                "can_expand": true,
+                "thumbnail": null,
+                "children": [],
+            })
+            .to_string(),
+            HassRequestKind::WeatherGetForecasts { .. } => json!({
+                "weather.demo": {
+                    "forecast": [
+                        {"condition": "sunny", "temperature": 22.0, "templow": 14.0},
+                        {"condition": "cloudy", "temperature": 19.0, "templow": 12.0},
+                    ],
+                },
+            })
+            .to_string(),
+            HassRequestKind::Auth { .. }
+            | HassRequestKind::SubscribeEvents { .. }
+            | HassRequestKind::SubscribeEntities => "{}".to_string(),
+        };
+
+        Yoke::attach_to_cart(json, |s| serde_json::from_str::<&RawValue>(s).unwrap())
     }
 }
@@ -0,0 +1,90 @@
+//! Push-to-talk intercom between rooms (`intercom` build feature): captures
+//! a clip on the panel's own microphone via [`start`]/[`Recording::finish`],
+//! which `main.rs` then uploads through
+//! [`crate::oracle::Oracle::upload_intercom_clip`] and plays back on the
+//! target room's speaker via
+//! [`crate::oracle::EloquentSpeaker::play_intercom_clip`].
+//!
+//! Unlike [`crate::sound`], which synthesizes tones for the panel's own
+//! local output, this module only ever captures — playback always happens
+//! on a Home Assistant `media_player` entity.
+
+use std::sync::Arc;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use parking_lot::Mutex;
+
+/// A microphone recording in progress, started by [`start`] and consumed by
+/// [`Self::finish`] once the user releases the push-to-talk button.
+pub struct Recording {
+    stream: cpal::Stream,
+    samples: Arc<Mutex<Vec<f32>>>,
+    sample_rate: u32,
+    channels: u16,
+}
+
+/// Starts recording from the system's default input device. The returned
+/// [`Recording`] must be kept alive (and eventually finished) for capture to
+/// continue — dropping it stops the stream without encoding anything.
+pub fn start() -> Result<Recording, String> {
+    let device = cpal::default_host()
+        .default_input_device()
+        .ok_or_else(|| "no microphone available".to_string())?;
+    let config = device.default_input_config().map_err(|e| e.to_string())?;
+
+    let sample_rate = config.sample_rate().0;
+    let channels = config.channels();
+    let samples = Arc::new(Mutex::new(Vec::new()));
+
+    let stream_samples = samples.clone();
+    let stream = device
+        .build_input_stream(
+            &config.config(),
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                stream_samples.lock().extend_from_slice(data);
+            },
+            |err| eprintln!("intercom: input stream error: {err}"),
+            None,
+        )
+        .map_err(|e| e.to_string())?;
+
+    stream.play().map_err(|e| e.to_string())?;
+
+    Ok(Recording {
+        stream,
+        samples,
+        sample_rate,
+        channels,
+    })
+}
+
+impl Recording {
+    /// Stops recording and encodes the captured samples as a WAV clip,
+    /// ready for [`crate::oracle::Oracle::upload_intercom_clip`].
+    pub fn finish(self) -> Vec<u8> {
+        let _res = self.stream.pause();
+        drop(self.stream);
+
+        let spec = hound::WavSpec {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+
+        let mut clip = std::io::Cursor::new(Vec::new());
+
+        {
+            let mut writer =
+                hound::WavWriter::new(&mut clip, spec).expect("hard-coded spec is always valid");
+
+            for sample in self.samples.lock().iter() {
+                let _res = writer.write_sample(*sample);
+            }
+
+            let _res = writer.finalize();
+        }
+
+        clip.into_inner()
+    }
+}
@@ -15,12 +15,11 @@ use iced::{
         text_input::{Appearance, Id},
         Text,
     },
-    window::RedrawRequest,
     Alignment, Background, Color, Length, Rectangle, Renderer, Size, Theme, Vector,
 };
 use keyframe::{functions::EaseOutQuint, keyframes, AnimationSequence};
 
-use crate::theme::Icon;
+use crate::theme::{self, Icon};
 
 // text height
 const INITIAL_SEARCH_BOX_SIZE: Size = Size::new(78., 78.);
@@ -241,11 +240,11 @@ where
         match state {
             State::Open if !self.open => {
                 *state = State::close();
-                shell.request_redraw(RedrawRequest::NextFrame);
+                theme::request_animation_frame(shell);
             }
             State::Closed if self.open => {
                 *state = State::open();
-                shell.request_redraw(RedrawRequest::NextFrame);
+                theme::request_animation_frame(shell);
             }
             _ => {}
         }
@@ -283,7 +282,11 @@ where
                     return Status::Ignored;
                 };
 
-                let elapsed = last_draw.elapsed().as_secs_f64();
+                let elapsed = if theme::reduced_motion() {
+                    theme::INSTANT_ANIMATION_STEP.as_secs_f64()
+                } else {
+                    last_draw.elapsed().as_secs_f64()
+                };
                 *last_draw = Instant::now();
 
                 text_opacity.advance_by(elapsed);
@@ -314,7 +317,7 @@ where
                     *state = std::mem::take(next_state);
                 }
 
-                shell.request_redraw(RedrawRequest::NextFrame);
+                theme::request_animation_frame(shell);
 
                 Status::Captured
             }
@@ -2,31 +2,50 @@
 #![allow(clippy::struct_field_names)]
 
 mod config;
+mod config_watch;
 mod context_menus;
+mod diagnostics;
+#[cfg(feature = "discovery")]
+mod discovery;
 mod hass_client;
+#[cfg(feature = "intercom")]
+mod intercom;
 mod magic;
+#[cfg(feature = "mqtt")]
+mod mqtt;
+mod network;
 mod oracle;
 mod pages;
+#[cfg(feature = "remote-http")]
+mod remote_http;
+mod secrets;
+mod sound;
 mod subscriptions;
 mod theme;
+mod tls;
 mod widgets;
 
 use std::{
+    any::TypeId,
     collections::BTreeMap,
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
 
 use iced::{
     alignment::{Horizontal, Vertical},
-    widget::container,
+    keyboard,
+    keyboard::key,
+    subscription,
+    widget::{column, container, mouse_area, row, text},
     window, Application, Command, Element, Length, Renderer, Settings, Size, Subscription, Theme,
 };
 
 use crate::{
     config::Config,
-    oracle::Oracle,
-    theme::Image,
+    oracle::{Announcement, ApplianceFinished, LocalTimerFinished, Oracle, QuickActionKind},
+    theme::{colours::ORANGE, Image},
     widgets::{
         context_menu::ContextMenu,
         floating_element::{Anchor, FloatingElement},
@@ -43,6 +62,12 @@ pub struct Shalom {
     theme: Theme,
     config: Option<Arc<Config>>,
     toast: BTreeMap<u8, Toast>,
+    last_activity: Instant,
+    pending_pin_action: Option<PinGatedAction>,
+    #[cfg(feature = "mqtt")]
+    mqtt_client: Option<rumqttc::AsyncClient>,
+    #[cfg(feature = "intercom")]
+    intercom_recording: Option<intercom::Recording>,
 }
 
 impl Shalom {
@@ -57,18 +82,48 @@ impl Shalom {
     }
 
     fn build_home_route(&self) -> ActivePage {
-        self.home_room.map_or_else(
-            || self.build_omni_route(),
-            |room| self.build_room_route(room),
-        )
+        self.home_room
+            .and_then(|room| self.build_room_route(room))
+            .unwrap_or_else(|| self.build_omni_route())
+    }
+
+    fn is_home_page(&self) -> bool {
+        match (&self.page, self.home_room) {
+            (ActivePage::Room(room), Some(home_room)) => room.room_id() == home_room,
+            (ActivePage::Omni(_), None) => true,
+            _ => false,
+        }
+    }
+
+    /// A stable name for the current page, for [`mqtt::PanelState`].
+    #[cfg(feature = "mqtt")]
+    fn current_page_name(&self) -> &'static str {
+        match &self.page {
+            ActivePage::Loading => "loading",
+            #[cfg(feature = "discovery")]
+            ActivePage::Discovery(_) => "discovery",
+            ActivePage::AuthFailed(_) => "auth_failed",
+            ActivePage::Room(room) => room.room_id(),
+            ActivePage::Omni(_) => "omni",
+            ActivePage::ShoppingList(_) => "shopping_list",
+            ActivePage::Maintenance(_) => "maintenance",
+            ActivePage::Map(_) => "map",
+            ActivePage::Alarms(_) => "alarms",
+            ActivePage::Remote(_) => "remote",
+            ActivePage::Scheduler(_) => "scheduler",
+            ActivePage::Floorplan(_) => "floorplan",
+        }
     }
 
-    fn build_room_route(&self, room: &'static str) -> ActivePage {
-        ActivePage::Room(pages::room::Room::new(
+    /// `None` if `room` was removed from Home Assistant's area registry
+    /// between the caller deciding to open it and this actually running --
+    /// see [`pages::room::Room::new`].
+    fn build_room_route(&self, room: &'static str) -> Option<ActivePage> {
+        Some(ActivePage::Room(pages::room::Room::new(
             room,
             self.oracle.as_ref().unwrap().clone(),
             self.config.as_ref().unwrap().clone(),
-        ))
+        )?))
     }
 
     fn build_omni_route(&self) -> ActivePage {
@@ -77,6 +132,355 @@ impl Shalom {
         ))
     }
 
+    fn build_shopping_list_route(&self) -> ActivePage {
+        ActivePage::ShoppingList(pages::shopping_list::ShoppingList::new(
+            self.oracle.as_ref().unwrap().clone(),
+        ))
+    }
+
+    fn build_maintenance_route(&self) -> ActivePage {
+        ActivePage::Maintenance(pages::maintenance::Maintenance::new(
+            self.oracle.as_ref().unwrap().clone(),
+        ))
+    }
+
+    fn build_alarms_route(&self) -> ActivePage {
+        ActivePage::Alarms(pages::alarms::Alarms::new(
+            self.oracle.as_ref().unwrap().clone(),
+        ))
+    }
+
+    fn build_remote_route(&self) -> ActivePage {
+        ActivePage::Remote(pages::remote::Remotes::new(
+            self.oracle.as_ref().unwrap().clone(),
+        ))
+    }
+
+    fn build_map_route(&self) -> ActivePage {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+        let centre = oracle.map_centre().unwrap();
+
+        ActivePage::Map(pages::map::Map::new(oracle, centre))
+    }
+
+    fn build_scheduler_route(&self) -> ActivePage {
+        ActivePage::Scheduler(pages::scheduler::Scheduler::new(
+            self.oracle.as_ref().unwrap().clone(),
+        ))
+    }
+
+    fn build_floorplan_route(&self) -> ActivePage {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+        let plan = oracle.floorplan().unwrap();
+
+        ActivePage::Floorplan(pages::floorplan::Floorplan::new(oracle.clone(), plan))
+    }
+
+    fn handle_map_event(&mut self, event: pages::map::Event) -> Command<Message> {
+        match event {
+            pages::map::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+        }
+    }
+
+    fn handle_floorplan_event(&mut self, event: pages::floorplan::Event) -> Command<Message> {
+        match event {
+            pages::floorplan::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::floorplan::Event::ToggleLight(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+                let on = oracle
+                    .fetch_light(id)
+                    .and_then(|light| light.on)
+                    .unwrap_or(false);
+
+                Command::perform(
+                    async move { oracle.set_light_state(id, !on).await },
+                    Message::UpdateLightResult,
+                )
+            }
+        }
+    }
+
+    fn handle_maintenance_event(&mut self, event: pages::maintenance::Event) -> Command<Message> {
+        match event {
+            pages::maintenance::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::maintenance::Event::Install(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.install_update(id).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::maintenance::Event::RestartHomeAssistant => {
+                self.request_pin_gated_action("maintenance", PinGatedAction::RestartHomeAssistant)
+            }
+            pages::maintenance::Event::ReloadAll => {
+                self.request_pin_gated_action("maintenance", PinGatedAction::ReloadAll)
+            }
+            pages::maintenance::Event::CheckConfig => {
+                self.request_pin_gated_action("maintenance", PinGatedAction::CheckConfig)
+            }
+            pages::maintenance::Event::ExportDiagnostics => {
+                self.request_pin_gated_action("maintenance", PinGatedAction::ExportDiagnostics)
+            }
+        }
+    }
+
+    /// Runs `action` immediately if `domain` (an entity domain, or a special
+    /// pseudo-domain like `settings`/`maintenance`) isn't PIN-gated,
+    /// otherwise defers it behind a [`ActiveContextMenu::PinPad`] challenge.
+    fn request_pin_gated_action(
+        &mut self,
+        domain: &str,
+        action: PinGatedAction,
+    ) -> Command<Message> {
+        if self
+            .config
+            .as_ref()
+            .is_some_and(|config| config.pin_required(domain))
+        {
+            self.pending_pin_action = Some(action);
+            self.context_menu = Some(ActiveContextMenu::PinPad(
+                context_menus::pin_pad::PinPad::new(),
+            ));
+            sound::play_confirm();
+            Command::none()
+        } else {
+            self.execute_pin_gated_action(action)
+        }
+    }
+
+    fn execute_pin_gated_action(&mut self, action: PinGatedAction) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match action {
+            PinGatedAction::OpenQuickSettings => {
+                // We don't read the backlight entity's current brightness
+                // back from Home Assistant, so just start the slider at
+                // full brightness.
+                self.context_menu = Some(ActiveContextMenu::QuickSettings(
+                    context_menus::quick_settings::QuickSettings::new(100),
+                ));
+                sound::play_confirm();
+                Command::none()
+            }
+            PinGatedAction::RestartHomeAssistant => Command::perform(
+                async move { oracle.restart_home_assistant().await },
+                Message::UpdateLightResult,
+            ),
+            PinGatedAction::ReloadAll => Command::perform(
+                async move { oracle.reload_all().await },
+                Message::UpdateLightResult,
+            ),
+            PinGatedAction::CheckConfig => Command::perform(
+                async move { oracle.check_config().await },
+                Message::UpdateLightResult,
+            ),
+            PinGatedAction::ExportDiagnostics => {
+                let config = self.config.as_ref().unwrap().clone();
+                let log = oracle.diagnostic_log();
+
+                Command::perform(
+                    async move {
+                        tokio::task::spawn_blocking(move || {
+                            diagnostics::export_bundle(&config, &log)
+                        })
+                        .await
+                        .unwrap()
+                    },
+                    Message::DiagnosticsExported,
+                )
+            }
+        }
+    }
+
+    fn handle_alarms_event(&mut self, event: pages::alarms::Event) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match event {
+            pages::alarms::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::alarms::Event::SetEnabled(index, enabled) => Command::perform(
+                async move { oracle.set_alarm_enabled(index, enabled).await },
+                Message::UpdateLightResult,
+            ),
+        }
+    }
+
+    fn handle_remote_event(&mut self, event: pages::remote::Event) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match event {
+            pages::remote::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::remote::Event::StartActivity(id, activity) => Command::perform(
+                async move { oracle.start_remote_activity(id, activity).await },
+                Message::UpdateLightResult,
+            ),
+            pages::remote::Event::StopActivity(id) => Command::perform(
+                async move { oracle.stop_remote_activity(id).await },
+                Message::UpdateLightResult,
+            ),
+            pages::remote::Event::SendCommand(id, command) => Command::perform(
+                async move { oracle.send_remote_command(id, command).await },
+                Message::UpdateLightResult,
+            ),
+        }
+    }
+
+    #[cfg(feature = "remote-http")]
+    fn handle_remote_http_command(&mut self, command: remote_http::Command) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match command {
+            remote_http::Command::OpenRoom(id) => {
+                if let Some(room) = oracle.rooms().into_iter().find(|entry| entry.0 == &*id) {
+                    if let Some(page) = self.build_room_route(room.0) {
+                        self.page = page;
+                    }
+                }
+
+                Command::none()
+            }
+            remote_http::Command::ShowCamera(id) => {
+                if let Some(camera) = oracle.cameras().into_iter().find(|entry| entry.0 == &*id) {
+                    self.context_menu = Some(ActiveContextMenu::CameraDetail(
+                        context_menus::camera_detail::CameraDetail::new(camera.1.name, Vec::new()),
+                    ));
+                }
+
+                Command::none()
+            }
+            remote_http::Command::ScreenOn => Command::perform(
+                async move { oracle.set_backlight(100).await },
+                Message::UpdateLightResult,
+            ),
+            remote_http::Command::ScreenOff => Command::perform(
+                async move { oracle.set_backlight(0).await },
+                Message::UpdateLightResult,
+            ),
+        }
+    }
+
+    #[cfg(feature = "mqtt")]
+    fn handle_mqtt_event(&mut self, event: mqtt::Event) -> Command<Message> {
+        match event {
+            mqtt::Event::Connected(client) => {
+                self.mqtt_client = Some(client);
+                Command::none()
+            }
+            mqtt::Event::Command(mqtt::Command::OpenRoom(id)) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                if let Some(room) = oracle.rooms().into_iter().find(|entry| entry.0 == &*id) {
+                    if let Some(page) = self.build_room_route(room.0) {
+                        self.page = page;
+                    }
+                }
+
+                Command::none()
+            }
+        }
+    }
+
+    #[cfg(feature = "mqtt")]
+    fn publish_mqtt_state(&self) -> Command<Message> {
+        let Some(client) = self.mqtt_client.clone() else {
+            return Command::none();
+        };
+        let Some(config) = self.config.as_ref() else {
+            return Command::none();
+        };
+
+        let idle = config
+            .display
+            .idle_timeout_secs
+            .is_some_and(|timeout| self.last_activity.elapsed() >= Duration::from_secs(timeout));
+
+        Command::perform(
+            mqtt::publish_state(
+                client,
+                config.mqtt.base_topic.clone().into(),
+                self.current_page_name().into(),
+                idle,
+                self.last_activity.elapsed().as_secs(),
+            ),
+            Message::MqttStatePublished,
+        )
+    }
+
+    fn handle_shopping_list_event(
+        &mut self,
+        event: pages::shopping_list::Event,
+    ) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match event {
+            pages::shopping_list::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::shopping_list::Event::AddItem(name) => Command::perform(
+                async move { oracle.add_shopping_list_item(name).await },
+                Message::UpdateLightResult,
+            ),
+            pages::shopping_list::Event::SetComplete(id, complete) => Command::perform(
+                async move {
+                    oracle
+                        .set_shopping_list_item_complete(id.to_string(), complete)
+                        .await;
+                },
+                Message::UpdateLightResult,
+            ),
+            pages::shopping_list::Event::RemoveItem(id) => Command::perform(
+                async move { oracle.remove_shopping_list_item(id.to_string()).await },
+                Message::UpdateLightResult,
+            ),
+        }
+    }
+
+    fn handle_scheduler_event(&mut self, event: pages::scheduler::Event) -> Command<Message> {
+        let oracle = self.oracle.as_ref().unwrap().clone();
+
+        match event {
+            pages::scheduler::Event::Exit => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            pages::scheduler::Event::AddScene(name, scene_entity_id, time) => Command::perform(
+                async move {
+                    oracle
+                        .add_scheduled_scene(name, scene_entity_id, time)
+                        .await;
+                },
+                Message::UpdateLightResult,
+            ),
+            pages::scheduler::Event::SetEnabled(id, enabled) => Command::perform(
+                async move {
+                    oracle.set_scheduled_scene_enabled(id, enabled).await;
+                },
+                Message::UpdateLightResult,
+            ),
+            pages::scheduler::Event::RemoveScene(id) => Command::perform(
+                async move { oracle.remove_scheduled_scene(id).await },
+                Message::UpdateLightResult,
+            ),
+        }
+    }
+
     fn handle_room_event(&mut self, e: pages::room::Message) -> Command<Message> {
         let ActivePage::Room(r) = &mut self.page else {
             return Command::none();
@@ -84,7 +488,17 @@ impl Shalom {
 
         match r.update(e) {
             Some(pages::room::Event::Lights(e)) => self.handle_light_event(e),
+            Some(pages::room::Event::Covers(e)) => self.handle_cover_event(e),
+            Some(pages::room::Event::Climate(e)) => self.handle_climate_event(e),
             Some(pages::room::Event::Listen(e)) => self.handle_listen_event(e),
+            Some(pages::room::Event::OpenSaveScene(room_id)) => {
+                self.context_menu = Some(ActiveContextMenu::SaveScene(
+                    context_menus::save_scene::SaveScene::new(room_id),
+                ));
+                sound::play_confirm();
+
+                Command::none()
+            }
             Some(pages::room::Event::Exit) => {
                 self.page = self.build_omni_route();
                 Command::none()
@@ -105,13 +519,131 @@ impl Shalom {
             }
             pages::room::lights::Event::OpenLightContextMenu(id) => {
                 if let Some(light) = self.oracle.as_ref().and_then(|o| o.fetch_light(id)) {
+                    let oracle = self.oracle.as_ref().unwrap();
+                    let presets = self.config.as_ref().unwrap().light_presets.clone();
+                    let recent = oracle.recent_colours(id);
+                    let power = light.power_entity.and_then(|id| oracle.fetch_sensor(id));
+                    let energy = light.energy_entity.and_then(|id| oracle.fetch_sensor(id));
+                    let circular_colour_picker =
+                        self.config.as_ref().unwrap().display.circular_colour_picker;
+                    let colour_loop = light.effect.as_deref() == Some("colorloop")
+                        || oracle.is_colour_looping(id);
+
                     self.context_menu = Some(ActiveContextMenu::LightControl(
-                        context_menus::light_control::LightControl::new(id, light),
+                        context_menus::light_control::LightControl::new(
+                            id,
+                            light,
+                            presets,
+                            recent,
+                            power,
+                            energy,
+                            circular_colour_picker,
+                            colour_loop,
+                        ),
                     ));
+                    sound::play_confirm();
                 }
 
                 Command::none()
             }
+            pages::room::lights::Event::SetAdaptiveLighting(id, enabled) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_adaptive_lighting(id, enabled).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::lights::Event::AdjustBrightness(id, delta) => {
+                let Some(oracle) = self.oracle.clone() else {
+                    return Command::none();
+                };
+                let Some(light) = oracle.fetch_light(id) else {
+                    return Command::none();
+                };
+
+                let brightness =
+                    (light.brightness.unwrap_or_default() / 255. + delta).clamp(0.0, 1.0);
+
+                Command::perform(
+                    async move { oracle.set_light_brightness(id, brightness).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::lights::Event::SetFullBrightness(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_light_full_brightness(id).await },
+                    Message::UpdateLightResult,
+                )
+            }
+        }
+    }
+
+    fn handle_cover_event(&mut self, event: pages::room::covers::Event) -> Command<Message> {
+        match event {
+            pages::room::covers::Event::OpenCover(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.open_cover(id).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::covers::Event::CloseCover(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.close_cover(id).await },
+                    Message::UpdateLightResult,
+                )
+            }
+        }
+    }
+
+    fn handle_climate_event(&mut self, event: pages::room::climate::Event) -> Command<Message> {
+        match event {
+            pages::room::climate::Event::SetTargetHumidity(id, humidity) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_humidifier_target(id, humidity).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::climate::Event::SetMode(id, mode) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_humidifier_mode(id, mode).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::climate::Event::SetHvacMode(id, mode) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_hvac_mode(id, mode).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::climate::Event::SetFanMode(id, mode) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_fan_mode(id, mode).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::climate::Event::SetPresetMode(id, mode) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.set_preset_mode(id, mode).await },
+                    Message::UpdateLightResult,
+                )
+            }
         }
     }
 
@@ -125,176 +657,897 @@ impl Shalom {
                     Message::UpdateLightResult,
                 )
             }
-            pages::room::listen::Event::SetSpeakerPosition(id, new) => {
+            pages::room::listen::Event::SetGroupVolume(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_group_volume(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerBass(id, new) => {
                 let oracle = self.oracle.as_ref().unwrap().clone();
 
-                Command::perform(
-                    async move { oracle.speaker(id).seek(new).await },
-                    Message::UpdateLightResult,
-                )
+                Command::perform(
+                    async move { oracle.speaker(id).set_bass(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerTreble(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_treble(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerLoudness(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_loudness(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerNightMode(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_night_mode(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerPosition(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).seek(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerPlaying(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move {
+                        let speaker = oracle.speaker(id);
+                        if new {
+                            speaker.play().await;
+                        } else {
+                            speaker.pause().await;
+                        }
+                    },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerMuted(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_mute(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerRepeat(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_repeat(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SpeakerNextTrack(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).next().await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SpeakerPreviousTrack(id) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).previous().await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::SetSpeakerShuffle(id, new) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                Command::perform(
+                    async move { oracle.speaker(id).set_shuffle(new).await },
+                    Message::UpdateLightResult,
+                )
+            }
+            pages::room::listen::Event::PlayTrack(id, uri) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                self.push_toast(Toast {
+                    text: "Song added to queue".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+
+                Command::perform(
+                    async move { oracle.speaker(id).play_track(uri).await },
+                    Message::PlayTrackResult,
+                )
+            }
+            pages::room::listen::Event::PlayMedia(id, content_id, content_type) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                self.push_toast(Toast {
+                    text: "Playing media".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+
+                Command::perform(
+                    async move {
+                        oracle
+                            .speaker(id)
+                            .play_media(content_id, content_type)
+                            .await
+                    },
+                    Message::PlayTrackResult,
+                )
+            }
+            pages::room::listen::Event::QueueTrack(id, uri) => {
+                let oracle = self.oracle.as_ref().unwrap().clone();
+
+                self.push_toast(Toast {
+                    text: "Added to queue".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+
+                Command::perform(
+                    async move { oracle.speaker(id).queue_track(uri).await },
+                    Message::PlayTrackResult,
+                )
+            }
+            pages::room::listen::Event::TransferPlayback(device_id) => {
+                let token = self.config.as_ref().unwrap().spotify.token.clone();
+
+                self.push_toast(Toast {
+                    text: "Playback transferred".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+
+                Command::perform(
+                    async move { pages::room::listen::transfer_playback(&token, device_id).await },
+                    Message::PlayTrackResult,
+                )
+            }
+            pages::room::listen::Event::ShareNowPlaying(url) => {
+                self.push_toast(Toast {
+                    text: "Link copied to clipboard".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+
+                iced::clipboard::write(url)
+            }
+        }
+    }
+}
+
+impl Application for Shalom {
+    type Executor = iced::executor::Default;
+    type Message = Message;
+    type Theme = Theme;
+    type Flags = ();
+
+    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
+        // this is only best-effort to try and prevent blocking when loading
+        // the omni-view, we don't need to block on this at boot
+        tokio::task::spawn_blocking(Image::preload);
+
+        let demo = std::env::args().any(|arg| arg == "--demo");
+
+        #[cfg(feature = "discovery")]
+        let (page, command) = if demo || std::path::Path::new("./config.toml").exists() {
+            (ActivePage::Loading, boot_command(demo))
+        } else {
+            (
+                ActivePage::Discovery(pages::discovery::Discovery::default()),
+                Command::none(),
+            )
+        };
+
+        #[cfg(not(feature = "discovery"))]
+        let (page, command) = (ActivePage::Loading, boot_command(demo));
+
+        let this = Self {
+            page,
+            context_menu: None,
+            oracle: None,
+            home_room: Some("living_room"),
+            theme: Theme::default(),
+            config: None,
+            toast: BTreeMap::new(),
+            last_activity: Instant::now(),
+            pending_pin_action: None,
+            #[cfg(feature = "mqtt")]
+            mqtt_client: None,
+            #[cfg(feature = "intercom")]
+            intercom_recording: None,
+        };
+
+        (this, command)
+    }
+
+    fn title(&self) -> String {
+        String::from("Shalom")
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+        #[allow(clippy::single_match)]
+        match (message, &mut self.page, &mut self.context_menu) {
+            (Message::Loaded(Ok((oracle, config))), _, _) => {
+                subscriptions::configure(&config.cache);
+                network::configure(config.network.proxy.clone());
+                theme::configure_scale_override(config.display.scale);
+                theme::configure_high_contrast(config.display.high_contrast);
+                theme::configure_font_scale(config.display.font_scale);
+                theme::configure_reduced_motion(config.display.reduce_animations);
+                sound::configure(config.sound.clone());
+                self.oracle = Some(oracle);
+                self.config = Some(Arc::new(config));
+                self.page = self.build_home_route();
+                Command::none()
+            }
+            (Message::ConfigReloaded(config), _, _) => {
+                subscriptions::configure(&config.cache);
+                network::configure(config.network.proxy.clone());
+                theme::configure_scale_override(config.display.scale);
+                theme::configure_high_contrast(config.display.high_contrast);
+                theme::configure_font_scale(config.display.font_scale);
+                theme::configure_reduced_motion(config.display.reduce_animations);
+                sound::configure(config.sound.clone());
+
+                if let Some(oracle) = &self.oracle {
+                    oracle.apply_entity_names(config.entity_names.clone());
+                }
+
+                self.config = Some(Arc::new(config));
+
+                Command::none()
+            }
+            (Message::Loaded(Err((reason, config))), _, _) => {
+                self.page =
+                    ActivePage::AuthFailed(pages::auth_failed::AuthFailed::new(reason, config));
+                Command::none()
+            }
+            (Message::AuthFailedEvent(e), ActivePage::AuthFailed(auth_failed), _) => {
+                let config = auth_failed.config().clone();
+
+                match auth_failed.update(e) {
+                    Some(pages::auth_failed::Event::Retry(token)) => {
+                        let mut config = config;
+                        config.home_assistant.token = token;
+                        retry_boot_command(config)
+                    }
+                    None => Command::none(),
+                }
+            }
+            #[cfg(feature = "discovery")]
+            (Message::DiscoveryEvent(e), ActivePage::Discovery(discovery), _) => {
+                match discovery.update(e) {
+                    Some(pages::discovery::Event::Selected(instance)) => Command::perform(
+                        async move {
+                            write_discovered_config(&instance.uri).await;
+                            instance.uri
+                        },
+                        |uri| Message::DiscoveryEvent(pages::discovery::Message::ConfigSaved(uri)),
+                    ),
+                    None => Command::none(),
+                }
+            }
+            (Message::WindowResized(width, _height), _, _) => {
+                theme::report_window_width(width);
+                Command::none()
+            }
+            (Message::UserActivity, _, _) => {
+                self.last_activity = Instant::now();
+                Command::none()
+            }
+            (Message::IdleCheck, _, _) => {
+                let Some(idle_timeout) = self
+                    .config
+                    .as_ref()
+                    .and_then(|config| config.display.idle_timeout_secs)
+                else {
+                    return Command::none();
+                };
+
+                if self.last_activity.elapsed() >= Duration::from_secs(idle_timeout) {
+                    self.context_menu = None;
+
+                    if !self.is_home_page() {
+                        self.page = self.build_home_route();
+                    }
+                }
+
+                Command::none()
+            }
+            (Message::CloseContextMenu, _, _) => {
+                self.context_menu = None;
+                self.pending_pin_action = None;
+                Command::none()
+            }
+            // Keyboard support beyond this is limited: iced 0.12 has no
+            // built-in focus/tab-traversal model for `mouse_area`-based
+            // widgets, and this app's cards/toggles are all built that way,
+            // so a full keyboard-only redesign of every page is out of
+            // scope here. Escape covering "close the open overlay, or back
+            // out to the omni page" is the keypad/dev-without-touch case
+            // this is meant to unblock.
+            (Message::EscapePressed, _, Some(_)) => {
+                self.context_menu = None;
+                self.pending_pin_action = None;
+                Command::none()
+            }
+            (Message::EscapePressed, ActivePage::Omni(_), None) => Command::none(),
+            (Message::EscapePressed, _, None) => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            (Message::OpenOmniPage, _, _) => {
+                self.page = self.build_omni_route();
+                Command::none()
+            }
+            (Message::OpenHomePage, _, _) => {
+                self.page = self.build_home_route();
+                Command::none()
+            }
+            (Message::OmniEvent(e), ActivePage::Omni(r), _) => match r.update(e) {
+                Some(pages::omni::Event::OpenRoom(room)) => {
+                    if let Some(page) = self.build_room_route(room) {
+                        self.page = page;
+                    }
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenRoomSummary(room)) => {
+                    let oracle = self.oracle.as_ref().unwrap();
+
+                    if let Some((_, r)) = oracle.rooms().into_iter().find(|(id, _)| *id == room) {
+                        let lights = r.lights(oracle).into_iter().collect();
+                        let temperature = r
+                            .thermostats(oracle)
+                            .into_values()
+                            .find_map(|thermostat| thermostat.current_temperature);
+                        let now_playing = r.speaker(oracle).and_then(|(_, speaker)| {
+                            speaker.media_title.map(|title| match speaker.media_artist {
+                                Some(artist) => Box::from(format!("{title} - {artist}")),
+                                None => title,
+                            })
+                        });
+
+                        self.context_menu = Some(ActiveContextMenu::RoomSummary(
+                            context_menus::room_summary::RoomSummary::new(
+                                Box::from(r.name.as_ref()),
+                                lights,
+                                temperature,
+                                now_playing,
+                            ),
+                        ));
+                        sound::play_confirm();
+                    }
+
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenShoppingList) => {
+                    self.page = self.build_shopping_list_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenMap) => {
+                    self.page = self.build_map_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenFloorplan) => {
+                    self.page = self.build_floorplan_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenMaintenance) => {
+                    self.page = self.build_maintenance_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenAlarms) => {
+                    self.page = self.build_alarms_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenRemote) => {
+                    self.page = self.build_remote_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenScheduler) => {
+                    self.page = self.build_scheduler_route();
+                    Command::none()
+                }
+                Some(pages::omni::Event::ShowWeatherAlert(message)) => {
+                    self.push_toast(Toast {
+                        text: message.to_string(),
+                        start: Instant::now(),
+                        ttl: Duration::from_secs(10),
+                    });
+                    Command::none()
+                }
+                Some(pages::omni::Event::StartTimer(id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.start_timer(id, Some("00:05:00".to_string())).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::CancelTimer(id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.cancel_timer(id).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::CleanVacuumSegment(id, segment_id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.clean_vacuum_segment(id, segment_id).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::OpenQuickSettings) => {
+                    self.request_pin_gated_action("settings", PinGatedAction::OpenQuickSettings)
+                }
+                Some(pages::omni::Event::AllLightsOff) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.all_lights_off().await },
+                        Message::AllLightsOffDone,
+                    )
+                }
+                Some(pages::omni::Event::RunRoutine(index)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.run_routine(index).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::PressButton(id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.press_button(id).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::StartLocalTimer(label, duration_secs)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.start_local_timer(label, duration_secs).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::CancelLocalTimer(id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.cancel_local_timer(id).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::AddHouseholdNote(author, message)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.add_household_note(author, message).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::RemoveHouseholdNote(id)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.remove_household_note(id).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::SetChoreComplete(index, complete)) => {
+                    let oracle = self.oracle.as_ref().unwrap().clone();
+
+                    Command::perform(
+                        async move { oracle.set_chore_complete(index, complete).await },
+                        Message::UpdateLightResult,
+                    )
+                }
+                Some(pages::omni::Event::StartIntercomRecording) => {
+                    #[cfg(feature = "intercom")]
+                    match intercom::start() {
+                        Ok(recording) => self.intercom_recording = Some(recording),
+                        Err(err) => self.push_toast(Toast {
+                            text: format!("Intercom: {err}"),
+                            start: Instant::now(),
+                            ttl: Duration::from_secs(5),
+                        }),
+                    }
+
+                    #[cfg(not(feature = "intercom"))]
+                    self.push_toast(Toast {
+                        text: "This build wasn't compiled with intercom support".to_string(),
+                        start: Instant::now(),
+                        ttl: Duration::from_secs(5),
+                    });
+
+                    Command::none()
+                }
+                Some(pages::omni::Event::SendIntercomClip(speaker_id)) => {
+                    #[cfg(not(feature = "intercom"))]
+                    let _ = speaker_id;
+
+                    #[cfg(feature = "intercom")]
+                    if let Some(recording) = self.intercom_recording.take() {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+                        let clip = recording.finish();
+
+                        return Command::perform(
+                            async move {
+                                match oracle.upload_intercom_clip(clip).await {
+                                    Ok(media_content_id) => {
+                                        oracle
+                                            .speaker(speaker_id)
+                                            .play_intercom_clip(media_content_id)
+                                            .await;
+                                    }
+                                    Err(err) => eprintln!("intercom: upload failed: {err}"),
+                                }
+                            },
+                            Message::UpdateLightResult,
+                        );
+                    }
+
+                    Command::none()
+                }
+                Some(pages::omni::Event::OpenCameraDetail(id)) => {
+                    if let Some((name, history)) = r.camera_detail(id) {
+                        self.context_menu = Some(ActiveContextMenu::CameraDetail(
+                            context_menus::camera_detail::CameraDetail::new(name, history),
+                        ));
+                        sound::play_confirm();
+                    }
+
+                    Command::none()
+                }
+                None => Command::none(),
+            },
+            (Message::RoomEvent(e), _, _) => self.handle_room_event(e),
+            (Message::ShoppingListEvent(e), ActivePage::ShoppingList(r), _) => match r.update(e) {
+                Some(event) => self.handle_shopping_list_event(event),
+                None => Command::none(),
+            },
+            (Message::MaintenanceEvent(e), ActivePage::Maintenance(r), _) => match r.update(e) {
+                Some(event) => self.handle_maintenance_event(event),
+                None => Command::none(),
+            },
+            (Message::MapEvent(e), ActivePage::Map(r), _) => match r.update(e) {
+                Some(event) => self.handle_map_event(event),
+                None => Command::none(),
+            },
+            (Message::AlarmsEvent(e), ActivePage::Alarms(r), _) => match r.update(e) {
+                Some(event) => self.handle_alarms_event(event),
+                None => Command::none(),
+            },
+            (Message::RemoteEvent(e), ActivePage::Remote(r), _) => match r.update(e) {
+                Some(event) => self.handle_remote_event(event),
+                None => Command::none(),
+            },
+            (Message::SchedulerEvent(e), ActivePage::Scheduler(r), _) => match r.update(e) {
+                Some(event) => self.handle_scheduler_event(event),
+                None => Command::none(),
+            },
+            (Message::FloorplanEvent(e), ActivePage::Floorplan(r), _) => match r.update(e) {
+                Some(event) => self.handle_floorplan_event(event),
+                None => Command::none(),
+            },
+            (Message::LightControlMenu(e), _, Some(ActiveContextMenu::LightControl(menu))) => {
+                match menu.update(e) {
+                    Some(context_menus::light_control::Event::UpdateLightColour {
+                        id,
+                        hue,
+                        saturation,
+                        brightness,
+                    }) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.update_light(id, hue, saturation, brightness).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    Some(context_menus::light_control::Event::UpdateLightWhite { id, white }) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.set_light_white(id, white).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    Some(context_menus::light_control::Event::UpdateLightBrightness {
+                        id,
+                        brightness,
+                    }) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.set_light_brightness(id, brightness).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    Some(context_menus::light_control::Event::SetColourLoop { id, enabled }) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.set_light_colour_loop(id, enabled).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    None => Command::none(),
+                }
+            }
+            (Message::RoomSummaryMenu(e), _, Some(ActiveContextMenu::RoomSummary(menu))) => {
+                match menu.update(e) {
+                    Some(context_menus::room_summary::Event::SetLightState(id, state)) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.set_light_state(id, state).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    None => Command::none(),
+                }
+            }
+            (Message::QuickSettingsMenu(e), _, Some(ActiveContextMenu::QuickSettings(menu))) => {
+                match menu.update(e) {
+                    Some(context_menus::quick_settings::Event::SetBacklight(brightness)) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+
+                        Command::perform(
+                            async move { oracle.set_backlight(brightness).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    None => Command::none(),
+                }
             }
-            pages::room::listen::Event::SetSpeakerPlaying(id, new) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
+            (Message::PinPadMenu(e), _, Some(ActiveContextMenu::PinPad(menu))) => {
+                match menu.update(e) {
+                    Some(context_menus::pin_pad::Event::PinEntered(attempt)) => {
+                        let pin_matches = self.config.as_ref().is_some_and(|config| {
+                            config.security.pin.as_deref() == Some(&*attempt)
+                        });
 
-                Command::perform(
-                    async move {
-                        let speaker = oracle.speaker(id);
-                        if new {
-                            speaker.play().await;
+                        if pin_matches {
+                            self.context_menu = None;
+
+                            match self.pending_pin_action.take() {
+                                Some(action) => self.execute_pin_gated_action(action),
+                                None => Command::none(),
+                            }
                         } else {
-                            speaker.pause().await;
+                            self.push_toast(Toast {
+                                text: "Incorrect PIN".to_string(),
+                                start: Instant::now(),
+                                ttl: Duration::from_secs(3),
+                            });
+
+                            Command::none()
                         }
-                    },
-                    Message::UpdateLightResult,
-                )
+                    }
+                    None => Command::none(),
+                }
             }
-            pages::room::listen::Event::SetSpeakerMuted(id, new) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
-
-                Command::perform(
-                    async move { oracle.speaker(id).set_mute(new).await },
-                    Message::UpdateLightResult,
-                )
+            (Message::CameraDetailMenu(e), _, Some(ActiveContextMenu::CameraDetail(_))) => {
+                match e {}
             }
-            pages::room::listen::Event::SetSpeakerRepeat(id, new) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
-
-                Command::perform(
-                    async move { oracle.speaker(id).set_repeat(new).await },
-                    Message::UpdateLightResult,
-                )
+            (Message::TimerFinishedMenu(e), _, Some(ActiveContextMenu::TimerFinished(_))) => {
+                match e {}
             }
-            pages::room::listen::Event::SpeakerNextTrack(id) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
+            (Message::SaveSceneMenu(e), _, Some(ActiveContextMenu::SaveScene(menu))) => {
+                match menu.update(e) {
+                    Some(context_menus::save_scene::Event::SaveScene { room_id, name }) => {
+                        let oracle = self.oracle.as_ref().unwrap().clone();
+                        self.context_menu = None;
 
-                Command::perform(
-                    async move { oracle.speaker(id).next().await },
-                    Message::UpdateLightResult,
-                )
-            }
-            pages::room::listen::Event::SpeakerPreviousTrack(id) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
+                        self.push_toast(Toast {
+                            text: format!("Saved scene \"{name}\""),
+                            start: Instant::now(),
+                            ttl: Duration::from_secs(3),
+                        });
 
-                Command::perform(
-                    async move { oracle.speaker(id).previous().await },
-                    Message::UpdateLightResult,
-                )
+                        Command::perform(
+                            async move { oracle.save_room_as_scene(room_id, name).await },
+                            Message::UpdateLightResult,
+                        )
+                    }
+                    None => Command::none(),
+                }
             }
-            pages::room::listen::Event::SetSpeakerShuffle(id, new) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
-
-                Command::perform(
-                    async move { oracle.speaker(id).set_shuffle(new).await },
-                    Message::UpdateLightResult,
-                )
+            (Message::AllLightsOffDone(()), _, _) => {
+                self.push_toast(Toast {
+                    text: "All lights turned off".to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
+                Command::none()
             }
-            pages::room::listen::Event::PlayTrack(id, uri) => {
-                let oracle = self.oracle.as_ref().unwrap().clone();
+            (Message::QuickAction(index), _, _) => {
+                let Some(oracle) = self.oracle.clone() else {
+                    return Command::none();
+                };
+                let Some(action) = oracle.quick_actions().get(index) else {
+                    return Command::none();
+                };
 
+                match &action.kind {
+                    &QuickActionKind::RunRoutine(index) => Command::perform(
+                        async move { oracle.run_routine(index).await },
+                        Message::UpdateLightResult,
+                    ),
+                    QuickActionKind::AllLightsOff => Command::perform(
+                        async move { oracle.all_lights_off().await },
+                        Message::AllLightsOffDone,
+                    ),
+                    &QuickActionKind::OpenCamera(id) => {
+                        if let Some(camera) = oracle.cameras().remove(id) {
+                            self.context_menu = Some(ActiveContextMenu::CameraDetail(
+                                context_menus::camera_detail::CameraDetail::new(
+                                    camera.name,
+                                    Vec::new(),
+                                ),
+                            ));
+                            sound::play_confirm();
+                        }
+                        Command::none()
+                    }
+                    QuickActionKind::MuteAllSpeakers => Command::perform(
+                        async move { oracle.mute_all_speakers().await },
+                        Message::UpdateLightResult,
+                    ),
+                }
+            }
+            #[cfg(feature = "remote-http")]
+            (Message::RemoteHttpCommand(command), _, _) => self.handle_remote_http_command(command),
+            #[cfg(feature = "mqtt")]
+            (Message::MqttEvent(event), _, _) => self.handle_mqtt_event(event),
+            #[cfg(feature = "mqtt")]
+            (Message::MqttStatePublished(_), _, _) => Command::none(),
+            #[cfg(feature = "mqtt")]
+            (Message::MqttPublishTick, _, _) => self.publish_mqtt_state(),
+            (Message::DiagnosticsExported(result), _, _) => {
                 self.push_toast(Toast {
-                    text: "Song added to queue".to_string(),
+                    text: match result {
+                        Ok(path) => format!("Diagnostics saved to {}", path.display()),
+                        Err(err) => format!("Diagnostics export failed: {err}"),
+                    },
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(8),
+                });
+                Command::none()
+            }
+            (Message::Announcement(announcement), _, _) => {
+                self.push_toast(Toast {
+                    text: announcement.message.to_string(),
                     start: Instant::now(),
                     ttl: Duration::from_secs(5),
                 });
 
-                Command::perform(
-                    async move { oracle.speaker(id).play_track(uri).await },
-                    Message::PlayTrackResult,
-                )
-            }
-        }
-    }
-}
+                let speaker = self.oracle.as_ref().and_then(|oracle| {
+                    oracle
+                        .announcement_tts_entity()
+                        .map(|tts_entity| (oracle.clone(), tts_entity))
+                });
 
-impl Application for Shalom {
-    type Executor = iced::executor::Default;
-    type Message = Message;
-    type Theme = Theme;
-    type Flags = ();
+                if let Some((oracle, tts_entity)) = speaker {
+                    let speaker_id = announcement.speaker_id;
+                    let message = announcement.message.to_string();
 
-    fn new(_flags: Self::Flags) -> (Self, Command<Self::Message>) {
-        let this = Self {
-            page: ActivePage::Loading,
-            context_menu: None,
-            oracle: None,
-            home_room: Some("living_room"),
-            theme: Theme::default(),
-            config: None,
-            toast: BTreeMap::new(),
-        };
+                    return Command::perform(
+                        async move {
+                            oracle
+                                .speaker(speaker_id)
+                                .announce(tts_entity, message)
+                                .await
+                        },
+                        Message::UpdateLightResult,
+                    );
+                }
 
-        // this is only best-effort to try and prevent blocking when loading
-        // the omni-view, we don't need to block on this at boot
-        tokio::task::spawn_blocking(Image::preload);
+                Command::none()
+            }
+            (Message::ApplianceFinished(finished), _, _) => {
+                self.push_toast(Toast {
+                    text: format!("{} finished", finished.name),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(5),
+                });
 
-        let command = Command::perform(
-            async {
-                let config = load_config().await;
-                let client = hass_client::create(config.home_assistant.clone()).await;
-                (Oracle::new(client.clone()).await, config)
-            },
-            Message::Loaded,
-        );
+                let announcement = self.oracle.as_ref().and_then(|oracle| {
+                    oracle
+                        .tts_entity()
+                        .zip(finished.speaker_id)
+                        .map(|(tts_entity, speaker_id)| (oracle.clone(), tts_entity, speaker_id))
+                });
 
-        (this, command)
-    }
+                if let Some((oracle, tts_entity, speaker_id)) = announcement {
+                    let message = format!("{} finished", finished.name);
 
-    fn title(&self) -> String {
-        String::from("Shalom")
-    }
+                    return Command::perform(
+                        async move {
+                            oracle
+                                .speaker(speaker_id)
+                                .announce(tts_entity, message)
+                                .await
+                        },
+                        Message::UpdateLightResult,
+                    );
+                }
 
-    #[allow(clippy::too_many_lines)]
-    fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
-        #[allow(clippy::single_match)]
-        match (message, &mut self.page, &mut self.context_menu) {
-            (Message::Loaded((oracle, config)), _, _) => {
-                self.oracle = Some(oracle);
-                self.config = Some(Arc::new(config));
-                self.page = self.build_home_route();
                 Command::none()
             }
-            (Message::CloseContextMenu, _, _) => {
-                self.context_menu = None;
-                Command::none()
-            }
-            (Message::OpenOmniPage, _, _) => {
-                self.page = self.build_omni_route();
+            (Message::LocalTimerFinished(finished), _, _) => {
+                self.push_toast(Toast {
+                    text: format!("{} finished", finished.label),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(10),
+                });
+
+                self.context_menu = Some(ActiveContextMenu::TimerFinished(
+                    context_menus::timer_finished::TimerFinished::new(finished.label),
+                ));
+                sound::play_timer();
+
                 Command::none()
             }
-            (Message::OpenHomePage, _, _) => {
-                self.page = self.build_home_route();
+            (Message::ToastTtlExpired(k), _, _) => {
+                self.toast.remove(&k);
                 Command::none()
             }
-            (Message::OmniEvent(e), ActivePage::Omni(r), _) => match r.update(e) {
-                Some(pages::omni::Event::OpenRoom(room)) => {
-                    self.page = self.build_room_route(room);
-                    Command::none()
-                }
-                None => Command::none(),
-            },
-            (Message::RoomEvent(e), _, _) => self.handle_room_event(e),
-            (Message::LightControlMenu(e), _, Some(ActiveContextMenu::LightControl(menu))) => {
-                match menu.update(e) {
-                    Some(context_menus::light_control::Event::UpdateLightColour {
-                        id,
-                        hue,
-                        saturation,
-                        brightness,
-                    }) => {
-                        let oracle = self.oracle.as_ref().unwrap().clone();
-
-                        Command::perform(
-                            async move { oracle.update_light(id, hue, saturation, brightness).await },
-                            Message::UpdateLightResult,
-                        )
+            (Message::ConnectionStatusChanged(status), _, _) => {
+                let text = match status {
+                    hass_client::ConnectionStatus::Connected => "Reconnected to Home Assistant",
+                    hass_client::ConnectionStatus::Disconnected => {
+                        "Lost connection to Home Assistant"
                     }
-                    None => Command::none(),
+                };
+
+                if let Some(oracle) = &self.oracle {
+                    oracle.set_stale(status == hass_client::ConnectionStatus::Disconnected);
                 }
-            }
-            (Message::ToastTtlExpired(k), _, _) => {
-                self.toast.remove(&k);
+
+                self.push_toast(Toast {
+                    text: text.to_string(),
+                    start: Instant::now(),
+                    ttl: Duration::from_secs(10),
+                });
+
                 Command::none()
             }
+            (Message::CloseRequested, _, _) => match self.oracle.clone() {
+                Some(oracle) => {
+                    Command::perform(async move { oracle.save_state_snapshot().await }, |()| {
+                        Message::ReadyToExit
+                    })
+                }
+                None => window::close(window::Id::MAIN),
+            },
+            (Message::ReadyToExit, _, _) => window::close(window::Id::MAIN),
             _ => Command::none(),
         }
     }
@@ -310,10 +1563,63 @@ impl Application for Shalom {
             ),
             ActivePage::Room(room) => room.view(&self.theme).map(Message::RoomEvent),
             ActivePage::Omni(omni) => omni.view().map(Message::OmniEvent),
+            ActivePage::ShoppingList(shopping_list) => {
+                shopping_list.view().map(Message::ShoppingListEvent)
+            }
+            ActivePage::Maintenance(maintenance) => {
+                maintenance.view().map(Message::MaintenanceEvent)
+            }
+            ActivePage::Map(map) => map.view().map(Message::MapEvent),
+            ActivePage::Alarms(alarms) => alarms.view().map(Message::AlarmsEvent),
+            ActivePage::Remote(remote) => remote.view().map(Message::RemoteEvent),
+            ActivePage::Scheduler(scheduler) => scheduler.view().map(Message::SchedulerEvent),
+            ActivePage::Floorplan(floorplan) => floorplan.view().map(Message::FloorplanEvent),
+            #[cfg(feature = "discovery")]
+            ActivePage::Discovery(discovery) => discovery.view().map(Message::DiscoveryEvent),
+            ActivePage::AuthFailed(auth_failed) => auth_failed.view().map(Message::AuthFailedEvent),
+        };
+
+        let quick_actions = self
+            .oracle
+            .as_ref()
+            .map(|oracle| oracle.quick_actions())
+            .unwrap_or_default();
+
+        let page_content = if quick_actions.is_empty() {
+            page_content
+        } else {
+            let bar = quick_actions.iter().enumerate().fold(
+                row![].spacing(10),
+                |bar, (index, action)| {
+                    bar.push(
+                        mouse_area(container(text(action.label.as_ref()).size(16)).padding(10))
+                            .on_press(Message::QuickAction(index)),
+                    )
+                },
+            );
+
+            column![page_content, bar]
+                .spacing(10)
+                .padding(10)
+                .height(Length::Fill)
+                .into()
         };
 
         let mut content = Element::from(page_content);
 
+        if self.oracle.as_ref().is_some_and(|oracle| oracle.is_stale()) {
+            content = FloatingElement::new(
+                content,
+                container(text("Offline — showing last known state").size(14))
+                    .padding(10)
+                    .width(Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(StaleBannerStyle))),
+            )
+            .anchor(Anchor::North)
+            .offset(0.0)
+            .into();
+        }
+
         for (i, (idx, toast)) in self.toast.iter().enumerate() {
             let offs = f32::from(u8::try_from(i).unwrap_or(u8::MAX));
 
@@ -329,6 +1635,16 @@ impl Application for Shalom {
         if let Some(context_menu) = &self.context_menu {
             let context_menu = match context_menu {
                 ActiveContextMenu::LightControl(menu) => menu.view().map(Message::LightControlMenu),
+                ActiveContextMenu::RoomSummary(menu) => menu.view().map(Message::RoomSummaryMenu),
+                ActiveContextMenu::QuickSettings(menu) => {
+                    menu.view().map(Message::QuickSettingsMenu)
+                }
+                ActiveContextMenu::PinPad(menu) => menu.view().map(Message::PinPadMenu),
+                ActiveContextMenu::CameraDetail(menu) => menu.view().map(Message::CameraDetailMenu),
+                ActiveContextMenu::TimerFinished(menu) => {
+                    menu.view().map(Message::TimerFinishedMenu)
+                }
+                ActiveContextMenu::SaveScene(menu) => menu.view().map(Message::SaveSceneMenu),
             };
 
             ContextMenu::new(content, context_menu)
@@ -340,44 +1656,371 @@ impl Application for Shalom {
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        match &self.page {
+        let page_subscription = match &self.page {
             ActivePage::Room(room) => room.subscription().map(Message::RoomEvent),
             ActivePage::Omni(omni) => omni.subscription().map(Message::OmniEvent),
-            ActivePage::Loading => Subscription::none(),
+            ActivePage::ShoppingList(shopping_list) => {
+                shopping_list.subscription().map(Message::ShoppingListEvent)
+            }
+            ActivePage::Maintenance(maintenance) => {
+                maintenance.subscription().map(Message::MaintenanceEvent)
+            }
+            ActivePage::Map(map) => map.subscription().map(Message::MapEvent),
+            ActivePage::Alarms(alarms) => alarms.subscription().map(Message::AlarmsEvent),
+            ActivePage::Remote(remote) => remote.subscription().map(Message::RemoteEvent),
+            ActivePage::Scheduler(scheduler) => {
+                scheduler.subscription().map(Message::SchedulerEvent)
+            }
+            ActivePage::Floorplan(floorplan) => {
+                floorplan.subscription().map(Message::FloorplanEvent)
+            }
+            #[cfg(feature = "discovery")]
+            ActivePage::Discovery(discovery) => {
+                discovery.subscription().map(Message::DiscoveryEvent)
+            }
+            ActivePage::AuthFailed(_) | ActivePage::Loading => Subscription::none(),
+        };
+
+        let resize_subscription = iced::subscription::events_with(|event, _status| match event {
+            iced::Event::Window(window::Event::Resized { width, height }) => {
+                Some(Message::WindowResized(width, height))
+            }
+            iced::Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested),
+            iced::Event::Mouse(_) | iced::Event::Touch(_) => Some(Message::UserActivity),
+            iced::Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(key::Named::Escape),
+                ..
+            }) => Some(Message::EscapePressed),
+            _ => None,
+        });
+
+        struct ApplianceFinishedSubscription;
+
+        let appliance_subscription =
+            self.oracle
+                .as_ref()
+                .map_or_else(Subscription::none, |oracle| {
+                    subscription::run_with_id(
+                        TypeId::of::<ApplianceFinishedSubscription>(),
+                        oracle
+                            .subscribe_appliances_finished()
+                            .map(Message::ApplianceFinished),
+                    )
+                });
+
+        struct AnnouncementSubscription;
+
+        let announcement_subscription =
+            self.oracle
+                .as_ref()
+                .map_or_else(Subscription::none, |oracle| {
+                    subscription::run_with_id(
+                        TypeId::of::<AnnouncementSubscription>(),
+                        oracle.subscribe_announcements().map(Message::Announcement),
+                    )
+                });
+
+        struct LocalTimerFinishedSubscription;
+
+        let local_timer_finished_subscription =
+            self.oracle
+                .as_ref()
+                .map_or_else(Subscription::none, |oracle| {
+                    subscription::run_with_id(
+                        TypeId::of::<LocalTimerFinishedSubscription>(),
+                        oracle
+                            .subscribe_local_timer_finished()
+                            .map(Message::LocalTimerFinished),
+                    )
+                });
+
+        struct ConnectionStatusSubscription;
+
+        let connection_status_subscription =
+            self.oracle
+                .as_ref()
+                .map_or_else(Subscription::none, |oracle| {
+                    subscription::run_with_id(
+                        TypeId::of::<ConnectionStatusSubscription>(),
+                        oracle
+                            .subscribe_connection_status()
+                            .map(Message::ConnectionStatusChanged),
+                    )
+                });
+
+        let idle_subscription = self
+            .config
+            .as_ref()
+            .and_then(|config| config.display.idle_timeout_secs)
+            .map_or_else(Subscription::none, |_| {
+                iced::time::every(Duration::from_secs(1)).map(|_| Message::IdleCheck)
+            });
+
+        let config_watch_subscription =
+            self.config.as_ref().map_or_else(Subscription::none, |_| {
+                config_watch::subscription().map(Message::ConfigReloaded)
+            });
+
+        #[cfg(feature = "remote-http")]
+        let remote_http_subscription = self
+            .config
+            .as_ref()
+            .filter(|config| config.remote_api.enabled)
+            .map_or_else(Subscription::none, |config| {
+                remote_http::subscription(config.remote_api.port).map(Message::RemoteHttpCommand)
+            });
+        #[cfg(not(feature = "remote-http"))]
+        let remote_http_subscription = Subscription::none();
+
+        #[cfg(feature = "mqtt")]
+        let mqtt_subscription = self
+            .config
+            .as_ref()
+            .filter(|config| config.mqtt.enabled)
+            .map_or_else(Subscription::none, |config| {
+                mqtt::subscription(config.mqtt.clone()).map(Message::MqttEvent)
+            });
+        #[cfg(not(feature = "mqtt"))]
+        let mqtt_subscription = Subscription::none();
+
+        #[cfg(feature = "mqtt")]
+        let mqtt_publish_subscription = self
+            .config
+            .as_ref()
+            .filter(|config| config.mqtt.enabled)
+            .map_or_else(Subscription::none, |_| {
+                iced::time::every(Duration::from_secs(10)).map(|_| Message::MqttPublishTick)
+            });
+        #[cfg(not(feature = "mqtt"))]
+        let mqtt_publish_subscription = Subscription::none();
+
+        Subscription::batch([
+            page_subscription,
+            resize_subscription,
+            idle_subscription,
+            config_watch_subscription,
+            appliance_subscription,
+            announcement_subscription,
+            local_timer_finished_subscription,
+            connection_status_subscription,
+            remote_http_subscription,
+            mqtt_subscription,
+            mqtt_publish_subscription,
+        ])
+    }
+}
+
+#[derive(Debug, Default)]
+struct StaleBannerStyle;
+
+impl container::StyleSheet for StaleBannerStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(ORANGE.into()),
+            text_color: Some(iced::Color::WHITE),
+            ..container::Appearance::default()
         }
     }
 }
 
 async fn load_config() -> Config {
-    let content = tokio::fs::read_to_string("./config.toml").await.unwrap();
-    toml::from_str(&content).unwrap()
+    try_load_config().await.unwrap()
+}
+
+/// Reads and parses `config.toml`, resolving any keyring-backed secrets.
+/// A fallible sibling of [`load_config`], for [`config_watch`]'s live
+/// reload, where a bad save should be logged and ignored rather than
+/// treated as fatal.
+async fn try_load_config() -> Result<Config, String> {
+    let content = tokio::fs::read_to_string("./config.toml")
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut config: Config = toml::from_str(&content).map_err(|err| err.to_string())?;
+
+    secrets::resolve(&mut config).await;
+
+    Ok(config)
+}
+
+fn boot_command(demo: bool) -> Command<Message> {
+    Command::perform(
+        async move {
+            let config = if demo {
+                Config::demo()
+            } else {
+                load_config().await
+            };
+
+            boot(config, demo).await
+        },
+        Message::Loaded,
+    )
+}
+
+/// Rebuilds a connection from a config that's already resolved (e.g. a copy
+/// of the boot config with a freshly-typed token substituted in after an
+/// [`ActivePage::AuthFailed`] retry), skipping [`load_config`] entirely.
+fn retry_boot_command(config: Config) -> Command<Message> {
+    Command::perform(boot(config, false), Message::Loaded)
+}
+
+async fn boot(config: Config, demo: bool) -> Result<(Arc<Oracle>, Config), (String, Config)> {
+    let client = if demo {
+        Ok(hass_client::mock::create().await)
+    } else {
+        hass_client::create(config.home_assistant.clone()).await
+    };
+
+    let client = match client {
+        Ok(client) => client,
+        Err(reason) => return Err((reason, config)),
+    };
+
+    let oracle = Oracle::new(
+        client.clone(),
+        config.display.backlight_entity.clone(),
+        config.display.night_backlight,
+        config.entity_names.clone(),
+        config.entity_room_overrides.clone(),
+        config.include_unassigned_room,
+        config.show_hidden_entities,
+        config.vacuum_rooms.clone(),
+        config.system_monitor.clone(),
+        config.map.clone(),
+        config.routines.clone(),
+        config.energy.clone(),
+        config.appliances.clone(),
+        config.bin_collection.clone(),
+        config.transport.clone(),
+        config.news.clone(),
+        config.alarms.clone(),
+        config.chores.clone(),
+        config.announcements.clone(),
+        config.intercom.clone(),
+        config.quick_actions.clone(),
+        config.floorplan.clone(),
+    )
+    .await;
+
+    Ok((oracle, config))
+}
+
+/// Writes a `config.toml` with `uri` pre-filled from mDNS discovery and every
+/// other required field left blank for someone to fill in by hand, so
+/// [`load_config`]'s `toml::from_str` doesn't choke on a missing section on
+/// the next boot.
+#[cfg(feature = "discovery")]
+async fn write_discovered_config(uri: &str) {
+    let contents =
+        format!("[home-assistant]\nuri = {uri:?}\ntoken = \"\"\n\n[spotify]\ntoken = \"\"\n");
+
+    let _res = tokio::fs::write("./config.toml", contents).await;
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    Loaded((Arc<Oracle>, Config)),
+    Loaded(Result<(Arc<Oracle>, Config), (String, Config)>),
+    #[cfg(feature = "discovery")]
+    DiscoveryEvent(pages::discovery::Message),
+    AuthFailedEvent(pages::auth_failed::Message),
     CloseContextMenu,
+    EscapePressed,
     OpenOmniPage,
     OpenHomePage,
     OmniEvent(pages::omni::Message),
     RoomEvent(pages::room::Message),
+    ShoppingListEvent(pages::shopping_list::Message),
+    MaintenanceEvent(pages::maintenance::Message),
+    MapEvent(pages::map::Message),
+    AlarmsEvent(pages::alarms::Message),
+    RemoteEvent(pages::remote::Message),
+    SchedulerEvent(pages::scheduler::Message),
+    FloorplanEvent(pages::floorplan::Message),
     LightControlMenu(context_menus::light_control::Message),
+    SaveSceneMenu(context_menus::save_scene::Message),
+    RoomSummaryMenu(context_menus::room_summary::Message),
+    QuickSettingsMenu(context_menus::quick_settings::Message),
+    PinPadMenu(context_menus::pin_pad::Message),
+    CameraDetailMenu(context_menus::camera_detail::Message),
+    TimerFinishedMenu(context_menus::timer_finished::Message),
     UpdateLightResult(()),
+    AllLightsOffDone(()),
+    QuickAction(usize),
     PlayTrackResult(()),
+    DiagnosticsExported(Result<PathBuf, String>),
+    #[cfg(feature = "remote-http")]
+    RemoteHttpCommand(remote_http::Command),
+    #[cfg(feature = "mqtt")]
+    MqttEvent(mqtt::Event),
+    #[cfg(feature = "mqtt")]
+    MqttStatePublished(Result<(), String>),
+    #[cfg(feature = "mqtt")]
+    MqttPublishTick,
+    ApplianceFinished(ApplianceFinished),
+    Announcement(Announcement),
+    LocalTimerFinished(LocalTimerFinished),
+    ConnectionStatusChanged(hass_client::ConnectionStatus),
     ToastTtlExpired(u8),
+    WindowResized(u32, u32),
+    UserActivity,
+    IdleCheck,
+    /// The window manager asked us to close. Persists a state snapshot
+    /// before actually closing, since [`window::Settings::exit_on_close_request`]
+    /// is disabled to allow for that.
+    CloseRequested,
+    /// [`Message::CloseRequested`]'s snapshot save has finished (or there
+    /// was no [`Oracle`] to save from); actually closes the window.
+    ReadyToExit,
+    /// `config.toml` was edited and re-read by [`config_watch::subscription`].
+    /// Re-applies the high-contrast, font-scale and reduce-animations display
+    /// settings and entity name overrides. `display.scale`, network, sound
+    /// and cache settings keep their startup values, as their `configure`
+    /// functions ignore every call after the first; everything else (rooms,
+    /// routines, and anything else only read once at startup) still needs a
+    /// restart to pick up.
+    ConfigReloaded(Config),
 }
 
 #[derive(Debug)]
 #[allow(clippy::large_enum_variant)]
 pub enum ActivePage {
     Loading,
+    #[cfg(feature = "discovery")]
+    Discovery(pages::discovery::Discovery),
+    AuthFailed(pages::auth_failed::AuthFailed),
     Room(pages::room::Room),
     Omni(pages::omni::Omni),
+    ShoppingList(pages::shopping_list::ShoppingList),
+    Maintenance(pages::maintenance::Maintenance),
+    Map(pages::map::Map),
+    Alarms(pages::alarms::Alarms),
+    Remote(pages::remote::Remotes),
+    Scheduler(pages::scheduler::Scheduler),
+    Floorplan(pages::floorplan::Floorplan),
 }
 
 #[derive(Clone, Debug)]
 pub enum ActiveContextMenu {
     LightControl(context_menus::light_control::LightControl),
+    RoomSummary(context_menus::room_summary::RoomSummary),
+    QuickSettings(context_menus::quick_settings::QuickSettings),
+    PinPad(context_menus::pin_pad::PinPad),
+    CameraDetail(context_menus::camera_detail::CameraDetail),
+    TimerFinished(context_menus::timer_finished::TimerFinished),
+    SaveScene(context_menus::save_scene::SaveScene),
+}
+
+/// A sensitive action that was deferred behind a [`ActiveContextMenu::PinPad`]
+/// challenge, to be carried out once the correct PIN is entered.
+#[derive(Clone, Debug)]
+pub enum PinGatedAction {
+    OpenQuickSettings,
+    RestartHomeAssistant,
+    ReloadAll,
+    CheckConfig,
+    ExportDiagnostics,
 }
 
 fn main() {
@@ -385,6 +2028,10 @@ fn main() {
         antialiasing: true,
         window: window::Settings {
             min_size: Some(Size::new(600.0, 600.0)),
+            // Intercepted as `Message::CloseRequested` instead, to persist a
+            // state snapshot (see `Oracle::save_state_snapshot`) before the
+            // window actually closes.
+            exit_on_close_request: false,
             ..window::Settings::default()
         },
         ..Settings::default()
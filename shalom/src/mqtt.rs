@@ -0,0 +1,135 @@
+//! Publishes the panel's own state (current page, idle/active, last
+//! interaction) to an MQTT broker so Home Assistant automations can react
+//! to someone using the panel, and reads navigation commands back from a
+//! command topic. Only compiled in with the `mqtt` feature, and only
+//! connects when [`crate::config::MqttConfig::enabled`] is set.
+//!
+//! State is published (retained) to `<base-topic>/state` as JSON on an
+//! interval, rather than threading a publish call through every place the
+//! panel's page or activity can change. Commands are read from
+//! `<base-topic>/command` as a bare navigation target, e.g. `living_room`.
+
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, Event as MqttEvent, MqttOptions, Packet, QoS};
+use serde::Serialize;
+
+use crate::config::MqttConfig;
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    /// The broker connection is up; holds a handle for publishing state.
+    Connected(AsyncClient),
+    Command(Command),
+}
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    OpenRoom(Box<str>),
+}
+
+#[derive(Serialize)]
+pub struct PanelState<'a> {
+    pub page: &'a str,
+    pub idle: bool,
+    pub last_interaction_secs: u64,
+}
+
+pub fn subscription(config: MqttConfig) -> iced::Subscription<Event> {
+    struct MqttSubscription;
+
+    iced::subscription::channel(
+        std::any::TypeId::of::<MqttSubscription>(),
+        16,
+        move |mut output| async move {
+            let command_topic = format!("{}/command", config.base_topic);
+
+            loop {
+                let mut options =
+                    MqttOptions::new("shalom-panel", config.broker.clone(), config.port);
+                options.set_keep_alive(Duration::from_secs(30));
+
+                if let (Some(username), Some(password)) = (&config.username, &config.password) {
+                    options.set_credentials(username.clone(), password.clone());
+                }
+
+                let (client, mut event_loop) = AsyncClient::new(options, 16);
+
+                if client
+                    .subscribe(command_topic.as_str(), QoS::AtMostOnce)
+                    .await
+                    .is_err()
+                {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    continue;
+                }
+
+                loop {
+                    match event_loop.poll().await {
+                        Ok(MqttEvent::Incoming(Packet::ConnAck(_))) => {
+                            let _res = iced::futures::SinkExt::send(
+                                &mut output,
+                                Event::Connected(client.clone()),
+                            )
+                            .await;
+                        }
+                        Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                            if let Some(command) = parse_command(&publish.payload) {
+                                let _res = iced::futures::SinkExt::send(
+                                    &mut output,
+                                    Event::Command(command),
+                                )
+                                .await;
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            eprintln!("mqtt: connection error: {err}");
+                            break;
+                        }
+                    }
+                }
+
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        },
+    )
+}
+
+fn parse_command(payload: &[u8]) -> Option<Command> {
+    let room = std::str::from_utf8(payload).ok()?.trim();
+
+    if room.is_empty() {
+        None
+    } else {
+        Some(Command::OpenRoom(room.into()))
+    }
+}
+
+/// Publishes `state` as retained JSON to `<base_topic>/state`. Fire-and-forget:
+/// intended to be driven from `Command::perform`, dropping the error into a
+/// result message like every other background task in `main.rs`.
+pub async fn publish_state(
+    client: AsyncClient,
+    base_topic: Box<str>,
+    page: Box<str>,
+    idle: bool,
+    last_interaction_secs: u64,
+) -> Result<(), String> {
+    let payload = serde_json::to_string(&PanelState {
+        page: &page,
+        idle,
+        last_interaction_secs,
+    })
+    .map_err(|err| err.to_string())?;
+
+    client
+        .publish(
+            format!("{base_topic}/state"),
+            QoS::AtLeastOnce,
+            true,
+            payload,
+        )
+        .await
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,46 @@
+//! Global proxy configuration, set once at boot from [`crate::config::NetworkConfig`]
+//! and read by every module that builds its own `reqwest::Client` — there's no
+//! single shared client to thread a setting like this through instead, the
+//! same situation [`crate::subscriptions::configure`] solves for cache sizes.
+
+use once_cell::sync::OnceCell;
+
+static PROXY: OnceCell<Option<String>> = OnceCell::new();
+
+/// Must be called before any `reqwest::Client` in the process is built for
+/// the proxy to take effect, since most of them are lazily constructed once
+/// on first use.
+pub fn configure(proxy: Option<String>) {
+    let _ = PROXY.set(proxy);
+}
+
+/// A `reqwest::ClientBuilder` with the configured proxy (if any) applied.
+/// Callers build from this instead of `reqwest::Client::builder()` directly.
+pub fn client_builder() -> reqwest::ClientBuilder {
+    let builder = reqwest::Client::builder();
+
+    match PROXY.get().and_then(Option::as_ref) {
+        Some(proxy) => match reqwest::Proxy::all(proxy) {
+            Ok(proxy) => builder.proxy(proxy),
+            Err(err) => {
+                eprintln!("ignoring invalid network.proxy {proxy:?}: {err}");
+                builder
+            }
+        },
+        None => builder,
+    }
+}
+
+/// Whether a proxy has been configured. Used by call sites that can't route
+/// through one (see [`crate::hass_client::create`]'s websocket connection) to
+/// at least surface that the setting isn't being honoured there.
+pub fn is_configured() -> bool {
+    matches!(PROXY.get(), Some(Some(_)))
+}
+
+/// A ready-to-use `reqwest::Client` with the configured proxy (if any)
+/// applied, for one-off requests that would otherwise reach for
+/// `reqwest::Client::new()` or the `reqwest::get` shorthand.
+pub fn client() -> reqwest::Client {
+    client_builder().build().unwrap()
+}
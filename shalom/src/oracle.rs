@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::PathBuf,
     str::FromStr,
     sync::{atomic::Ordering, Arc},
     time::Duration,
@@ -12,7 +13,8 @@ use iced::futures::{future, Stream, StreamExt};
 use internment::Intern;
 use itertools::Itertools;
 use parking_lot::Mutex;
-use time::OffsetDateTime;
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 use tokio::{
     sync::{broadcast, broadcast::error::RecvError},
     time::MissedTickBehavior,
@@ -22,18 +24,26 @@ use url::Url;
 use yoke::Yoke;
 
 use crate::{
+    hass_client::responses,
     hass_client::{
         responses::{
-            Area, AreaRegistryList, ColorMode, DeviceRegistryList, Entity, EntityRegistryList,
-            StateAttributes, StateCameraAttributes, StateLightAttributes,
-            StateMediaPlayerAttributes, StateWeatherAttributes, StatesList, WeatherCondition,
+            Area, AreaRegistryList, ColorMode, Device, DeviceRegistryList, Entity,
+            EntityRegistryList, MediaContentId, StateAttributes, StateBinarySensorAttributes,
+            StateButtonAttributes, StateCameraAttributes, StateClimateAttributes,
+            StateCoverAttributes, StateDeviceTrackerAttributes, StateHumidifierAttributes,
+            StateLightAttributes, StateMediaPlayerAttributes, StateNumberAttributes,
+            StatePlantAttributes, StateRemoteAttributes, StateSensorAttributes,
+            StateSwitchAttributes, StateTimerAttributes, StateUpdateAttributes,
+            StateVacuumAttributes, StateWeatherAttributes, StatesList, WeatherCondition,
         },
         CallServiceRequestData, CallServiceRequestLight, CallServiceRequestLightTurnOn,
         CallServiceRequestMediaPlayer, CallServiceRequestMediaPlayerMediaSeek,
         CallServiceRequestMediaPlayerPlayMedia, CallServiceRequestMediaPlayerPlayMediaEnqueue,
-        CallServiceRequestMediaPlayerPlayMediaType, CallServiceRequestMediaPlayerRepeatSet,
-        CallServiceRequestMediaPlayerShuffleSet, CallServiceRequestMediaPlayerVolumeMute,
-        CallServiceRequestMediaPlayerVolumeSet, Event, HassRequestKind, MediaPlayerRepeat,
+        CallServiceRequestMediaPlayerRepeatSet, CallServiceRequestMediaPlayerShuffleSet,
+        CallServiceRequestMediaPlayerVolumeMute, CallServiceRequestMediaPlayerVolumeSet,
+        CallServiceRequestNumber, CallServiceRequestNumberSetValue, CallServiceRequestSwitch,
+        CallServiceRequestTimer, CallServiceRequestTimerStart, Event, ForecastType,
+        HassRequestKind, MediaPlayerRepeat,
     },
     widgets::colour_picker::clamp_to_u8,
 };
@@ -42,25 +52,258 @@ use crate::{
 #[derive(Debug)]
 pub struct Oracle {
     client: crate::hass_client::Client,
-    rooms: BTreeMap<&'static str, Room>,
+    /// Rebuilt wholesale by [`Self::rebuild_rooms`] whenever Home Assistant's
+    /// area/device/entity registries change, so it needs to be mutable after
+    /// construction unlike most other fields seeded once in [`Self::new`].
+    rooms: Mutex<BTreeMap<&'static str, Room>>,
+    /// `config.toml`'s `entity-room-overrides` table, consulted by
+    /// [`group_entities_by_room`] ahead of Home Assistant's own device-area
+    /// placement.
+    entity_room_overrides: BTreeMap<&'static str, &'static str>,
+    include_unassigned_room: bool,
+    /// `config.toml`'s `show-hidden-entities` switch, consulted by
+    /// [`group_entities_by_room`] to decide whether an entity the registry
+    /// marks `hidden_by`/`disabled_by` still shows up in its room.
+    show_hidden_entities: bool,
     weather: Atomic<Weather>,
     media_players: Mutex<BTreeMap<&'static str, MediaPlayer>>,
     lights: Mutex<BTreeMap<&'static str, Light>>,
     cameras: Mutex<BTreeMap<&'static str, Camera>>,
+    weather_alert: Mutex<Option<WeatherAlert>>,
+    shopping_list: Mutex<Vec<ShoppingListItem>>,
+    timers: Mutex<BTreeMap<&'static str, Timer>>,
+    recently_played: Mutex<BTreeMap<&'static str, Vec<String>>>,
     entity_updates: broadcast::Sender<Arc<str>>,
+    backlight_entity: Option<&'static str>,
+    night_backlight: u8,
+    sun_above_horizon: Atomic<bool>,
+    /// Set while the Home Assistant connection is down, so pages can keep
+    /// showing the last known state instead of blanking out. See
+    /// [`Self::is_stale`].
+    stale: Atomic<bool>,
+    /// Overrides for entity friendly names, keyed by entity id; see
+    /// [`Self::apply_entity_names`] for how these get (re)applied.
+    entity_names: Mutex<BTreeMap<&'static str, Box<str>>>,
+    /// `None` until [`Self::hydrate_states`]'s `GetStates` response comes
+    /// back and reports which entity is the weather entity.
+    weather_entity_id: Mutex<Option<&'static str>>,
+    daily_forecast: Mutex<Vec<ForecastDay>>,
+    hourly_forecast: Mutex<Vec<ForecastDay>>,
+    vacuums: Mutex<BTreeMap<&'static str, Vacuum>>,
+    vacuum_rooms: BTreeMap<&'static str, Vec<VacuumRoom>>,
+    covers: Mutex<BTreeMap<&'static str, Cover>>,
+    humidifiers: Mutex<BTreeMap<&'static str, Humidifier>>,
+    thermostats: Mutex<BTreeMap<&'static str, Thermostat>>,
+    /// `on`/`off` state of `binary_sensor.*` entities with a `motion` or
+    /// `occupancy` device class, for the room cards' occupancy badge.
+    occupancy: Mutex<BTreeMap<&'static str, bool>>,
+    updates: Mutex<BTreeMap<&'static str, Update>>,
+    sensors: Mutex<BTreeMap<&'static str, Sensor>>,
+    numbers: Mutex<BTreeMap<&'static str, NumberEntity>>,
+    switches: Mutex<BTreeMap<&'static str, SwitchEntity>>,
+    system_monitor_entities: SystemMonitorEntities,
+    people: Mutex<BTreeMap<&'static str, Person>>,
+    map_centre: Option<MapCentre>,
+    routines: Vec<Routine>,
+    recent_colours: Mutex<BTreeMap<&'static str, Vec<RecentColour>>>,
+    /// Lights running a client-side colour loop, for bulbs whose
+    /// `effect_list` doesn't advertise Home Assistant's native `colorloop`
+    /// effect. Stepped every tick by [`Self::step_colour_loops`].
+    colour_loop_lights: Mutex<HashSet<&'static str>>,
+    /// On/brightness/colour state captured by [`Self::snapshot_lights`], for
+    /// [`Self::restore_lights`] to put back after an attention-grabbing
+    /// action (doorbell flash, announcement) is done with the lights.
+    light_snapshots: Mutex<BTreeMap<&'static str, LightRestoreSnapshot>>,
+    price_entity: Option<&'static str>,
+    energy_price: Mutex<Option<EnergyPrice>>,
+    plants: Mutex<BTreeMap<&'static str, Plant>>,
+    tts_entity: Option<&'static str>,
+    appliances: Mutex<BTreeMap<&'static str, ApplianceRule>>,
+    appliance_finished: broadcast::Sender<ApplianceFinished>,
+    announcement_tts_entity: Option<&'static str>,
+    chime_url: Option<&'static str>,
+    announcements: Mutex<BTreeMap<&'static str, AnnouncementRule>>,
+    announcement_fired: broadcast::Sender<Announcement>,
+    bin_collection_entities: Vec<&'static str>,
+    bins: Mutex<BTreeMap<&'static str, BinCollection>>,
+    transport_entities: Vec<&'static str>,
+    departures: Mutex<BTreeMap<&'static str, Departure>>,
+    news_feeds: Vec<Url>,
+    news_refresh_interval: Duration,
+    alarms: Mutex<Vec<Alarm>>,
+    alarms_fired_today: Mutex<BTreeMap<usize, time::Date>>,
+    remotes: Mutex<BTreeMap<&'static str, Remote>>,
+    buttons: Mutex<BTreeMap<&'static str, Button>>,
+    scheduled_scenes: Mutex<Vec<ScheduledScene>>,
+    scheduled_scenes_fired_today: Mutex<BTreeMap<u64, time::Date>>,
+    local_timers: Mutex<Vec<LocalTimer>>,
+    local_timer_finished: broadcast::Sender<LocalTimerFinished>,
+    household_notes: Mutex<Vec<HouseholdNote>>,
+    chores: Mutex<Vec<Chore>>,
+    intercom_rooms: Vec<IntercomRoom>,
+    quick_actions: Vec<QuickAction>,
+    floorplan: Option<FloorplanPlan>,
 }
 
 impl Oracle {
-    pub async fn new(hass_client: crate::hass_client::Client) -> Arc<Self> {
-        let (rooms, devices, entities, states) = tokio::join!(
+    pub async fn new(
+        hass_client: crate::hass_client::Client,
+        backlight_entity: Option<String>,
+        night_backlight: u8,
+        entity_names: HashMap<String, String>,
+        entity_room_overrides: HashMap<String, String>,
+        include_unassigned_room: bool,
+        show_hidden_entities: bool,
+        vacuum_rooms: HashMap<String, Vec<crate::config::VacuumRoomConfig>>,
+        system_monitor: crate::config::SystemMonitorConfig,
+        map: crate::config::MapConfig,
+        routines: Vec<crate::config::RoutineConfig>,
+        energy: crate::config::EnergyConfig,
+        appliances: crate::config::AppliancesConfig,
+        bin_collection: crate::config::BinCollectionConfig,
+        transport: crate::config::TransportConfig,
+        news: crate::config::NewsConfig,
+        alarms: crate::config::AlarmsConfig,
+        chores: Vec<crate::config::ChoreConfig>,
+        announcements: crate::config::AnnouncementsConfig,
+        intercom: crate::config::IntercomConfig,
+        quick_actions: Vec<crate::config::QuickActionConfig>,
+        floorplan: crate::config::FloorplanConfig,
+    ) -> Arc<Self> {
+        let backlight_entity = backlight_entity.map(|id| Intern::<str>::from(id.as_str()).as_ref());
+        let system_monitor_entities = SystemMonitorEntities::from_config(system_monitor);
+        let map_centre = MapCentre::from_config(map);
+        let routines: Vec<Routine> = routines.into_iter().map(Routine::from_config).collect();
+        let price_entity = energy
+            .price_entity
+            .map(|id| Intern::<str>::from(id.as_str()).as_ref());
+        let recent_colours = load_recent_colours().await;
+        let scheduled_scenes = load_scheduled_scenes().await;
+        let household_notes = load_household_notes().await;
+        let completed_chores = load_completed_chores().await;
+        let chores: Vec<Chore> = chores
+            .into_iter()
+            .map(|chore| Chore::from_config(chore, &completed_chores))
+            .collect();
+        let tts_entity = appliances
+            .tts_entity
+            .map(|id| Intern::<str>::from(id.as_str()).as_ref());
+        let mut appliance_rules: BTreeMap<&'static str, ApplianceRule> = appliances
+            .rules
+            .into_iter()
+            .map(|rule| {
+                (
+                    Intern::<str>::from(rule.entity_id.as_str()).as_ref(),
+                    ApplianceRule::from_config(rule),
+                )
+            })
+            .collect();
+        let announcement_tts_entity = announcements
+            .tts_entity
+            .map(|id| Intern::<str>::from(id.as_str()).as_ref());
+        let chime_url = announcements
+            .chime_url
+            .map(|url| Intern::<str>::from(url.as_str()).as_ref());
+        let mut announcement_rules: BTreeMap<&'static str, AnnouncementRule> = announcements
+            .events
+            .into_iter()
+            .map(|event| {
+                (
+                    Intern::<str>::from(event.entity_id.as_str()).as_ref(),
+                    AnnouncementRule::from_config(event),
+                )
+            })
+            .collect();
+
+        let intercom_rooms: Vec<IntercomRoom> = intercom
+            .rooms
+            .into_iter()
+            .map(IntercomRoom::from_config)
+            .collect();
+
+        let quick_actions: Vec<QuickAction> = quick_actions
+            .into_iter()
+            .map(QuickAction::from_config)
+            .collect();
+
+        let floorplan = FloorplanPlan::from_config(floorplan);
+
+        let entity_names: BTreeMap<&'static str, Box<str>> = entity_names
+            .into_iter()
+            .map(|(id, name)| {
+                (
+                    Intern::<str>::from(id.as_str()).as_ref(),
+                    Box::from(name.as_str()),
+                )
+            })
+            .collect();
+
+        let entity_room_overrides: BTreeMap<&'static str, &'static str> = entity_room_overrides
+            .into_iter()
+            .map(|(entity_id, area_id)| {
+                (
+                    Intern::<str>::from(entity_id.as_str()).as_ref(),
+                    Intern::<str>::from(area_id.as_str()).as_ref(),
+                )
+            })
+            .collect();
+
+        let vacuum_rooms: BTreeMap<&'static str, Vec<VacuumRoom>> = vacuum_rooms
+            .into_iter()
+            .map(|(id, rooms)| {
+                (
+                    Intern::<str>::from(id.as_str()).as_ref(),
+                    rooms.into_iter().map(VacuumRoom::from).collect(),
+                )
+            })
+            .collect();
+
+        let bin_collection_entities: Vec<&'static str> = bin_collection
+            .entities
+            .iter()
+            .map(|id| Intern::<str>::from(id.as_str()).as_ref())
+            .collect();
+
+        let transport_entities: Vec<&'static str> = transport
+            .entities
+            .iter()
+            .map(|id| Intern::<str>::from(id.as_str()).as_ref())
+            .collect();
+
+        // Feeds that don't parse as URLs are simply dropped rather than
+        // failing startup over a typo in `config.toml`.
+        let news_feeds: Vec<Url> = news
+            .feeds
+            .iter()
+            .filter_map(|url| Url::parse(url).ok())
+            .collect();
+        let news_refresh_interval = Duration::from_secs(news.refresh_interval_secs);
+
+        // Alarms with an unparseable time are dropped rather than failing
+        // startup over a typo in `config.toml`.
+        let alarms: Vec<Alarm> = alarms
+            .alarms
+            .into_iter()
+            .filter_map(Alarm::from_config)
+            .collect();
+
+        let state_snapshot = load_state_snapshot().await;
+
+        // Room/area metadata is enough to paint the omni and room pages, and
+        // is far cheaper than the full `GetStates` response below, so it's
+        // fetched first and the `Oracle` handed to the UI as soon as it's
+        // back rather than waiting on states too. Home Assistant's
+        // websocket API has no per-domain equivalent of `get_states`, so
+        // the state list itself still comes back as one response; what's
+        // staggered here is when each domain's slice of it reaches the UI,
+        // in [`Self::hydrate_states`] below.
+        let (rooms, devices, entities) = tokio::join!(
             hass_client.request::<AreaRegistryList<'_>>(HassRequestKind::AreaRegistry),
             hass_client.request::<DeviceRegistryList<'_>>(HassRequestKind::DeviceRegistry),
             hass_client.request::<EntityRegistryList<'_>>(HassRequestKind::EntityRegistry),
-            hass_client.request::<StatesList<'_>>(HassRequestKind::GetStates),
         );
 
-        let rooms = &rooms.get().0;
-        let states = states.get();
+        let rooms_list = &rooms.get().0;
         let devices = &devices.get().0;
         let entities = &entities.get().0;
 
@@ -69,69 +312,507 @@ impl Oracle {
             .filter_map(|v| v.device_id.as_deref().zip(Some(v)))
             .into_group_map();
 
-        let room_devices = devices
+        // Interned to `'static` so they can outlive the registry responses'
+        // `Yoke`s above and move into `Self::hydrate_states`'s background
+        // task.
+        let entity_icons: HashMap<&'static str, &'static str> = entities
             .iter()
-            .filter_map(|v| v.area_id.as_deref().zip(all_entities.get(v.id.as_ref())))
-            .into_group_map();
+            .filter_map(|v| {
+                v.icon.as_deref().map(|icon| {
+                    (
+                        Intern::<str>::from(v.entity_id.as_ref()).as_ref(),
+                        Intern::<str>::from(icon).as_ref(),
+                    )
+                })
+            })
+            .collect();
 
-        let rooms = rooms
+        let entity_device_id: HashMap<&'static str, &'static str> = entities
             .iter()
-            .map(|room| build_room(&room_devices, room))
+            .filter_map(|v| {
+                v.device_id.as_deref().map(|device_id| {
+                    (
+                        Intern::<str>::from(v.entity_id.as_ref()).as_ref(),
+                        Intern::<str>::from(device_id).as_ref(),
+                    )
+                })
+            })
             .collect();
 
-        eprintln!("{rooms:#?}");
+        let device_entities: HashMap<&'static str, Vec<&'static str>> = all_entities
+            .iter()
+            .map(|(device_id, siblings)| {
+                (
+                    Intern::<str>::from(*device_id).as_ref(),
+                    siblings
+                        .iter()
+                        .map(|v| Intern::<str>::from(v.entity_id.as_ref()).as_ref())
+                        .collect(),
+                )
+            })
+            .collect();
 
-        let mut media_players = BTreeMap::new();
-        let mut lights = BTreeMap::new();
-        let mut cameras = BTreeMap::new();
+        let room_entities = group_entities_by_room(
+            devices,
+            entities,
+            &entity_room_overrides,
+            include_unassigned_room,
+            show_hidden_entities,
+        );
 
-        for state in &states.0 {
-            match &state.attributes {
-                StateAttributes::MediaPlayer(attr) => {
-                    media_players.insert(
-                        Intern::<str>::from(state.entity_id.as_ref()).as_ref(),
-                        MediaPlayer::new(attr, &state.state, &hass_client.base),
-                    );
-                }
-                StateAttributes::Light(attr) => {
-                    lights.insert(
-                        Intern::<str>::from(state.entity_id.as_ref()).as_ref(),
-                        Light::from((attr.clone(), state.state.as_ref())),
-                    );
-                }
-                StateAttributes::Camera(attr) => {
-                    cameras.insert(
-                        Intern::<str>::from(state.entity_id.as_ref()).as_ref(),
-                        Camera::new(attr, &hass_client.base),
-                    );
-                }
-                _ => {}
-            }
-        }
+        let unassigned_area: Option<Area> = include_unassigned_room.then(|| Area {
+            aliases: Vec::new(),
+            area_id: Cow::Borrowed(UNASSIGNED_ROOM_ID),
+            name: Cow::Borrowed("Other"),
+            picture: None,
+        });
+
+        let rooms = rooms_list
+            .iter()
+            .chain(unassigned_area.iter())
+            .map(|room| build_room(&room_entities, room))
+            .collect();
+
+        eprintln!("{rooms:#?}");
+
+        // Seeded from last time's persisted snapshot so an entity still
+        // shows *something* if `GetStates` below doesn't mention it again;
+        // every entity that does gets its real value inserted over this a
+        // few lines down.
+        let mut media_players: BTreeMap<&'static str, MediaPlayer> = state_snapshot
+            .media_players
+            .iter()
+            .map(|(id, snapshot)| {
+                (
+                    Intern::<str>::from(id.as_str()).as_ref(),
+                    MediaPlayer::Speaker(MediaPlayerSpeaker::placeholder(snapshot)),
+                )
+            })
+            .collect();
+        let mut lights: BTreeMap<&'static str, Light> = state_snapshot
+            .lights
+            .iter()
+            .map(|(id, snapshot)| {
+                (
+                    Intern::<str>::from(id.as_str()).as_ref(),
+                    Light::placeholder(snapshot),
+                )
+            })
+            .collect();
 
+        // Everything below is populated once `Self::hydrate_states`'s
+        // `GetStates` response comes back, after the `Oracle` this
+        // constructs is already in the UI's hands.
         let (entity_updates, _) = broadcast::channel(10);
+        let (appliance_finished, _) = broadcast::channel(10);
+        let (announcement_fired, _) = broadcast::channel(10);
+        let (local_timer_finished, _) = broadcast::channel(10);
+
+        // A placeholder until `Self::hydrate_states` reports the real
+        // reading; `condition` is `WeatherCondition::default()`
+        // (`Unknown`).
+        let weather = state_snapshot.weather.unwrap_or(Weather {
+            temperature: 0,
+            high: 0,
+            low: 0,
+            condition: WeatherCondition::default() as u16,
+        });
 
         let this = Arc::new(Self {
             client: hass_client,
-            rooms,
-            weather: Atomic::new(Weather::parse_from_states(states)),
+            rooms: Mutex::new(rooms),
+            entity_room_overrides,
+            include_unassigned_room,
+            show_hidden_entities,
+            weather: Atomic::new(weather),
             media_players: Mutex::new(media_players),
             lights: Mutex::new(lights),
             entity_updates: entity_updates.clone(),
-            cameras: Mutex::new(cameras),
+            cameras: Mutex::new(BTreeMap::new()),
+            weather_alert: Mutex::new(None),
+            shopping_list: Mutex::new(Vec::new()),
+            timers: Mutex::new(BTreeMap::new()),
+            recently_played: Mutex::new(BTreeMap::new()),
+            backlight_entity,
+            night_backlight,
+            sun_above_horizon: Atomic::new(true),
+            stale: Atomic::new(false),
+            entity_names: Mutex::new(entity_names),
+            weather_entity_id: Mutex::new(None),
+            daily_forecast: Mutex::new(Vec::new()),
+            hourly_forecast: Mutex::new(Vec::new()),
+            vacuums: Mutex::new(BTreeMap::new()),
+            vacuum_rooms,
+            covers: Mutex::new(BTreeMap::new()),
+            humidifiers: Mutex::new(BTreeMap::new()),
+            thermostats: Mutex::new(BTreeMap::new()),
+            occupancy: Mutex::new(BTreeMap::new()),
+            updates: Mutex::new(BTreeMap::new()),
+            sensors: Mutex::new(BTreeMap::new()),
+            numbers: Mutex::new(BTreeMap::new()),
+            switches: Mutex::new(BTreeMap::new()),
+            system_monitor_entities,
+            people: Mutex::new(BTreeMap::new()),
+            map_centre,
+            routines,
+            recent_colours: Mutex::new(recent_colours),
+            colour_loop_lights: Mutex::new(HashSet::new()),
+            light_snapshots: Mutex::new(BTreeMap::new()),
+            price_entity,
+            energy_price: Mutex::new(None),
+            plants: Mutex::new(BTreeMap::new()),
+            tts_entity,
+            appliances: Mutex::new(appliance_rules),
+            appliance_finished,
+            announcement_tts_entity,
+            chime_url,
+            announcements: Mutex::new(announcement_rules),
+            announcement_fired,
+            bin_collection_entities,
+            bins: Mutex::new(BTreeMap::new()),
+            transport_entities,
+            departures: Mutex::new(BTreeMap::new()),
+            news_feeds,
+            news_refresh_interval,
+            alarms: Mutex::new(alarms),
+            alarms_fired_today: Mutex::new(BTreeMap::new()),
+            remotes: Mutex::new(BTreeMap::new()),
+            buttons: Mutex::new(BTreeMap::new()),
+            scheduled_scenes: Mutex::new(scheduled_scenes),
+            scheduled_scenes_fired_today: Mutex::new(BTreeMap::new()),
+            local_timers: Mutex::new(Vec::new()),
+            local_timer_finished,
+            household_notes: Mutex::new(household_notes),
+            chores: Mutex::new(chores),
+            intercom_rooms,
+            quick_actions,
+            floorplan,
         });
 
+        this.clone()
+            .hydrate_states(entity_icons, entity_device_id, device_entities);
         this.clone().spawn_worker();
 
         this
     }
 
-    pub fn rooms(&self) -> impl Iterator<Item = (&'static str, &'_ Room)> + '_ {
-        self.rooms.iter().map(|(k, v)| (*k, v))
+    /// Fetches the full `GetStates` response and uses it to populate
+    /// everything [`Self::new`] left empty — lights, media players, and
+    /// every other per-domain map — firing [`Self::entity_updates`] as each
+    /// entity lands so pages already on screen pick the data up as it
+    /// arrives, instead of waiting on this to finish before showing
+    /// anything. Spawned as a background task rather than `await`ed so
+    /// [`Self::new`] can hand the UI a mostly-empty `Oracle` immediately
+    /// after the (much cheaper) registry round-trip above.
+    fn hydrate_states(
+        self: Arc<Self>,
+        entity_icons: HashMap<&'static str, &'static str>,
+        entity_device_id: HashMap<&'static str, &'static str>,
+        device_entities: HashMap<&'static str, Vec<&'static str>>,
+    ) {
+        tokio::spawn(async move {
+            let states = self
+                .client
+                .request::<StatesList<'_>>(HassRequestKind::GetStates)
+                .await;
+            let states_list = states.get();
+            let states = &states_list.0;
+
+            let mut weather_entity_id = None;
+            let mut sun_above_horizon = true;
+            let mut weather_alert = None;
+            let mut energy_price = None;
+
+            for state in states {
+                let entity_id = Intern::<str>::from(state.entity_id.as_ref()).as_ref();
+
+                match &state.attributes {
+                    StateAttributes::Sun(_) => {
+                        sun_above_horizon = state.state.as_ref() == "above_horizon";
+                    }
+                    StateAttributes::Weather(_) => {
+                        weather_entity_id = Some(entity_id);
+                    }
+                    StateAttributes::MediaPlayer(attr) => {
+                        let mut media_player =
+                            MediaPlayer::new(attr, &state.state, &self.client.base);
+                        if let MediaPlayer::Speaker(ref mut speaker) = media_player {
+                            if let Some(name) = self.entity_names.lock().get(entity_id) {
+                                speaker.friendly_name = name.clone();
+                            }
+                        }
+
+                        self.media_players.lock().insert(entity_id, media_player);
+                    }
+                    StateAttributes::Light(attr) => {
+                        let mut light = Light::from((attr.clone(), state.state.as_ref()));
+                        light.icon = entity_icons.get(entity_id).map(|icon| Box::from(*icon));
+
+                        if let Some(name) = self.entity_names.lock().get(entity_id) {
+                            light.friendly_name = name.clone();
+                        }
+
+                        self.lights.lock().insert(entity_id, light);
+                    }
+                    StateAttributes::Camera(attr) => {
+                        self.cameras
+                            .lock()
+                            .insert(entity_id, Camera::new(attr, &self.client.base));
+                    }
+                    StateAttributes::BinarySensor(attr) => {
+                        if let Some(alert) = WeatherAlert::from_state(&state.state, attr) {
+                            weather_alert = Some(alert);
+                        }
+
+                        if is_occupancy_sensor(attr) {
+                            self.occupancy
+                                .lock()
+                                .insert(entity_id, state.state.as_ref() == "on");
+                        }
+                    }
+                    StateAttributes::Timer(attr) => {
+                        self.timers
+                            .lock()
+                            .insert(entity_id, Timer::from((attr, state.state.as_ref())));
+                    }
+                    StateAttributes::Vacuum(attr) => {
+                        self.vacuums.lock().insert(
+                            entity_id,
+                            Vacuum::new(attr, &state.state, &self.client.base),
+                        );
+                    }
+                    StateAttributes::Cover(attr) => {
+                        self.covers
+                            .lock()
+                            .insert(entity_id, Cover::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Humidifier(attr) => {
+                        self.humidifiers
+                            .lock()
+                            .insert(entity_id, Humidifier::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Climate(attr) => {
+                        self.thermostats
+                            .lock()
+                            .insert(entity_id, Thermostat::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Update(attr) => {
+                        self.updates
+                            .lock()
+                            .insert(entity_id, Update::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Sensor(attr) => {
+                        if Some(entity_id) == self.price_entity {
+                            energy_price = EnergyPrice::from_state(attr, state.state.as_ref());
+                        }
+
+                        if attr.device_class.as_deref() == Some("moisture") {
+                            if let Some(plant) =
+                                Plant::from_moisture_sensor(attr, state.state.as_ref())
+                            {
+                                self.plants.lock().insert(entity_id, plant);
+                            }
+                        }
+
+                        if self.bin_collection_entities.contains(&entity_id) {
+                            self.bins.lock().insert(
+                                entity_id,
+                                BinCollection::new(
+                                    attr.friendly_name.as_deref(),
+                                    state.state.as_ref(),
+                                ),
+                            );
+                        }
+
+                        if self.transport_entities.contains(&entity_id) {
+                            self.departures.lock().insert(
+                                entity_id,
+                                Departure::new(attr.friendly_name.as_deref(), state.state.as_ref()),
+                            );
+                        }
+
+                        self.sensors
+                            .lock()
+                            .insert(entity_id, Sensor::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Plant(attr) => {
+                        self.plants
+                            .lock()
+                            .insert(entity_id, Plant::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Person(attr) | StateAttributes::DeviceTracker(attr) => {
+                        self.people
+                            .lock()
+                            .insert(entity_id, Person::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Remote(attr) => {
+                        self.remotes
+                            .lock()
+                            .insert(entity_id, Remote::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Button(attr) => {
+                        self.buttons.lock().insert(entity_id, Button::new(attr));
+                    }
+                    StateAttributes::Number(attr) => {
+                        self.numbers
+                            .lock()
+                            .insert(entity_id, NumberEntity::new(attr, state.state.as_ref()));
+                    }
+                    StateAttributes::Switch(attr) => {
+                        self.switches
+                            .lock()
+                            .insert(entity_id, SwitchEntity::new(attr, state.state.as_ref()));
+                    }
+                    _ => {}
+                }
+
+                if let Some(rule) = self.appliances.lock().get_mut(entity_id) {
+                    rule.running = state.state.as_ref() == &*rule.running_state;
+                }
+
+                if let Some(rule) = self.announcements.lock().get_mut(entity_id) {
+                    rule.last_state = Box::from(state.state.as_ref());
+                }
+
+                let _res = self.entity_updates.send(Arc::from(entity_id));
+            }
+
+            for (light_id, light) in self.lights.lock().iter_mut() {
+                let Some(siblings) = entity_device_id
+                    .get(light_id)
+                    .and_then(|device_id| device_entities.get(device_id))
+                else {
+                    continue;
+                };
+
+                for sibling_id in siblings {
+                    let Some(sensor) = self.sensors.lock().get(sibling_id).cloned() else {
+                        continue;
+                    };
+
+                    match sensor.unit_of_measurement.as_deref() {
+                        Some("W") => light.power_entity = Some(*sibling_id),
+                        Some("kWh") => light.energy_entity = Some(*sibling_id),
+                        _ => {}
+                    }
+                }
+            }
+
+            for (speaker_id, media_player) in self.media_players.lock().iter_mut() {
+                let MediaPlayer::Speaker(speaker) = media_player else {
+                    continue;
+                };
+
+                let Some(siblings) = entity_device_id
+                    .get(speaker_id)
+                    .and_then(|device_id| device_entities.get(device_id))
+                else {
+                    continue;
+                };
+
+                for sibling_id in siblings {
+                    let name = sibling_id.to_lowercase();
+
+                    if sibling_id.starts_with("number.") {
+                        if name.contains("bass") {
+                            speaker.bass_entity = Some(*sibling_id);
+                        } else if name.contains("treble") {
+                            speaker.treble_entity = Some(*sibling_id);
+                        }
+                    } else if sibling_id.starts_with("switch.") {
+                        if name.contains("loudness") {
+                            speaker.loudness_entity = Some(*sibling_id);
+                        } else if name.contains("night") {
+                            speaker.night_mode_entity = Some(*sibling_id);
+                        }
+                    }
+                }
+            }
+
+            *self.weather_alert.lock() = weather_alert;
+            *self.energy_price.lock() = energy_price;
+            self.sun_above_horizon
+                .store(sun_above_horizon, Ordering::Release);
+            *self.weather_entity_id.lock() = weather_entity_id;
+
+            self.refresh_shopping_list().await;
+
+            if weather_entity_id.is_some() {
+                // Populates `daily_forecast`/`hourly_forecast` first, since
+                // `Weather::parse_from_states` folds today's high/low out of
+                // the daily forecast.
+                self.refresh_forecasts().await;
+                self.weather.store(
+                    Weather::parse_from_states(states_list, &self.daily_forecast.lock()),
+                    Ordering::Release,
+                );
+                let _res = self
+                    .entity_updates
+                    .send(Arc::from(weather_entity_id.unwrap()));
+            }
+        });
+    }
+
+    pub fn rooms(&self) -> BTreeMap<&'static str, Room> {
+        (*self.rooms.lock()).clone()
+    }
+
+    /// `None` if `id` isn't a known room -- rooms are rebuilt live
+    /// ([`Self::rebuild_rooms`]), so a room can be deleted from Home
+    /// Assistant's area registry between a caller deciding to open it and
+    /// this actually running.
+    pub fn room(&self, id: &str) -> Option<Room> {
+        self.rooms.lock().get(id).cloned()
+    }
+
+    /// Re-fetches the area/device/entity registries and rebuilds [`Self::rooms`]
+    /// from scratch, for [`Self::spawn_worker`] to call whenever Home Assistant
+    /// reports one of them changed. Cheap enough to redo wholesale rather than
+    /// try to patch the affected room in place, the same tradeoff [`Self::new`]
+    /// makes on first fetch.
+    async fn rebuild_rooms(&self) {
+        let (rooms, devices, entities) = tokio::join!(
+            self.client
+                .request::<AreaRegistryList<'_>>(HassRequestKind::AreaRegistry),
+            self.client
+                .request::<DeviceRegistryList<'_>>(HassRequestKind::DeviceRegistry),
+            self.client
+                .request::<EntityRegistryList<'_>>(HassRequestKind::EntityRegistry),
+        );
+
+        let rooms_list = &rooms.get().0;
+        let devices = &devices.get().0;
+        let entities = &entities.get().0;
+
+        let room_entities = group_entities_by_room(
+            devices,
+            entities,
+            &self.entity_room_overrides,
+            self.include_unassigned_room,
+            self.show_hidden_entities,
+        );
+
+        let unassigned_area: Option<Area> = self.include_unassigned_room.then(|| Area {
+            aliases: Vec::new(),
+            area_id: Cow::Borrowed(UNASSIGNED_ROOM_ID),
+            name: Cow::Borrowed("Other"),
+            picture: None,
+        });
+
+        *self.rooms.lock() = rooms_list
+            .iter()
+            .chain(unassigned_area.iter())
+            .map(|room| build_room(&room_entities, room))
+            .collect();
+
+        let _res = self.entity_updates.send(Arc::from("rooms"));
     }
 
-    pub fn room(&self, id: &str) -> &Room {
-        self.rooms.get(id).unwrap()
+    pub fn subscribe_rooms(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(v.as_ref() == "rooms"))
+            .map(|_| ())
     }
 
     pub fn current_weather(&self) -> Weather {
@@ -145,384 +826,3590 @@ impl Oracle {
             .map(|_| ())
     }
 
+    pub fn daily_forecast(&self) -> Vec<ForecastDay> {
+        self.daily_forecast.lock().clone()
+    }
+
+    pub fn hourly_forecast(&self) -> Vec<ForecastDay> {
+        self.hourly_forecast.lock().clone()
+    }
+
+    /// Re-fetches both forecasts via `weather.get_forecasts`. Home Assistant
+    /// doesn't push forecast updates over the event stream, so this needs to
+    /// be polled; [`Self::spawn_worker`] calls it periodically.
+    async fn refresh_forecasts(&self) {
+        let Some(weather_entity_id) = *self.weather_entity_id.lock() else {
+            return;
+        };
+
+        let (daily, hourly) = tokio::join!(
+            fetch_forecast(&self.client, weather_entity_id, ForecastType::Daily),
+            fetch_forecast(&self.client, weather_entity_id, ForecastType::Hourly),
+        );
+
+        *self.daily_forecast.lock() = daily;
+        *self.hourly_forecast.lock() = hourly;
+
+        let _res = self.entity_updates.send(Arc::from(weather_entity_id));
+    }
+
     pub fn cameras(&self) -> BTreeMap<&'static str, Camera> {
         (*self.cameras.lock()).clone()
     }
 
-    pub fn subscribe_all_cameras(&self) -> impl Stream<Item = ()> {
+    pub fn vacuums(&self) -> BTreeMap<&'static str, Vacuum> {
+        (*self.vacuums.lock()).clone()
+    }
+
+    pub fn remotes(&self) -> BTreeMap<&'static str, Remote> {
+        (*self.remotes.lock()).clone()
+    }
+
+    pub fn subscribe_all_remotes(&self) -> impl Stream<Item = ()> {
         BroadcastStream::new(self.entity_updates.subscribe())
             .filter_map(|v| future::ready(v.ok()))
-            .filter(|v| future::ready(v.starts_with("camera.")))
+            .filter(|v| future::ready(v.starts_with("remote.")))
             .map(|_| ())
     }
 
-    pub fn subscribe_id(&self, id: &'static str) -> impl Stream<Item = ()> {
+    /// `button` entities, e.g. `wake_on_lan` "Wake" buttons, shown as
+    /// dedicated quick-action buttons on the omni page.
+    pub fn buttons(&self) -> BTreeMap<&'static str, Button> {
+        (*self.buttons.lock()).clone()
+    }
+
+    pub async fn press_button(&self, entity_id: &'static str) {
+        self.call_service("button", "press", entity_id, serde_json::Value::Null)
+            .await;
+    }
+
+    /// Starts `entity_id`'s named `activity` (Harmony-style hubs), or just
+    /// turns it on if it doesn't have activities.
+    pub async fn start_remote_activity(&self, entity_id: &'static str, activity: Option<String>) {
+        let service_data = match activity {
+            Some(activity) => serde_json::json!({ "activity": activity }),
+            None => serde_json::json!({}),
+        };
+
+        self.call_service("remote", "turn_on", entity_id, service_data)
+            .await;
+    }
+
+    pub async fn stop_remote_activity(&self, entity_id: &'static str) {
+        self.call_service("remote", "turn_off", entity_id, serde_json::json!({}))
+            .await;
+    }
+
+    /// Sends a raw `remote.send_command`, e.g. a D-pad direction or a custom
+    /// command typed in on the remote page. Some integrations (Harmony) also
+    /// need a `device` slug, which isn't modelled here since it isn't exposed
+    /// anywhere in `config.toml` yet.
+    pub async fn send_remote_command(&self, entity_id: &'static str, command: String) {
+        self.call_service(
+            "remote",
+            "send_command",
+            entity_id,
+            serde_json::json!({ "command": command }),
+        )
+        .await;
+    }
+
+    /// Configured room segments for `entity_id`'s targeted-clean map, if any
+    /// were set in `config.toml`.
+    pub fn vacuum_rooms(&self, entity_id: &str) -> &[VacuumRoom] {
+        self.vacuum_rooms.get(entity_id).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn subscribe_all_vacuums(&self) -> impl Stream<Item = ()> {
         BroadcastStream::new(self.entity_updates.subscribe())
             .filter_map(|v| future::ready(v.ok()))
-            .filter(move |v| future::ready(&**v == id))
+            .filter(|v| future::ready(v.starts_with("vacuum.")))
             .map(|_| ())
     }
 
-    pub fn fetch_light(&self, entity_id: &'static str) -> Option<Light> {
-        self.lights.lock().get(entity_id).cloned()
+    /// Starts a targeted clean of a single room segment via
+    /// `vacuum.send_command`, using the `app_segment_clean` command most
+    /// segment-map-capable vacuum integrations (e.g. Xiaomi/Roborock) expose.
+    pub async fn clean_vacuum_segment(&self, entity_id: &'static str, segment_id: u32) {
+        self.call_service(
+            "vacuum",
+            "send_command",
+            entity_id,
+            serde_json::json!({
+                "command": "app_segment_clean",
+                "params": [segment_id],
+            }),
+        )
+        .await;
     }
 
-    pub fn speaker(&self, speaker_id: &'static str) -> EloquentSpeaker<'_> {
-        EloquentSpeaker {
-            speaker_id,
-            oracle: self,
-        }
+    pub fn current_weather_alert(&self) -> Option<WeatherAlert> {
+        self.weather_alert.lock().clone()
     }
 
-    pub async fn set_light_state(&self, entity_id: &'static str, on: bool) {
+    pub fn subscribe_weather_alert(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(v.starts_with("binary_sensor.")))
+            .map(|_| ())
+    }
+
+    pub fn shopping_list(&self) -> Vec<ShoppingListItem> {
+        self.shopping_list.lock().clone()
+    }
+
+    pub fn subscribe_shopping_list(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(&**v == "shopping_list"))
+            .map(|_| ())
+    }
+
+    pub async fn add_shopping_list_item(&self, name: String) {
+        self.client.shopping_list_add_item(name).await;
+        self.refresh_shopping_list().await;
+    }
+
+    pub async fn set_shopping_list_item_complete(&self, item_id: String, complete: bool) {
+        self.client
+            .shopping_list_set_complete(item_id, complete)
+            .await;
+        self.refresh_shopping_list().await;
+    }
+
+    pub async fn remove_shopping_list_item(&self, item_id: String) {
+        self.client.shopping_list_remove_item(item_id).await;
+        self.refresh_shopping_list().await;
+    }
+
+    pub fn timers(&self) -> BTreeMap<&'static str, Timer> {
+        (*self.timers.lock()).clone()
+    }
+
+    pub fn subscribe_id_prefix(&self, prefix: &'static str) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(move |v| future::ready(v.starts_with(prefix)))
+            .map(|_| ())
+    }
+
+    pub async fn start_timer(&self, entity_id: &'static str, duration: Option<String>) {
         let _res = self
             .client
             .call_service(
                 entity_id,
-                CallServiceRequestData::Light(if on {
-                    CallServiceRequestLight::TurnOn(CallServiceRequestLightTurnOn {
-                        brightness: None,
-                        hs_color: None,
-                    })
-                } else {
-                    CallServiceRequestLight::TurnOff
-                }),
+                CallServiceRequestData::Timer(CallServiceRequestTimer::Start(
+                    CallServiceRequestTimerStart { duration },
+                )),
             )
             .await;
     }
 
-    pub async fn update_light(
-        &self,
-        entity_id: &'static str,
-        hue: f32,
-        saturation: f32,
-        brightness: f32,
-    ) {
+    pub async fn cancel_timer(&self, entity_id: &'static str) {
         let _res = self
             .client
             .call_service(
                 entity_id,
-                CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
-                    CallServiceRequestLightTurnOn {
-                        hs_color: Some((hue, saturation * 100.)),
-                        brightness: Some(clamp_to_u8(brightness)),
-                    },
-                )),
+                CallServiceRequestData::Timer(CallServiceRequestTimer::Cancel),
             )
             .await;
     }
 
-    pub fn spawn_worker(self: Arc<Self>) {
-        tokio::spawn(async move {
-            let mut recv = self.client.subscribe();
-            let mut second_tick = tokio::time::interval(Duration::from_secs(1));
-            second_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    /// Panel-local kitchen timers, e.g. "9 minutes for the pasta" — these
+    /// aren't Home Assistant `timer.*` entities (see [`Self::timers`]
+    /// above), so they work even if nothing in HA models them, and multiple
+    /// can run at once.
+    pub fn local_timers(&self) -> Vec<LocalTimer> {
+        self.local_timers.lock().clone()
+    }
 
-            let mut active_media_players = self
-                .media_players
-                .lock()
-                .iter()
-                .filter(|(_k, v)| v.is_playing())
-                .map(|(k, _v)| *k)
-                .collect::<HashSet<_>>();
+    pub fn subscribe_local_timers(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(&**v == "local_timers"))
+            .map(|_| ())
+    }
 
-            loop {
-                tokio::select! {
-                    msg = recv.recv() => match msg {
-                        Ok(msg) => self.handle_state_update_event(&msg, &mut active_media_players),
-                        Err(RecvError::Lagged(_)) => continue,
-                        Err(RecvError::Closed) => break,
-                    },
-                    _ = second_tick.tick(), if !active_media_players.is_empty() => {
-                        self.update_media_player_positions(&active_media_players);
-                    },
-                }
-            }
+    /// A [`LocalTimer`] reaching zero, for the visual/audible alarm overlay
+    /// in `main.rs`.
+    pub fn subscribe_local_timer_finished(&self) -> impl Stream<Item = LocalTimerFinished> {
+        BroadcastStream::new(self.local_timer_finished.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+    }
+
+    pub async fn start_local_timer(&self, label: String, duration_secs: u32) {
+        let mut local_timers = self.local_timers.lock();
+        let id = local_timers.iter().map(|timer| timer.id).max().unwrap_or(0) + 1;
+
+        local_timers.push(LocalTimer {
+            id,
+            label: Box::from(label.trim()),
+            finishes_at: OffsetDateTime::now_utc() + Duration::from_secs(u64::from(duration_secs)),
         });
+        drop(local_timers);
+
+        let _res = self.entity_updates.send(Arc::from("local_timers"));
     }
 
-    fn update_media_player_positions(&self, active_media_players: &HashSet<&'static str>) {
-        let mut media_players = self.media_players.lock();
+    pub async fn cancel_local_timer(&self, id: u64) {
+        self.local_timers.lock().retain(|timer| timer.id != id);
+        let _res = self.entity_updates.send(Arc::from("local_timers"));
+    }
 
-        for entity_id in active_media_players {
-            let Some(MediaPlayer::Speaker(speaker)) = media_players.get_mut(entity_id) else {
-                continue;
-            };
+    /// Nudges the countdown display every second, and fires
+    /// [`Self::local_timer_finished`] for any [`LocalTimer`] that just hit
+    /// zero. Called once a second by [`Self::spawn_worker`], same as
+    /// [`Self::tick_timers`].
+    fn tick_local_timers(&self) {
+        let now = OffsetDateTime::now_utc();
+        let mut local_timers = self.local_timers.lock();
 
-            speaker.actual_media_position = speaker
-                .media_position
-                .zip(speaker.media_position_updated_at)
-                .zip(Some(speaker.state))
-                .map(calculate_actual_media_position);
+        if local_timers.is_empty() {
+            return;
+        }
 
-            let _res = self.entity_updates.send(Arc::from(*entity_id));
+        let (finished, still_running): (Vec<_>, Vec<_>) = local_timers
+            .drain(..)
+            .partition(|timer| timer.finishes_at <= now);
+
+        *local_timers = still_running;
+        drop(local_timers);
+
+        for timer in finished {
+            let _res = self
+                .local_timer_finished
+                .send(LocalTimerFinished { label: timer.label });
         }
+
+        let _res = self.entity_updates.send(Arc::from("local_timers"));
     }
 
-    fn handle_state_update_event(
+    /// Escape hatch for calling a Home Assistant service on a domain that
+    /// doesn't have typed support in [`CallServiceRequestData`] yet (e.g.
+    /// covers, scenes, climate). Prefer a typed method where one exists.
+    pub async fn call_service(
         &self,
-        msg: &Yoke<Event<'static>, String>,
-        active_media_players: &mut HashSet<&'static str>,
+        domain: impl Into<String>,
+        service: impl Into<String>,
+        entity_id: &'static str,
+        service_data: serde_json::Value,
     ) {
-        match msg.get() {
-            Event::StateChanged(state_changed) => {
-                match &state_changed.new_state.attributes {
-                    StateAttributes::MediaPlayer(attrs) => {
-                        let entity_id =
-                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
-                        eprintln!("{entity_id} updated");
-                        let new_state = MediaPlayer::new(
-                            attrs,
-                            &state_changed.new_state.state,
-                            &self.client.base,
-                        );
+        let _res = self
+            .client
+            .call_service_generic(domain, service, entity_id, service_data)
+            .await;
+    }
 
-                        if new_state.is_playing() {
-                            active_media_players.insert(entity_id);
-                        } else {
-                            active_media_players.remove(entity_id);
-                        }
+    async fn refresh_shopping_list(&self) {
+        let items = self
+            .client
+            .shopping_list_items()
+            .await
+            .get()
+            .0
+            .iter()
+            .map(ShoppingListItem::from)
+            .collect();
 
-                        self.media_players.lock().insert(entity_id, new_state);
-                    }
-                    StateAttributes::Weather(attrs) => {
-                        self.weather.store(
-                            Weather::parse_from_state_and_attributes(
-                                state_changed.new_state.state.as_ref(),
-                                attrs,
-                            ),
-                            Ordering::Release,
-                        );
-                    }
-                    StateAttributes::Light(attrs) => {
-                        self.lights.lock().insert(
-                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
-                            Light::from((attrs.clone(), state_changed.new_state.state.as_ref())),
-                        );
-                    }
-                    StateAttributes::Camera(attrs) => {
-                        self.cameras.lock().insert(
-                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
-                            Camera::new(attrs, &self.client.base),
-                        );
-                    }
-                    _ => {
-                        // TODO
-                    }
-                }
+        *self.shopping_list.lock() = items;
+
+        let _res = self.entity_updates.send(Arc::from("shopping_list"));
+    }
+
+    pub fn subscribe_all_cameras(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(v.starts_with("camera.")))
+            .map(|_| ())
+    }
+
+    pub fn subscribe_id(&self, id: &'static str) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(move |v| future::ready(&**v == id))
+            .map(|_| ())
+    }
+
+    pub fn fetch_light(&self, entity_id: &'static str) -> Option<Light> {
+        self.lights.lock().get(entity_id).cloned()
+    }
+
+    pub fn fetch_cover(&self, entity_id: &'static str) -> Option<Cover> {
+        self.covers.lock().get(entity_id).cloned()
+    }
+
+    pub async fn open_cover(&self, entity_id: &'static str) {
+        self.call_service("cover", "open_cover", entity_id, serde_json::Value::Null)
+            .await;
+    }
+
+    pub async fn close_cover(&self, entity_id: &'static str) {
+        self.call_service("cover", "close_cover", entity_id, serde_json::Value::Null)
+            .await;
+    }
+
+    pub fn fetch_humidifier(&self, entity_id: &'static str) -> Option<Humidifier> {
+        self.humidifiers.lock().get(entity_id).cloned()
+    }
+
+    pub async fn set_humidifier_target(&self, entity_id: &'static str, humidity: u8) {
+        self.call_service(
+            "humidifier",
+            "set_humidity",
+            entity_id,
+            serde_json::json!({ "humidity": humidity }),
+        )
+        .await;
+    }
+
+    pub async fn set_humidifier_mode(&self, entity_id: &'static str, mode: Box<str>) {
+        self.call_service(
+            "humidifier",
+            "set_mode",
+            entity_id,
+            serde_json::json!({ "mode": mode }),
+        )
+        .await;
+    }
+
+    pub fn fetch_thermostat(&self, entity_id: &'static str) -> Option<Thermostat> {
+        self.thermostats.lock().get(entity_id).cloned()
+    }
+
+    pub async fn set_hvac_mode(&self, entity_id: &'static str, hvac_mode: Box<str>) {
+        self.call_service(
+            "climate",
+            "set_hvac_mode",
+            entity_id,
+            serde_json::json!({ "hvac_mode": hvac_mode }),
+        )
+        .await;
+    }
+
+    pub async fn set_fan_mode(&self, entity_id: &'static str, fan_mode: Box<str>) {
+        self.call_service(
+            "climate",
+            "set_fan_mode",
+            entity_id,
+            serde_json::json!({ "fan_mode": fan_mode }),
+        )
+        .await;
+    }
+
+    pub async fn set_preset_mode(&self, entity_id: &'static str, preset_mode: Box<str>) {
+        self.call_service(
+            "climate",
+            "set_preset_mode",
+            entity_id,
+            serde_json::json!({ "preset_mode": preset_mode }),
+        )
+        .await;
+    }
+
+    pub fn updates(&self) -> BTreeMap<&'static str, Update> {
+        (*self.updates.lock()).clone()
+    }
+
+    pub async fn install_update(&self, entity_id: &'static str) {
+        self.call_service("update", "install", entity_id, serde_json::Value::Null)
+            .await;
+    }
+
+    /// Restarts Home Assistant itself (`homeassistant.restart`). Doesn't
+    /// target a specific entity, so it bypasses [`Self::call_service`].
+    pub async fn restart_home_assistant(&self) {
+        let _res = self
+            .client
+            .call_service_domain("homeassistant", "restart")
+            .await;
+    }
+
+    /// Reloads all YAML configuration (`homeassistant.reload_all`) without a
+    /// full restart.
+    pub async fn reload_all(&self) {
+        let _res = self
+            .client
+            .call_service_domain("homeassistant", "reload_all")
+            .await;
+    }
+
+    /// Asks Home Assistant to validate its configuration
+    /// (`homeassistant.check_config`) without applying it.
+    pub async fn check_config(&self) {
+        let _res = self
+            .client
+            .call_service_domain("homeassistant", "check_config")
+            .await;
+    }
+
+    /// The most recent connection-level log lines, for the exportable
+    /// diagnostics bundle. See [`crate::diagnostics`].
+    pub fn diagnostic_log(&self) -> Vec<Box<str>> {
+        self.client.diagnostic_log()
+    }
+
+    pub fn fetch_sensor(&self, entity_id: &'static str) -> Option<Sensor> {
+        self.sensors.lock().get(entity_id).cloned()
+    }
+
+    pub fn fetch_switch(&self, entity_id: &'static str) -> Option<SwitchEntity> {
+        self.switches.lock().get(entity_id).cloned()
+    }
+
+    /// The configured systemmonitor sensors, for the omni page's system
+    /// monitor card. Each field is `None` if its entity id wasn't set in
+    /// [`crate::config::SystemMonitorConfig`], or if that entity doesn't
+    /// (yet) exist.
+    pub fn system_monitor(&self) -> SystemMonitorStats {
+        let sensors = self.sensors.lock();
+
+        SystemMonitorStats {
+            cpu: self
+                .system_monitor_entities
+                .cpu
+                .and_then(|id| sensors.get(id).cloned()),
+            memory: self
+                .system_monitor_entities
+                .memory
+                .and_then(|id| sensors.get(id).cloned()),
+            disk: self
+                .system_monitor_entities
+                .disk
+                .and_then(|id| sensors.get(id).cloned()),
+            temperature: self
+                .system_monitor_entities
+                .temperature
+                .and_then(|id| sensors.get(id).cloned()),
+        }
+    }
+
+    /// Tracked `person`/`device_tracker` entities, for the map page.
+    pub fn people(&self) -> BTreeMap<&'static str, Person> {
+        (*self.people.lock()).clone()
+    }
+
+    /// The map page's centre point and zoom level, from
+    /// [`crate::config::MapConfig`]. `None` if `home-latitude`/
+    /// `home-longitude` weren't both set, in which case the map link is
+    /// hidden entirely.
+    pub fn map_centre(&self) -> Option<MapCentre> {
+        self.map_centre
+    }
+
+    /// Rooms reachable from the omni page's intercom card, in `config.toml`
+    /// order.
+    pub fn intercom_rooms(&self) -> &[IntercomRoom] {
+        &self.intercom_rooms
+    }
+
+    /// Buttons on the persistent quick-actions bar, in `config.toml` order.
+    pub fn quick_actions(&self) -> &[QuickAction] {
+        &self.quick_actions
+    }
+
+    /// The floorplan page's plan and hotspots, from
+    /// [`crate::config::FloorplanConfig`]. `None` unless `svg-path` was set,
+    /// in which case the floorplan link on the omni page is hidden.
+    pub fn floorplan(&self) -> Option<&FloorplanPlan> {
+        self.floorplan.as_ref()
+    }
+
+    /// Uploads a recorded intercom clip (see [`crate::intercom`]) to Home
+    /// Assistant's local media source, returning the `media_content_id` to
+    /// play it back via [`EloquentSpeaker::play_intercom_clip`].
+    pub async fn upload_intercom_clip(&self, wav: Vec<u8>) -> Result<String, String> {
+        self.client
+            .upload_local_media("intercom-clip.wav", wav)
+            .await
+    }
+
+    /// Configured routine buttons, in `config.toml` order, for the omni page.
+    pub fn routines(&self) -> &[Routine] {
+        &self.routines
+    }
+
+    /// The configured electricity price sensor's current rate and today's
+    /// hourly curve, for the omni page's price card. `None` if
+    /// `energy.price-entity` wasn't set, or that entity doesn't (yet) exist.
+    pub fn energy_price(&self) -> Option<EnergyPrice> {
+        self.energy_price.lock().clone()
+    }
+
+    /// Plants tracked via a `plant` entity or a standalone moisture sensor,
+    /// for the omni page's plants card.
+    pub fn plants(&self) -> BTreeMap<&'static str, Plant> {
+        (*self.plants.lock()).clone()
+    }
+
+    /// The `tts` entity configured for appliance-finished announcements, if
+    /// any.
+    pub fn tts_entity(&self) -> Option<&'static str> {
+        self.tts_entity
+    }
+
+    pub fn subscribe_appliances_finished(&self) -> impl Stream<Item = ApplianceFinished> {
+        BroadcastStream::new(self.appliance_finished.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+    }
+
+    /// For a status indicator: fires whenever the Home Assistant websocket
+    /// connection goes down or comes back up.
+    pub fn subscribe_connection_status(
+        &self,
+    ) -> impl Stream<Item = crate::hass_client::ConnectionStatus> {
+        BroadcastStream::new(self.client.subscribe_connection_status())
+            .filter_map(|v| future::ready(v.ok()))
+    }
+
+    /// Whether the last known lights/weather/media state shown across the
+    /// panel is stale because the Home Assistant connection is currently
+    /// down, rather than freshly pushed.
+    pub fn is_stale(&self) -> bool {
+        self.stale.load(Ordering::Acquire)
+    }
+
+    pub fn set_stale(&self, stale: bool) {
+        self.stale.store(stale, Ordering::Release);
+    }
+
+    /// Re-applies `entity_names` (`config.toml`'s `entity_names` table,
+    /// reloaded after an edit) to already-known lights and media players, so
+    /// a friendly-name override takes effect without needing a restart.
+    /// Doesn't touch entities that aren't a light or media player: those are
+    /// the only entities that take a friendly-name override from
+    /// [`Self::entity_names`].
+    pub fn apply_entity_names(&self, entity_names: HashMap<String, String>) {
+        let entity_names: BTreeMap<&'static str, Box<str>> = entity_names
+            .into_iter()
+            .map(|(id, name)| {
+                (
+                    Intern::<str>::from(id.as_str()).as_ref(),
+                    Box::from(name.as_str()),
+                )
+            })
+            .collect();
+
+        for (id, light) in self.lights.lock().iter_mut() {
+            if let Some(name) = entity_names.get(id) {
+                light.friendly_name = name.clone();
+                let _res = self.entity_updates.send(Arc::from(*id));
+            }
+        }
+
+        for (id, media_player) in self.media_players.lock().iter_mut() {
+            let MediaPlayer::Speaker(speaker) = media_player else {
+                continue;
+            };
+
+            if let Some(name) = entity_names.get(id) {
+                speaker.friendly_name = name.clone();
+                let _res = self.entity_updates.send(Arc::from(*id));
+            }
+        }
+
+        *self.entity_names.lock() = entity_names;
+    }
+
+    /// Builds the persistable snapshot of this `Oracle`'s current lights,
+    /// media players, and weather, for [`Self::save_state_snapshot`].
+    /// Rooms aren't included: a room's membership comes from Home
+    /// Assistant's area/device registry rather than mutable state, so
+    /// there's nothing there to snapshot beyond the lights/media already
+    /// captured here.
+    fn state_snapshot(&self) -> StateSnapshot {
+        let lights = self
+            .lights
+            .lock()
+            .iter()
+            .map(|(id, light)| {
+                (
+                    (*id).to_string(),
+                    LightSnapshot {
+                        friendly_name: light.friendly_name.to_string(),
+                        on: light.on,
+                        brightness: light.brightness,
+                    },
+                )
+            })
+            .collect();
+
+        let media_players = self
+            .media_players
+            .lock()
+            .iter()
+            .filter_map(|(id, media_player)| {
+                let MediaPlayer::Speaker(speaker) = media_player else {
+                    return None;
+                };
+
+                Some((
+                    (*id).to_string(),
+                    MediaPlayerSnapshot {
+                        friendly_name: speaker.friendly_name.to_string(),
+                        state: speaker.state,
+                        media_title: speaker.media_title.as_deref().map(String::from),
+                        media_artist: speaker.media_artist.as_deref().map(String::from),
+                    },
+                ))
+            })
+            .collect();
+
+        StateSnapshot {
+            lights,
+            media_players,
+            weather: Some(self.current_weather()),
+        }
+    }
+
+    /// Persists [`Self::state_snapshot`] to [`STATE_SNAPSHOT_PATH`], so the
+    /// panel can show something other than a blank loading page the next
+    /// time it starts up before Home Assistant answers. Called from
+    /// `main.rs` as the panel is shutting down.
+    pub async fn save_state_snapshot(&self) {
+        let snapshot = self.state_snapshot();
+
+        if let Ok(json) = serde_json::to_string(&snapshot) {
+            let _res = tokio::fs::write(STATE_SNAPSHOT_PATH, json).await;
+        }
+    }
+
+    /// The `tts` entity configured for arbitrary event announcements, if any.
+    /// Separate from [`Self::tts_entity`], which is scoped to
+    /// appliance-finished announcements.
+    pub fn announcement_tts_entity(&self) -> Option<&'static str> {
+        self.announcement_tts_entity
+    }
+
+    pub fn subscribe_announcements(&self) -> impl Stream<Item = Announcement> {
+        BroadcastStream::new(self.announcement_fired.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+    }
+
+    pub fn bins(&self) -> BTreeMap<&'static str, BinCollection> {
+        (*self.bins.lock()).clone()
+    }
+
+    pub fn departures(&self) -> BTreeMap<&'static str, Departure> {
+        (*self.departures.lock()).clone()
+    }
+
+    /// Configured RSS/Atom feed URLs for the omni page's news ticker.
+    pub fn news_feeds(&self) -> Vec<Url> {
+        self.news_feeds.clone()
+    }
+
+    /// How often [`Self::news_feeds`] should be re-fetched.
+    pub fn news_refresh_interval(&self) -> Duration {
+        self.news_refresh_interval
+    }
+
+    pub fn alarms(&self) -> Vec<Alarm> {
+        self.alarms.lock().clone()
+    }
+
+    pub async fn set_alarm_enabled(&self, index: usize, enabled: bool) {
+        if let Some(alarm) = self.alarms.lock().get_mut(index) {
+            alarm.enabled = enabled;
+        }
+    }
+
+    /// Fires any enabled [`Alarm`] whose time-of-day matches right now and
+    /// hasn't already fired today. Called once a second by
+    /// [`Self::spawn_worker`].
+    fn check_alarms(self: &Arc<Self>) {
+        let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let today = now.date();
+
+        let due: Vec<Alarm> = {
+            let alarms = self.alarms.lock();
+            let mut fired_today = self.alarms_fired_today.lock();
+
+            alarms
+                .iter()
+                .enumerate()
+                .filter(|(index, alarm)| {
+                    alarm.enabled
+                        && alarm.time_of_day.hour() == now.hour()
+                        && alarm.time_of_day.minute() == now.minute()
+                        && fired_today.get(index) != Some(&today)
+                })
+                .map(|(index, alarm)| {
+                    fired_today.insert(index, today);
+                    alarm.clone()
+                })
+                .collect()
+        };
+
+        for alarm in due {
+            let oracle = self.clone();
+            tokio::spawn(async move { oracle.trigger_alarm(&alarm).await });
+        }
+    }
+
+    /// Starts `alarm`'s speaker at [`Alarm`]'s configured playlist/station,
+    /// then ramps the volume from `starting_volume` up to `target_volume`
+    /// over `ramp_duration_secs` in fixed steps, so the wake-up isn't jarring.
+    async fn trigger_alarm(&self, alarm: &Alarm) {
+        let speaker = self.speaker(alarm.speaker_id);
+
+        speaker.set_volume(alarm.starting_volume).await;
+        speaker
+            .play_media(
+                alarm.media_uri.to_string(),
+                alarm.media_content_type.to_string(),
+            )
+            .await;
+
+        const RAMP_STEPS: u32 = 10;
+        let step_duration = Duration::from_secs(alarm.ramp_duration_secs) / RAMP_STEPS;
+        let volume_step = (alarm.target_volume - alarm.starting_volume) / RAMP_STEPS as f32;
+
+        for step in 1..=RAMP_STEPS {
+            tokio::time::sleep(step_duration).await;
+            speaker
+                .set_volume(alarm.starting_volume + volume_step * step as f32)
+                .await;
+        }
+    }
+
+    /// Scene triggers set from the panel's scheduler page, in insertion
+    /// order.
+    pub fn scheduled_scenes(&self) -> Vec<ScheduledScene> {
+        self.scheduled_scenes.lock().clone()
+    }
+
+    pub fn subscribe_scheduled_scenes(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(&**v == "scheduled_scenes"))
+            .map(|_| ())
+    }
+
+    /// Adds a new scheduled scene, parsing `time` as `HH:MM`. No-ops if
+    /// `time` doesn't parse or `name`/`scene_entity_id` is blank, since this
+    /// is fed straight from the scheduler page's text inputs.
+    pub async fn add_scheduled_scene(&self, name: String, scene_entity_id: String, time: String) {
+        let name = name.trim();
+        let scene_entity_id = scene_entity_id.trim();
+
+        let Some((hour, minute)) = parse_hour_minute(&time) else {
+            return;
+        };
+
+        if name.is_empty() || scene_entity_id.is_empty() {
+            return;
+        }
+
+        {
+            let mut scheduled_scenes = self.scheduled_scenes.lock();
+            let id = scheduled_scenes
+                .iter()
+                .map(|scene| scene.id)
+                .max()
+                .unwrap_or(0)
+                + 1;
+
+            scheduled_scenes.push(ScheduledScene {
+                id,
+                name: Box::from(name),
+                scene_entity_id: Box::from(scene_entity_id),
+                hour,
+                minute,
+                enabled: true,
+            });
+        }
+
+        self.save_scheduled_scenes().await;
+    }
+
+    pub async fn set_scheduled_scene_enabled(&self, id: u64, enabled: bool) {
+        if let Some(scene) = self
+            .scheduled_scenes
+            .lock()
+            .iter_mut()
+            .find(|scene| scene.id == id)
+        {
+            scene.enabled = enabled;
+        }
+
+        self.save_scheduled_scenes().await;
+    }
+
+    pub async fn remove_scheduled_scene(&self, id: u64) {
+        self.scheduled_scenes.lock().retain(|scene| scene.id != id);
+        self.save_scheduled_scenes().await;
+    }
+
+    async fn save_scheduled_scenes(&self) {
+        let scheduled_scenes = self.scheduled_scenes.lock().clone();
+
+        if let Ok(json) = serde_json::to_string(&scheduled_scenes) {
+            let _res = tokio::fs::write(SCHEDULED_SCENES_PATH, json).await;
+        }
+
+        let _res = self.entity_updates.send(Arc::from("scheduled_scenes"));
+    }
+
+    /// Short messages family members have left each other from the omni
+    /// page's note board, oldest first.
+    pub fn household_notes(&self) -> Vec<HouseholdNote> {
+        self.household_notes.lock().clone()
+    }
+
+    pub fn subscribe_household_notes(&self) -> impl Stream<Item = ()> {
+        BroadcastStream::new(self.entity_updates.subscribe())
+            .filter_map(|v| future::ready(v.ok()))
+            .filter(|v| future::ready(&**v == "household_notes"))
+            .map(|_| ())
+    }
+
+    /// Adds a note to the board. No-ops if `author` or `message` is blank,
+    /// since this is fed straight from the omni page's text inputs.
+    pub async fn add_household_note(&self, author: String, message: String) {
+        let author = author.trim();
+        let message = message.trim();
+
+        if author.is_empty() || message.is_empty() {
+            return;
+        }
+
+        {
+            let mut household_notes = self.household_notes.lock();
+            let id = household_notes
+                .iter()
+                .map(|note| note.id)
+                .max()
+                .unwrap_or(0)
+                + 1;
+
+            household_notes.push(HouseholdNote {
+                id,
+                author: Box::from(author),
+                message: Box::from(message),
+            });
+        }
+
+        self.save_household_notes().await;
+    }
+
+    pub async fn remove_household_note(&self, id: u64) {
+        self.household_notes.lock().retain(|note| note.id != id);
+        self.save_household_notes().await;
+    }
+
+    async fn save_household_notes(&self) {
+        let household_notes = self.household_notes.lock().clone();
+
+        if let Ok(json) = serde_json::to_string(&household_notes) {
+            let _res = tokio::fs::write(HOUSEHOLD_NOTES_PATH, json).await;
+        }
+
+        let _res = self.entity_updates.send(Arc::from("household_notes"));
+    }
+
+    /// Chores from `config.toml`, in configured order, with completion
+    /// state restored from [`CHORES_STATE_PATH`].
+    pub fn chores(&self) -> Vec<Chore> {
+        self.chores.lock().clone()
+    }
+
+    pub async fn set_chore_complete(&self, index: usize, complete: bool) {
+        if let Some(chore) = self.chores.lock().get_mut(index) {
+            chore.complete = complete;
+        }
+
+        self.save_completed_chores().await;
+    }
+
+    async fn save_completed_chores(&self) {
+        let completed: Vec<&str> = self
+            .chores
+            .lock()
+            .iter()
+            .filter(|chore| chore.complete)
+            .map(|chore| chore.name.as_ref())
+            .collect();
+
+        if let Ok(json) = serde_json::to_string(&completed) {
+            let _res = tokio::fs::write(CHORES_STATE_PATH, json).await;
+        }
+    }
+
+    /// Turns on any enabled [`ScheduledScene`] whose time matches right now
+    /// and hasn't already fired today. Called once a second by
+    /// [`Self::spawn_worker`], same as [`Self::check_alarms`].
+    fn check_scheduled_scenes(self: &Arc<Self>) {
+        let now = OffsetDateTime::now_local().unwrap_or_else(|_| OffsetDateTime::now_utc());
+        let today = now.date();
+
+        let due: Vec<Box<str>> = {
+            let scheduled_scenes = self.scheduled_scenes.lock();
+            let mut fired_today = self.scheduled_scenes_fired_today.lock();
+
+            scheduled_scenes
+                .iter()
+                .filter(|scene| {
+                    scene.enabled
+                        && scene.hour == now.hour()
+                        && scene.minute == now.minute()
+                        && fired_today.get(&scene.id) != Some(&today)
+                })
+                .map(|scene| {
+                    fired_today.insert(scene.id, today);
+                    scene.scene_entity_id.clone()
+                })
+                .collect()
+        };
+
+        for scene_entity_id in due {
+            let oracle = self.clone();
+
+            tokio::spawn(async move {
+                let entity_id = Intern::<str>::from(scene_entity_id.as_ref()).as_ref();
+                oracle
+                    .call_service("scene", "turn_on", entity_id, serde_json::Value::Null)
+                    .await;
+            });
+        }
+    }
+
+    /// Runs every action of `routines()[index]` in order, e.g. a "Good
+    /// night" routine turning off all lights then pausing all speakers.
+    pub async fn run_routine(&self, index: usize) {
+        let Some(routine) = self.routines.get(index) else {
+            return;
+        };
+
+        for action in &routine.actions {
+            self.call_service(
+                action.domain.to_string(),
+                action.service.to_string(),
+                action.entity_id,
+                serde_json::Value::Null,
+            )
+            .await;
+        }
+    }
+
+    pub fn speaker(&self, speaker_id: &'static str) -> EloquentSpeaker<'_> {
+        EloquentSpeaker {
+            speaker_id,
+            oracle: self,
+        }
+    }
+
+    /// Snapshots the current on/brightness/colour of every light in
+    /// `room_id` and calls `scene.create`, for the lights page's "Save as
+    /// Scene" action. Colour is captured from whichever attribute matches
+    /// the light's current `color_mode`, so colour-temp and RGBW(W) bulbs
+    /// come back the way they were saved instead of with no colour data.
+    /// No-ops if `name` is blank or `room_id` doesn't exist.
+    pub async fn save_room_as_scene(&self, room_id: &str, name: String) {
+        let name = name.trim();
+
+        if name.is_empty() {
+            return;
+        }
+
+        let Some(room) = self.rooms.lock().get(room_id).cloned() else {
+            return;
+        };
+
+        let entities: serde_json::Map<String, serde_json::Value> = room
+            .lights(self)
+            .into_iter()
+            .map(|(id, light)| {
+                let mut state = serde_json::Map::new();
+
+                state.insert(
+                    "state".to_string(),
+                    serde_json::json!(if light.on == Some(true) { "on" } else { "off" }),
+                );
+
+                if let Some(brightness) = light.brightness {
+                    state.insert(
+                        "brightness".to_string(),
+                        serde_json::json!(clamp_to_u8(brightness / 255.)),
+                    );
+                }
+
+                match light.color_mode {
+                    Some(ColorMode::ColorTemp) => {
+                        if let Some(kelvin) = light.color_temp_kelvin {
+                            state
+                                .insert("color_temp_kelvin".to_string(), serde_json::json!(kelvin));
+                        }
+                    }
+                    Some(ColorMode::Rgbw) => {
+                        if let Some(rgbw) = light.rgbw_color {
+                            state.insert("rgbw_color".to_string(), serde_json::json!(rgbw));
+                        }
+                    }
+                    Some(ColorMode::Rgbww) => {
+                        if let Some(rgbww) = light.rgbww_color {
+                            state.insert("rgbww_color".to_string(), serde_json::json!(rgbww));
+                        }
+                    }
+                    Some(ColorMode::Xy) | Some(ColorMode::Unsupported) | None => {
+                        if let Some((hue, saturation)) = light.hs_color {
+                            state.insert(
+                                "hs_color".to_string(),
+                                serde_json::json!([hue, saturation]),
+                            );
+                        }
+                    }
+                    Some(ColorMode::Brightness) => {}
+                }
+
+                (id.to_string(), serde_json::Value::Object(state))
+            })
+            .collect();
+
+        let scene_id = name.to_lowercase().replace(' ', "_");
+
+        self.call_service_no_target(
+            "scene",
+            "create",
+            serde_json::json!({ "scene_id": scene_id, "entities": entities }),
+        )
+        .await;
+    }
+
+    /// Escape hatch for calling a Home Assistant service that acts on a whole
+    /// domain rather than a specific entity (e.g. `scene.create`), unlike
+    /// [`Self::call_service`] which always targets one.
+    async fn call_service_no_target(
+        &self,
+        domain: impl Into<String>,
+        service: impl Into<String>,
+        service_data: serde_json::Value,
+    ) {
+        let _res = self
+            .client
+            .call_service_domain_with_data(domain, service, service_data)
+            .await;
+    }
+
+    /// Captures the current on/brightness/colour state of every light in
+    /// `room_id`, so a later [`Self::restore_lights`] call can put them back
+    /// the way they were. For attention-grabbing actions (doorbell flash,
+    /// announcement) that want to flash the lights and then restore them.
+    /// Overwrites any previously captured snapshot for these lights.
+    pub fn snapshot_lights(&self, room_id: &str) {
+        let Some(room) = self.rooms.lock().get(room_id).cloned() else {
+            return;
+        };
+
+        let mut snapshots = self.light_snapshots.lock();
+
+        for (id, light) in room.lights(self) {
+            snapshots.insert(
+                id,
+                LightRestoreSnapshot {
+                    on: light.on,
+                    brightness: light.brightness,
+                    hs_color: light.hs_color,
+                },
+            );
+        }
+    }
+
+    /// Restores every light captured by [`Self::snapshot_lights`] to its
+    /// snapshotted state, then clears the snapshot. The calls are batched
+    /// with [`future::join_all`] like [`Self::all_lights_off`].
+    pub async fn restore_lights(&self) {
+        let snapshots = std::mem::take(&mut *self.light_snapshots.lock());
+
+        future::join_all(snapshots.into_iter().map(|(id, snapshot)| async move {
+            if snapshot.on != Some(true) {
+                self.set_light_state(id, false).await;
+                return;
+            }
+
+            let _res = self
+                .client
+                .call_service(
+                    id,
+                    CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                        CallServiceRequestLightTurnOn {
+                            hs_color: snapshot.hs_color,
+                            brightness: snapshot.brightness.map(|v| clamp_to_u8(v / 255.)),
+                            rgbw_color: None,
+                            rgbww_color: None,
+                            effect: None,
+                        },
+                    )),
+                )
+                .await;
+        }))
+        .await;
+    }
+
+    /// Turns off every light known to the [`Oracle`], for the omni page's
+    /// "All off" button. The calls are batched with [`future::join_all`]
+    /// rather than run one after another, so the panel isn't left waiting on
+    /// each light in turn.
+    pub async fn all_lights_off(&self) {
+        let light_ids: Vec<&'static str> = self.lights.lock().keys().copied().collect();
+
+        future::join_all(
+            light_ids
+                .into_iter()
+                .map(|id| self.set_light_state(id, false)),
+        )
+        .await;
+    }
+
+    /// Mutes every media player known to the [`Oracle`], for the quick-actions
+    /// bar's "Mute all" button. Like [`Self::all_lights_off`], the calls are
+    /// batched with [`future::join_all`] rather than run one after another.
+    pub async fn mute_all_speakers(&self) {
+        let speaker_ids: Vec<&'static str> = self.media_players.lock().keys().copied().collect();
+
+        future::join_all(
+            speaker_ids
+                .into_iter()
+                .map(|id| self.speaker(id).set_mute(true)),
+        )
+        .await;
+    }
+
+    pub async fn set_light_state(&self, entity_id: &'static str, on: bool) {
+        let _res = self
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Light(if on {
+                    CallServiceRequestLight::TurnOn(CallServiceRequestLightTurnOn {
+                        brightness: None,
+                        hs_color: None,
+                        rgbw_color: None,
+                        rgbww_color: None,
+                        effect: None,
+                    })
+                } else {
+                    CallServiceRequestLight::TurnOff
+                }),
+            )
+            .await;
+    }
+
+    pub async fn update_light(
+        &self,
+        entity_id: &'static str,
+        hue: f32,
+        saturation: f32,
+        brightness: f32,
+    ) {
+        let _res = self
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                    CallServiceRequestLightTurnOn {
+                        hs_color: Some((hue, saturation * 100.)),
+                        brightness: Some(clamp_to_u8(brightness)),
+                        rgbw_color: None,
+                        rgbww_color: None,
+                        effect: None,
+                    },
+                )),
+            )
+            .await;
+
+        self.record_recent_colour(
+            entity_id,
+            RecentColour {
+                hue,
+                saturation,
+                brightness,
+            },
+        )
+        .await;
+
+        // Picking a colour by hand contradicts what Adaptive Lighting is
+        // trying to do for this room, so turn it off rather than have it
+        // fight the user's choice on its next adjustment cycle.
+        if let Some(switch) = self.adaptive_lighting_switch_for(entity_id) {
+            self.set_adaptive_lighting(switch, false).await;
+        }
+    }
+
+    /// The Adaptive Lighting switch for `light_id`'s room, if that light is
+    /// in a room with one configured.
+    fn adaptive_lighting_switch_for(&self, light_id: &str) -> Option<&'static str> {
+        self.rooms
+            .lock()
+            .values()
+            .find(|room| room.lights.iter().any(|light| light.as_ref() == light_id))
+            .and_then(|room| room.adaptive_lighting_switch)
+            .map(|switch| switch.as_ref())
+    }
+
+    /// Turns the Adaptive Lighting integration's `switch.adaptive_lighting_*`
+    /// entity on or off, for the room header/light card badge's toggle and
+    /// [`Self::update_light`]'s auto-disable-on-manual-pick.
+    pub async fn set_adaptive_lighting(&self, entity_id: &'static str, is_on: bool) {
+        if let Some(switch) = self.switches.lock().get_mut(entity_id) {
+            switch.is_on = is_on;
+        }
+
+        let data = CallServiceRequestData::Switch(if is_on {
+            CallServiceRequestSwitch::TurnOn
+        } else {
+            CallServiceRequestSwitch::TurnOff
+        });
+
+        let _res = self.client.call_service(entity_id, data).await;
+    }
+
+    /// Sets `entity_id`'s brightness without touching colour, for lights
+    /// whose only `supported_color_modes` entry is [`ColorMode::Brightness`]
+    /// (dimmable but not colour-capable) — sending an `hs_color` at them like
+    /// [`Self::update_light`] does is rejected by Home Assistant.
+    pub async fn set_light_brightness(&self, entity_id: &'static str, brightness: f32) {
+        let _res = self
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                    CallServiceRequestLightTurnOn {
+                        hs_color: None,
+                        brightness: Some(clamp_to_u8(brightness)),
+                        rgbw_color: None,
+                        rgbww_color: None,
+                        effect: None,
+                    },
+                )),
+            )
+            .await;
+    }
+
+    /// Sets `entity_id`'s dedicated white channel, for RGBW/RGBWW bulbs'
+    /// white-level slider in the light context menu. Leaves brightness
+    /// untouched by not sending it alongside, and preserves the bulb's
+    /// current colour by reusing its last-known `rgbw_color`/`rgbww_color`
+    /// with only the white component replaced, instead of zeroing RGB out.
+    pub async fn set_light_white(&self, entity_id: &'static str, white: u8) {
+        let (rgbww, rgb) = self
+            .lights
+            .lock()
+            .get(entity_id)
+            .map_or((false, (0, 0, 0)), |light| {
+                let rgbww = light.supported_color_modes.contains(&ColorMode::Rgbww);
+                let rgb = if rgbww {
+                    light
+                        .rgbww_color
+                        .map_or((0, 0, 0), |(r, g, b, _, _)| (r, g, b))
+                } else {
+                    light.rgbw_color.map_or((0, 0, 0), |(r, g, b, _)| (r, g, b))
+                };
+
+                (rgbww, rgb)
+            });
+        let (r, g, b) = rgb;
+
+        let _res = self
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                    CallServiceRequestLightTurnOn {
+                        hs_color: None,
+                        brightness: None,
+                        rgbw_color: (!rgbww).then_some((r, g, b, white)),
+                        rgbww_color: rgbww.then_some((r, g, b, 0, white)),
+                        effect: None,
+                    },
+                )),
+            )
+            .await;
+    }
+
+    /// Jumps `entity_id` to full brightness and neutral white, for the light
+    /// card's double-tap shortcut. Lights whose only `supported_color_modes`
+    /// entry is [`ColorMode::Brightness`] get brightness only, since sending
+    /// `hs_color` at them is rejected the same way [`Self::set_light_brightness`]
+    /// notes.
+    pub async fn set_light_full_brightness(&self, entity_id: &'static str) {
+        let brightness_only = self
+            .lights
+            .lock()
+            .get(entity_id)
+            .is_some_and(|light| light.supported_color_modes == [ColorMode::Brightness]);
+
+        let _res = self
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                    CallServiceRequestLightTurnOn {
+                        hs_color: (!brightness_only).then_some((0.0, 0.0)),
+                        brightness: Some(255),
+                        rgbw_color: None,
+                        rgbww_color: None,
+                        effect: None,
+                    },
+                )),
+            )
+            .await;
+    }
+
+    /// Whether `entity_id` is running the client-side colour loop started by
+    /// [`Self::set_light_colour_loop`], for the light context menu to show
+    /// its toggle as on across a re-open. Bulbs looping via their own native
+    /// `colorloop` effect report that through `Light::effect` instead.
+    pub fn is_colour_looping(&self, entity_id: &str) -> bool {
+        self.colour_loop_lights.lock().contains(entity_id)
+    }
+
+    /// Toggles the light context menu's "Colour loop" switch. Uses the
+    /// bulb's own `colorloop` effect when `effect_list` advertises one
+    /// (most Zigbee/Z-Wave bulbs); otherwise runs the loop client-side,
+    /// hue-stepping the light every tick from `spawn_worker`'s
+    /// `colour_loop_tick`, since not every controllable light exposes one.
+    pub async fn set_light_colour_loop(&self, entity_id: &'static str, enabled: bool) {
+        let native = self.lights.lock().get(entity_id).is_some_and(|light| {
+            light
+                .effect_list
+                .iter()
+                .any(|effect| &**effect == COLOUR_LOOP_EFFECT)
+        });
+
+        if native {
+            let _res = self
+                .client
+                .call_service(
+                    entity_id,
+                    CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                        CallServiceRequestLightTurnOn {
+                            hs_color: None,
+                            brightness: None,
+                            rgbw_color: None,
+                            rgbww_color: None,
+                            effect: Some(
+                                if enabled { COLOUR_LOOP_EFFECT } else { "none" }.to_string(),
+                            ),
+                        },
+                    )),
+                )
+                .await;
+        } else if enabled {
+            self.colour_loop_lights.lock().insert(entity_id);
+        } else {
+            self.colour_loop_lights.lock().remove(entity_id);
+        }
+    }
+
+    /// Advances every light in [`Self::colour_loop_lights`] by one hue step,
+    /// called from [`Self::spawn_worker`]'s `colour_loop_tick`.
+    async fn step_colour_loops(&self) {
+        let entity_ids: Vec<&'static str> =
+            self.colour_loop_lights.lock().iter().copied().collect();
+
+        future::join_all(entity_ids.into_iter().map(|entity_id| async move {
+            let Some((hue, saturation)) = self
+                .lights
+                .lock()
+                .get(entity_id)
+                .and_then(|light| light.hs_color)
+            else {
+                return;
+            };
+
+            let hue = (hue + COLOUR_LOOP_HUE_STEP) % 360.0;
+            let saturation = if saturation == 0.0 { 100.0 } else { saturation };
+
+            let _res = self
+                .client
+                .call_service(
+                    entity_id,
+                    CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                        CallServiceRequestLightTurnOn {
+                            hs_color: Some((hue, saturation)),
+                            brightness: None,
+                            rgbw_color: None,
+                            rgbww_color: None,
+                            effect: None,
+                        },
+                    )),
+                )
+                .await;
+        }))
+        .await;
+    }
+
+    /// The last few colours applied to `entity_id`, most recent first, for
+    /// the "Recent" swatches in the light context menu.
+    pub fn recent_colours(&self, entity_id: &str) -> Vec<RecentColour> {
+        self.recent_colours
+            .lock()
+            .get(entity_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Records a colour applied to `entity_id`, moving it to the front if
+    /// already present and truncating to [`RECENT_COLOURS_LIMIT`], then
+    /// persists the result to [`RECENT_COLOURS_PATH`] so it survives a
+    /// restart.
+    async fn record_recent_colour(&self, entity_id: &'static str, colour: RecentColour) {
+        {
+            let mut recent_colours = self.recent_colours.lock();
+            let colours = recent_colours.entry(entity_id).or_default();
+
+            colours.retain(|existing| existing != &colour);
+            colours.insert(0, colour);
+            colours.truncate(RECENT_COLOURS_LIMIT);
+        }
+
+        self.save_recent_colours().await;
+    }
+
+    async fn save_recent_colours(&self) {
+        let recent_colours = self.recent_colours.lock().clone();
+
+        if let Ok(json) = serde_json::to_string(&recent_colours) {
+            let _res = tokio::fs::write(RECENT_COLOURS_PATH, json).await;
+        }
+    }
+
+    pub fn backlight_entity(&self) -> Option<&'static str> {
+        self.backlight_entity
+    }
+
+    pub fn night_backlight(&self) -> u8 {
+        self.night_backlight
+    }
+
+    pub async fn set_backlight(&self, brightness: u8) {
+        let Some(entity_id) = self.backlight_entity else {
+            return;
+        };
+
+        let data = if entity_id.starts_with("number.") {
+            CallServiceRequestData::Number(CallServiceRequestNumber::SetValue(
+                CallServiceRequestNumberSetValue {
+                    value: f32::from(brightness),
+                },
+            ))
+        } else {
+            CallServiceRequestData::Light(CallServiceRequestLight::TurnOn(
+                CallServiceRequestLightTurnOn {
+                    brightness: Some(clamp_to_u8(f32::from(brightness) / 100.)),
+                    hs_color: None,
+                    rgbw_color: None,
+                    rgbww_color: None,
+                    effect: None,
+                },
+            ))
+        };
+
+        let _res = self.client.call_service(entity_id, data).await;
+    }
+
+    pub fn spawn_worker(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let mut recv = self.client.subscribe();
+            let mut second_tick = tokio::time::interval(Duration::from_secs(1));
+            second_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            // Coalesces `entity_updates` broadcasts so a flood of state
+            // changes for the same entity within one frame (e.g. a light in
+            // a color loop) only wakes subscribers once per frame.
+            let mut pending_updates: HashSet<&'static str> = HashSet::new();
+            let mut coalesce_tick = tokio::time::interval(Duration::from_millis(16));
+            coalesce_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            let mut forecast_tick = tokio::time::interval(Duration::from_secs(30 * 60));
+            forecast_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            let mut colour_loop_tick = tokio::time::interval(Duration::from_millis(100));
+            colour_loop_tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            let mut active_media_players = self
+                .media_players
+                .lock()
+                .iter()
+                .filter(|(_k, v)| v.is_playing())
+                .map(|(k, _v)| *k)
+                .collect::<HashSet<_>>();
+
+            let mut active_timers = self
+                .timers
+                .lock()
+                .iter()
+                .filter(|(_k, v)| v.active)
+                .map(|(k, _v)| *k)
+                .collect::<HashSet<_>>();
+
+            let has_alarms = !self.alarms.lock().is_empty();
+
+            loop {
+                tokio::select! {
+                    msg = recv.recv() => match msg {
+                        Ok(msg) if matches!(msg.get(), Event::ShoppingListUpdated(_)) => {
+                            self.refresh_shopping_list().await;
+                        }
+                        Ok(msg) if matches!(
+                            msg.get(),
+                            Event::AreaRegistryUpdated(_)
+                                | Event::DeviceRegistryUpdated(_)
+                                | Event::EntityRegistryUpdated(_)
+                        ) => {
+                            self.rebuild_rooms().await;
+                        }
+                        Ok(msg) => {
+                            if let Some(brightness) = self.handle_state_update_event(
+                                &msg,
+                                &mut active_media_players,
+                                &mut pending_updates,
+                            ) {
+                                self.set_backlight(brightness).await;
+                            }
+                            self.handle_timer_update(&msg, &mut active_timers);
+                        }
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = second_tick.tick(), if !active_media_players.is_empty() || !active_timers.is_empty() || has_alarms || !self.scheduled_scenes.lock().is_empty() || !self.local_timers.lock().is_empty() => {
+                        self.update_media_player_positions(&active_media_players);
+                        self.tick_timers(&active_timers);
+                        if has_alarms {
+                            self.check_alarms();
+                        }
+                        self.check_scheduled_scenes();
+                        self.tick_local_timers();
+                    },
+                    _ = coalesce_tick.tick(), if !pending_updates.is_empty() => {
+                        for entity_id in pending_updates.drain() {
+                            let _res = self.entity_updates.send(Arc::from(entity_id));
+                        }
+                    },
+                    _ = forecast_tick.tick() => {
+                        self.refresh_forecasts().await;
+                    },
+                    _ = colour_loop_tick.tick(), if !self.colour_loop_lights.lock().is_empty() => {
+                        self.step_colour_loops().await;
+                    },
+                }
+            }
+        });
+    }
+
+    fn update_media_player_positions(&self, active_media_players: &HashSet<&'static str>) {
+        let mut media_players = self.media_players.lock();
+
+        for entity_id in active_media_players {
+            let Some(MediaPlayer::Speaker(speaker)) = media_players.get_mut(entity_id) else {
+                continue;
+            };
+
+            speaker.actual_media_position = speaker
+                .media_position
+                .zip(speaker.media_position_updated_at)
+                .zip(Some(speaker.state))
+                .map(calculate_actual_media_position);
+
+            let _res = self.entity_updates.send(Arc::from(*entity_id));
+        }
+    }
+
+    /// Timer remaining-time is computed on demand from `finishes_at`, so the
+    /// tick just needs to nudge subscribers to re-render every second.
+    fn tick_timers(&self, active_timers: &HashSet<&'static str>) {
+        for entity_id in active_timers {
+            let _res = self.entity_updates.send(Arc::from(*entity_id));
+        }
+    }
+
+    fn handle_timer_update(
+        &self,
+        msg: &Yoke<Event<'static>, String>,
+        active_timers: &mut HashSet<&'static str>,
+    ) {
+        let Event::StateChanged(state_changed) = msg.get() else {
+            return;
+        };
+
+        if let StateAttributes::Timer(attr) = &state_changed.new_state.attributes {
+            let entity_id = Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+            let timer = Timer::from((attr, state_changed.new_state.state.as_ref()));
+
+            if timer.active {
+                active_timers.insert(entity_id);
+            } else {
+                active_timers.remove(entity_id);
+            }
+
+            self.timers.lock().insert(entity_id, timer);
+        }
+    }
+
+    /// Watched appliance entities aren't tied to a single Home Assistant
+    /// domain, so this runs independently of the domain-specific match in
+    /// [`Self::handle_state_update_event`]. Broadcasts an
+    /// [`ApplianceFinished`] when a watched entity transitions away from its
+    /// configured `running_state`.
+    fn handle_appliance_update(
+        &self,
+        state_changed: &crate::hass_client::events::StateChanged<'static>,
+    ) {
+        let entity_id = Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+        let mut appliances = self.appliances.lock();
+
+        let Some(rule) = appliances.get_mut(entity_id) else {
+            return;
+        };
+
+        let now_running = state_changed.new_state.state.as_ref() == &*rule.running_state;
+
+        if rule.running && !now_running {
+            let _res = self.appliance_finished.send(ApplianceFinished {
+                name: rule.name.clone(),
+                speaker_id: rule.speaker_id,
+            });
+        }
+
+        rule.running = now_running;
+    }
+
+    /// Like [`Self::handle_appliance_update`], but for arbitrary
+    /// entity/state-transition announcements: broadcasts an [`Announcement`]
+    /// when a watched entity transitions *into* its configured
+    /// `trigger_state`, rather than away from a `running_state`.
+    fn handle_announcement_update(
+        &self,
+        state_changed: &crate::hass_client::events::StateChanged<'static>,
+    ) {
+        let entity_id = Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+        let mut announcements = self.announcements.lock();
+
+        let Some(rule) = announcements.get_mut(entity_id) else {
+            return;
+        };
+
+        let new_state = state_changed.new_state.state.as_ref();
+        let now_triggered = new_state == &*rule.trigger_state;
+
+        if now_triggered && &*rule.last_state != new_state {
+            let _res = self.announcement_fired.send(Announcement {
+                message: rule.message.clone(),
+                speaker_id: rule.speaker_id,
+            });
+        }
+
+        rule.last_state = Box::from(new_state);
+    }
+
+    /// Handles a `state_changed` event, updating the relevant in-memory
+    /// state. Rather than notifying subscribers immediately, the changed
+    /// entity is added to `pending_updates` for the caller to flush on the
+    /// next coalesce tick, so a flood of updates for the same entity only
+    /// triggers one `entity_updates` broadcast. Returns `Some(brightness)` if
+    /// the panel backlight should be updated in response (i.e. the sun just
+    /// rose or set).
+    fn handle_state_update_event(
+        &self,
+        msg: &Yoke<Event<'static>, String>,
+        active_media_players: &mut HashSet<&'static str>,
+        pending_updates: &mut HashSet<&'static str>,
+    ) -> Option<u8> {
+        let backlight_update = match msg.get() {
+            Event::StateChanged(state_changed) => {
+                self.handle_appliance_update(state_changed);
+                self.handle_announcement_update(state_changed);
+
+                let backlight_update = match &state_changed.new_state.attributes {
+                    StateAttributes::Sun(_) => {
+                        let above_horizon =
+                            state_changed.new_state.state.as_ref() == "above_horizon";
+                        let was_above_horizon =
+                            self.sun_above_horizon.swap(above_horizon, Ordering::AcqRel);
+
+                        (above_horizon != was_above_horizon).then_some(if above_horizon {
+                            DAY_BACKLIGHT
+                        } else {
+                            self.night_backlight
+                        })
+                    }
+                    StateAttributes::MediaPlayer(attrs) => {
+                        let entity_id =
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+                        eprintln!("{entity_id} updated");
+
+                        // The advanced-control companion entities are
+                        // discovered once from the device registry at
+                        // startup and don't come through state_changed
+                        // events, so carry them over from what we already
+                        // know about this speaker.
+                        let advanced_entities = match self.media_players.lock().get(entity_id) {
+                            Some(MediaPlayer::Speaker(speaker)) => (
+                                speaker.bass_entity,
+                                speaker.treble_entity,
+                                speaker.loudness_entity,
+                                speaker.night_mode_entity,
+                            ),
+                            _ => (None, None, None, None),
+                        };
+
+                        let mut new_state = MediaPlayer::new(
+                            attrs,
+                            &state_changed.new_state.state,
+                            &self.client.base,
+                        );
+                        if let MediaPlayer::Speaker(ref mut speaker) = new_state {
+                            if let Some(name) = self.entity_names.lock().get(entity_id) {
+                                speaker.friendly_name = name.clone();
+                            }
+
+                            (
+                                speaker.bass_entity,
+                                speaker.treble_entity,
+                                speaker.loudness_entity,
+                                speaker.night_mode_entity,
+                            ) = advanced_entities;
+                        }
+
+                        if new_state.is_playing() {
+                            active_media_players.insert(entity_id);
+                        } else {
+                            active_media_players.remove(entity_id);
+                        }
+
+                        self.media_players.lock().insert(entity_id, new_state);
+
+                        None
+                    }
+                    StateAttributes::Weather(attrs) => {
+                        self.weather.store(
+                            Weather::parse_from_state_and_attributes(
+                                state_changed.new_state.state.as_ref(),
+                                attrs,
+                                &self.daily_forecast.lock(),
+                            ),
+                            Ordering::Release,
+                        );
+
+                        None
+                    }
+                    StateAttributes::Light(attrs) => {
+                        let entity_id =
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+                        let mut lights = self.lights.lock();
+
+                        // The entity registry icon override doesn't come through
+                        // state_changed events, so carry it over from what we
+                        // already know about this light.
+                        let icon = lights.get(entity_id).and_then(|light| light.icon.clone());
+
+                        let mut light =
+                            Light::from((attrs.clone(), state_changed.new_state.state.as_ref()));
+                        light.icon = icon;
+
+                        if let Some(name) = self.entity_names.lock().get(entity_id) {
+                            light.friendly_name = name.clone();
+                        }
+
+                        lights.insert(entity_id, light);
+
+                        None
+                    }
+                    StateAttributes::Camera(attrs) => {
+                        self.cameras.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Camera::new(attrs, &self.client.base),
+                        );
+
+                        None
+                    }
+                    StateAttributes::BinarySensor(attrs) => {
+                        *self.weather_alert.lock() =
+                            WeatherAlert::from_state(&state_changed.new_state.state, attrs);
+
+                        if is_occupancy_sensor(attrs) {
+                            self.occupancy.lock().insert(
+                                Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                                state_changed.new_state.state.as_ref() == "on",
+                            );
+                        }
+
+                        None
+                    }
+                    StateAttributes::Vacuum(attrs) => {
+                        self.vacuums.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Vacuum::new(attrs, &state_changed.new_state.state, &self.client.base),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Cover(attrs) => {
+                        self.covers.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Cover::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Humidifier(attrs) => {
+                        self.humidifiers.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Humidifier::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Climate(attrs) => {
+                        self.thermostats.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Thermostat::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Update(attrs) => {
+                        self.updates.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Update::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Sensor(attrs) => {
+                        let entity_id =
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref();
+
+                        if Some(entity_id) == self.price_entity {
+                            *self.energy_price.lock() = EnergyPrice::from_state(
+                                attrs,
+                                state_changed.new_state.state.as_ref(),
+                            );
+                        }
+
+                        if attrs.device_class.as_deref() == Some("moisture") {
+                            if let Some(plant) = Plant::from_moisture_sensor(
+                                attrs,
+                                state_changed.new_state.state.as_ref(),
+                            ) {
+                                self.plants.lock().insert(entity_id, plant);
+                            }
+                        }
+
+                        if self.bin_collection_entities.contains(&entity_id) {
+                            self.bins.lock().insert(
+                                entity_id,
+                                BinCollection::new(
+                                    attrs.friendly_name.as_deref(),
+                                    state_changed.new_state.state.as_ref(),
+                                ),
+                            );
+                        }
+
+                        if self.transport_entities.contains(&entity_id) {
+                            self.departures.lock().insert(
+                                entity_id,
+                                Departure::new(
+                                    attrs.friendly_name.as_deref(),
+                                    state_changed.new_state.state.as_ref(),
+                                ),
+                            );
+                        }
+
+                        self.sensors.lock().insert(
+                            entity_id,
+                            Sensor::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Person(attrs) | StateAttributes::DeviceTracker(attrs) => {
+                        self.people.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Person::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Remote(attrs) => {
+                        self.remotes.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Remote::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Button(attrs) => {
+                        self.buttons.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            Button::new(attrs),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Number(attrs) => {
+                        self.numbers.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            NumberEntity::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    StateAttributes::Switch(attrs) => {
+                        self.switches.lock().insert(
+                            Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref(),
+                            SwitchEntity::new(attrs, state_changed.new_state.state.as_ref()),
+                        );
+
+                        None
+                    }
+                    _ => {
+                        // TODO
+                        None
+                    }
+                };
+
+                pending_updates
+                    .insert(Intern::<str>::from(state_changed.entity_id.as_ref()).as_ref());
+
+                backlight_update
+            }
+            Event::ShoppingListUpdated(_)
+            | Event::AreaRegistryUpdated(_)
+            | Event::DeviceRegistryUpdated(_)
+            | Event::EntityRegistryUpdated(_)
+            | Event::Other => None,
+        };
+
+        backlight_update
+    }
+}
+
+const DAY_BACKLIGHT: u8 = 100;
+
+const RECENTLY_PLAYED_LIMIT: usize = 10;
+
+/// Eloquent interface for interacting with a speaker. Does not hold any state
+/// of its own.
+pub struct EloquentSpeaker<'a> {
+    oracle: &'a Oracle,
+    speaker_id: &'static str,
+}
+
+impl EloquentSpeaker<'_> {
+    async fn call(&self, msg: CallServiceRequestMediaPlayer) {
+        let _res = self
+            .oracle
+            .client
+            .call_service(self.speaker_id, CallServiceRequestData::MediaPlayer(msg))
+            .await;
+    }
+
+    pub async fn set_mute(&self, is_volume_muted: bool) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.muted = is_volume_muted;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::VolumeMute(
+            CallServiceRequestMediaPlayerVolumeMute { is_volume_muted },
+        ))
+        .await;
+    }
+
+    pub async fn set_volume(&self, volume_level: f32) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.volume = volume_level;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::VolumeSet(
+            CallServiceRequestMediaPlayerVolumeSet { volume_level },
+        ))
+        .await;
+    }
+
+    /// This group's members and their current volumes, if this speaker is a
+    /// [`MediaPlayerSpeaker::is_group_coordinator`]. Empty if it isn't
+    /// grouped, or none of its members are known speakers.
+    fn group_member_volumes(&self) -> Vec<(&'static str, f32)> {
+        let group_members = match self.oracle.media_players.lock().get(self.speaker_id) {
+            Some(MediaPlayer::Speaker(speaker)) => speaker.group_members.clone(),
+            _ => return Vec::new(),
+        };
+
+        let media_players = self.oracle.media_players.lock();
+        group_members
+            .into_iter()
+            .filter_map(|id| match media_players.get(id) {
+                Some(MediaPlayer::Speaker(member)) => Some((id, member.volume)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The group's overall volume, shown on the group-volume slider: the
+    /// average of every member's current volume. `None` if this speaker
+    /// isn't grouped.
+    pub fn group_volume(&self) -> Option<f32> {
+        let members = self.group_member_volumes();
+        if members.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        Some(members.iter().map(|(_, v)| v).sum::<f32>() / members.len() as f32)
+    }
+
+    /// Moves `new_group_volume` onto every member of the group, scaling each
+    /// member's current volume by the same ratio so their relative offsets
+    /// (e.g. the kitchen speaker running quieter than the living room one)
+    /// are preserved, mirroring the Sonos app's group volume slider.
+    pub async fn set_group_volume(&self, new_group_volume: f32) {
+        let members = self.group_member_volumes();
+        if members.is_empty() {
+            return;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let current_average = members.iter().map(|(_, v)| v).sum::<f32>() / members.len() as f32;
+
+        future::join_all(members.into_iter().map(|(id, volume)| {
+            let target = if current_average > 0.0 {
+                volume * (new_group_volume / current_average)
+            } else {
+                new_group_volume
+            };
+
+            self.oracle.speaker(id).set_volume(target.clamp(0.0, 1.0))
+        }))
+        .await;
+    }
+
+    fn advanced_entities(&self) -> Option<MediaPlayerSpeaker> {
+        match self.oracle.media_players.lock().get(self.speaker_id) {
+            Some(MediaPlayer::Speaker(speaker)) => Some(speaker.clone()),
+            _ => None,
+        }
+    }
+
+    /// The current bass level, if this speaker exposes a
+    /// [`MediaPlayerSpeaker::bass_entity`].
+    pub fn bass(&self) -> Option<f32> {
+        let entity_id = self.advanced_entities()?.bass_entity?;
+        self.oracle.numbers.lock().get(entity_id).map(|n| n.value)
+    }
+
+    pub async fn set_bass(&self, value: f32) {
+        let Some(entity_id) = self.advanced_entities().and_then(|s| s.bass_entity) else {
+            return;
+        };
+
+        if let Some(number) = self.oracle.numbers.lock().get_mut(entity_id) {
+            number.value = value;
+        }
+
+        let _res = self
+            .oracle
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Number(CallServiceRequestNumber::SetValue(
+                    CallServiceRequestNumberSetValue { value },
+                )),
+            )
+            .await;
+    }
+
+    /// The current treble level, if this speaker exposes a
+    /// [`MediaPlayerSpeaker::treble_entity`].
+    pub fn treble(&self) -> Option<f32> {
+        let entity_id = self.advanced_entities()?.treble_entity?;
+        self.oracle.numbers.lock().get(entity_id).map(|n| n.value)
+    }
+
+    pub async fn set_treble(&self, value: f32) {
+        let Some(entity_id) = self.advanced_entities().and_then(|s| s.treble_entity) else {
+            return;
+        };
+
+        if let Some(number) = self.oracle.numbers.lock().get_mut(entity_id) {
+            number.value = value;
+        }
+
+        let _res = self
+            .oracle
+            .client
+            .call_service(
+                entity_id,
+                CallServiceRequestData::Number(CallServiceRequestNumber::SetValue(
+                    CallServiceRequestNumberSetValue { value },
+                )),
+            )
+            .await;
+    }
+
+    /// Whether loudness compensation is on, if this speaker exposes a
+    /// [`MediaPlayerSpeaker::loudness_entity`].
+    pub fn loudness(&self) -> Option<bool> {
+        let entity_id = self.advanced_entities()?.loudness_entity?;
+        self.oracle.switches.lock().get(entity_id).map(|s| s.is_on)
+    }
+
+    pub async fn set_loudness(&self, is_on: bool) {
+        let Some(entity_id) = self.advanced_entities().and_then(|s| s.loudness_entity) else {
+            return;
+        };
+
+        self.set_switch(entity_id, is_on).await;
+    }
+
+    /// Whether night mode / speech enhancement is on, if this speaker
+    /// exposes a [`MediaPlayerSpeaker::night_mode_entity`].
+    pub fn night_mode(&self) -> Option<bool> {
+        let entity_id = self.advanced_entities()?.night_mode_entity?;
+        self.oracle.switches.lock().get(entity_id).map(|s| s.is_on)
+    }
+
+    pub async fn set_night_mode(&self, is_on: bool) {
+        let Some(entity_id) = self.advanced_entities().and_then(|s| s.night_mode_entity) else {
+            return;
+        };
+
+        self.set_switch(entity_id, is_on).await;
+    }
+
+    async fn set_switch(&self, entity_id: &'static str, is_on: bool) {
+        if let Some(switch) = self.oracle.switches.lock().get_mut(entity_id) {
+            switch.is_on = is_on;
+        }
+
+        let data = CallServiceRequestData::Switch(if is_on {
+            CallServiceRequestSwitch::TurnOn
+        } else {
+            CallServiceRequestSwitch::TurnOff
+        });
+
+        let _res = self.oracle.client.call_service(entity_id, data).await;
+    }
+
+    pub async fn seek(&self, position: Duration) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.media_position = Some(position);
+            speaker.actual_media_position = Some(position);
+            speaker.media_position_updated_at = Some(OffsetDateTime::now_utc());
+        }
+
+        self.call(CallServiceRequestMediaPlayer::MediaSeek(
+            CallServiceRequestMediaPlayerMediaSeek {
+                seek_position: position,
+            },
+        ))
+        .await;
+    }
+
+    pub async fn set_shuffle(&self, shuffle: bool) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.shuffle = shuffle;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::ShuffleSet(
+            CallServiceRequestMediaPlayerShuffleSet { shuffle },
+        ))
+        .await;
+    }
+
+    pub async fn set_repeat(&self, repeat: MediaPlayerRepeat) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.repeat = repeat;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::RepeatSet(
+            CallServiceRequestMediaPlayerRepeatSet { repeat },
+        ))
+        .await;
+    }
+
+    pub async fn play(&self) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.state = MediaPlayerSpeakerState::Playing;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::MediaPlay).await;
+    }
+
+    pub async fn pause(&self) {
+        if let MediaPlayer::Speaker(speaker) = self
+            .oracle
+            .media_players
+            .lock()
+            .get_mut(self.speaker_id)
+            .unwrap()
+        {
+            speaker.state = MediaPlayerSpeakerState::Paused;
+        }
+
+        self.call(CallServiceRequestMediaPlayer::MediaPause).await;
+    }
+
+    pub async fn next(&self) {
+        self.call(CallServiceRequestMediaPlayer::MediaNextTrack)
+            .await;
+    }
+
+    pub async fn previous(&self) {
+        self.call(CallServiceRequestMediaPlayer::MediaPreviousTrack)
+            .await;
+    }
+
+    pub async fn play_track(&self, uri: String) {
+        self.play_media(uri, "music".to_string()).await;
+    }
+
+    /// Speaks `message` on this speaker via Home Assistant's `tts.speak`
+    /// service, using `tts_entity` (e.g. `tts.piper` or
+    /// `tts.google_translate_en_com`) as the text-to-speech provider. Plays
+    /// the configured chime (see [`crate::config::AnnouncementsConfig::chime_url`])
+    /// first, if any.
+    pub async fn announce(&self, tts_entity: &'static str, message: String) {
+        if let Some(chime_url) = self.oracle.chime_url {
+            self.play_chime(chime_url).await;
+        }
+
+        self.oracle
+            .call_service(
+                "tts",
+                "speak",
+                tts_entity,
+                serde_json::json!({
+                    "media_player_entity_id": self.speaker_id,
+                    "message": message,
+                }),
+            )
+            .await;
+    }
+
+    pub async fn play_media(&self, media_content_id: String, media_content_type: String) {
+        self.record_recently_played(media_content_id.clone());
+
+        self.play_media_with_enqueue(
+            media_content_id,
+            media_content_type,
+            CallServiceRequestMediaPlayerPlayMediaEnqueue::Play,
+        )
+        .await;
+    }
+
+    /// Plays an uploaded intercom clip (see [`crate::intercom`]). Unlike
+    /// [`Self::play_media`], this doesn't get added to `recently_played`,
+    /// since it's a one-off voice memo rather than music.
+    pub async fn play_intercom_clip(&self, media_content_id: String) {
+        self.play_media_with_enqueue(
+            media_content_id,
+            "music".to_string(),
+            CallServiceRequestMediaPlayerPlayMediaEnqueue::Play,
+        )
+        .await;
+    }
+
+    fn record_recently_played(&self, uri: String) {
+        let mut recently_played = self.oracle.recently_played.lock();
+        let played = recently_played.entry(self.speaker_id).or_default();
+
+        played.retain(|v| v != &uri);
+        played.insert(0, uri);
+        played.truncate(RECENTLY_PLAYED_LIMIT);
+    }
+
+    pub fn recently_played(&self) -> Vec<String> {
+        self.oracle
+            .recently_played
+            .lock()
+            .get(self.speaker_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub async fn queue_track(&self, uri: String) {
+        self.play_media_with_enqueue(
+            uri,
+            "music".to_string(),
+            CallServiceRequestMediaPlayerPlayMediaEnqueue::Add,
+        )
+        .await;
+    }
+
+    async fn play_media_with_enqueue(
+        &self,
+        media_content_id: String,
+        media_content_type: String,
+        enqueue: CallServiceRequestMediaPlayerPlayMediaEnqueue,
+    ) {
+        self.call(CallServiceRequestMediaPlayer::PlayMedia(
+            CallServiceRequestMediaPlayerPlayMedia {
+                media_content_id,
+                media_content_type,
+                enqueue,
+                announce: None,
+            },
+        ))
+        .await;
+    }
+
+    /// Plays the configured chime (see [`crate::config::AnnouncementsConfig::chime_url`])
+    /// on this speaker via `media_player.play_media`, using `announce: true`
+    /// so it ducks rather than interrupts whatever's already playing.
+    async fn play_chime(&self, chime_url: &'static str) {
+        self.call(CallServiceRequestMediaPlayer::PlayMedia(
+            CallServiceRequestMediaPlayerPlayMedia {
+                media_content_id: chime_url.to_string(),
+                media_content_type: "music".to_string(),
+                enqueue: CallServiceRequestMediaPlayerPlayMediaEnqueue::Play,
+                announce: Some(true),
+            },
+        ))
+        .await;
+    }
+
+    pub async fn browse_media(
+        &self,
+        media_content_id: Option<String>,
+        media_content_type: Option<String>,
+    ) -> Vec<MediaItem> {
+        let res = self
+            .oracle
+            .client
+            .browse_media(self.speaker_id, media_content_id, media_content_type)
+            .await;
+
+        let base = &self.oracle.client.base;
+
+        res.get()
+            .children
+            .iter()
+            .map(|item| MediaItem::from_browse_item(item, base))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MediaItem {
+    pub title: Box<str>,
+    pub media_content_id: Box<str>,
+    pub media_content_type: Box<str>,
+    pub can_play: bool,
+    pub can_expand: bool,
+    /// Poster/thumbnail art, e.g. movie/show artwork from a Jellyfin or Plex
+    /// media browser. Relative paths are resolved against the Home Assistant
+    /// base URL, matching [`MediaPlayer`]'s `entity_picture`.
+    pub thumbnail: Option<Url>,
+}
+
+impl MediaItem {
+    fn from_browse_item(value: &responses::BrowseMediaItem<'_>, base: &Url) -> Self {
+        Self {
+            title: value.title.as_ref().into(),
+            media_content_id: value.media_content_id.as_ref().into(),
+            media_content_type: value.media_content_type.as_ref().into(),
+            can_play: value.can_play,
+            can_expand: value.can_expand,
+            thumbnail: value
+                .thumbnail
+                .as_deref()
+                .and_then(|path| base.join(path).ok()),
+        }
+    }
+}
+
+/// The virtual room [`group_entities_by_room`] collects area-less entities
+/// into when `include_unassigned_room` is set, and the area id [`build_room`]
+/// looks it up under.
+const UNASSIGNED_ROOM_ID: &str = "other";
+
+/// Groups entities by the room they end up in: the entity's own `area_id`
+/// if HA has one set (an entity-level override, which takes precedence over
+/// its device's area in HA itself), falling back to the device's area, with
+/// `entity_room_overrides` (`config.toml`'s `entity-room-overrides` table)
+/// applied on top of both for anything HA still placed in the wrong area or
+/// left area-less. An entity that still has no area is dropped unless
+/// `include_unassigned_room` is set, in which case it's grouped under
+/// [`UNASSIGNED_ROOM_ID`] instead. Entities the registry marks
+/// `hidden_by`/`disabled_by` are skipped entirely unless
+/// `show_hidden_entities` is set.
+fn group_entities_by_room<'a>(
+    devices: &'a [Device<'a>],
+    entities: &'a [Entity<'a>],
+    entity_room_overrides: &BTreeMap<&'static str, &'static str>,
+    include_unassigned_room: bool,
+    show_hidden_entities: bool,
+) -> HashMap<&'a str, Vec<&'a Entity<'a>>> {
+    let device_area: HashMap<&str, &str> = devices
+        .iter()
+        .filter_map(|device| {
+            device
+                .area_id
+                .as_deref()
+                .map(|area_id| (device.id.as_ref(), area_id))
+        })
+        .collect();
+
+    let mut room_entities: HashMap<&str, Vec<&Entity>> = HashMap::new();
+
+    for entity in entities {
+        if !show_hidden_entities && (entity.hidden_by.is_some() || entity.disabled_by.is_some()) {
+            continue;
+        }
+
+        let area_id = entity_room_overrides
+            .get(entity.entity_id.as_ref())
+            .copied()
+            .or_else(|| entity.area_id.as_deref())
+            .or_else(|| {
+                entity
+                    .device_id
+                    .as_deref()
+                    .and_then(|device_id| device_area.get(device_id).copied())
+            });
+
+        match area_id {
+            Some(area_id) => room_entities.entry(area_id).or_default().push(entity),
+            None if include_unassigned_room => {
+                room_entities
+                    .entry(UNASSIGNED_ROOM_ID)
+                    .or_default()
+                    .push(entity);
+            }
+            None => {}
+        }
+    }
+
+    room_entities
+}
+
+fn build_room(room_entities: &HashMap<&str, Vec<&Entity>>, room: &Area) -> (&'static str, Room) {
+    let entities = room_entities
+        .get(room.area_id.as_ref())
+        .iter()
+        .flat_map(|v| v.iter())
+        .map(|v| Intern::from(v.entity_id.as_ref()))
+        .collect::<Vec<Intern<str>>>();
+
+    let speaker_id = entities
+        .iter()
+        .filter(|v| {
+            // TODO: support multiple media players in one room
+            v.as_ref() != "media_player.lg_webos_smart_tv"
+        })
+        .find(|v| v.starts_with("media_player."))
+        .copied();
+
+    let lights = entities
+        .iter()
+        .filter(|v| v.starts_with("light."))
+        .copied()
+        .collect();
+
+    let covers = entities
+        .iter()
+        .filter(|v| v.starts_with("cover."))
+        .copied()
+        .collect();
+
+    let humidifiers = entities
+        .iter()
+        .filter(|v| v.starts_with("humidifier."))
+        .copied()
+        .collect();
+
+    let thermostats = entities
+        .iter()
+        .filter(|v| v.starts_with("climate."))
+        .copied()
+        .collect();
+
+    let binary_sensors = entities
+        .iter()
+        .filter(|v| v.starts_with("binary_sensor."))
+        .copied()
+        .collect();
+
+    let adaptive_lighting_switch = entities
+        .iter()
+        .find(|v| v.starts_with("switch.adaptive_lighting_"))
+        .copied();
+
+    let area = Intern::<str>::from(room.area_id.as_ref()).as_ref();
+    let room = Room {
+        name: Intern::from(room.name.as_ref()),
+        entities,
+        speaker_id,
+        lights,
+        covers,
+        humidifiers,
+        thermostats,
+        binary_sensors,
+        adaptive_lighting_switch,
+    };
+
+    (area, room)
+}
+
+#[derive(Debug, Clone)]
+pub struct Timer {
+    pub friendly_name: Box<str>,
+    pub active: bool,
+    pub finishes_at: Option<OffsetDateTime>,
+}
+
+impl Timer {
+    pub fn remaining(&self) -> Option<Duration> {
+        if !self.active {
+            return None;
+        }
+
+        self.finishes_at
+            .map(|finishes_at| (finishes_at - OffsetDateTime::now_utc()).unsigned_abs())
+    }
+}
+
+impl From<(&StateTimerAttributes<'_>, &str)> for Timer {
+    fn from((attr, state): (&StateTimerAttributes<'_>, &str)) -> Self {
+        Self {
+            friendly_name: Box::from(attr.friendly_name.as_deref().unwrap_or("Timer")),
+            active: state == "active",
+            finishes_at: attr.finishes_at,
+        }
+    }
+}
+
+/// A panel-local kitchen timer, unrelated to a Home Assistant `timer.*`
+/// entity (see [`Timer`]). Lives only in memory, so it doesn't survive a
+/// panel restart.
+#[derive(Debug, Clone)]
+pub struct LocalTimer {
+    pub id: u64,
+    pub label: Box<str>,
+    pub finishes_at: OffsetDateTime,
+}
+
+impl LocalTimer {
+    pub fn remaining(&self) -> Duration {
+        (self.finishes_at - OffsetDateTime::now_utc()).unsigned_abs()
+    }
+}
+
+/// A [`LocalTimer`] reaching zero, broadcast for the alarm overlay in
+/// `main.rs`.
+#[derive(Clone, Debug)]
+pub struct LocalTimerFinished {
+    pub label: Box<str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShoppingListItem {
+    pub id: Box<str>,
+    pub name: Box<str>,
+    pub complete: bool,
+}
+
+impl From<&responses::ShoppingListItem<'_>> for ShoppingListItem {
+    fn from(value: &responses::ShoppingListItem<'_>) -> Self {
+        Self {
+            id: Box::from(value.id.as_ref()),
+            name: Box::from(value.name.as_ref()),
+            complete: value.complete,
+        }
+    }
+}
+
+/// Whether a `binary_sensor.*` entity reports occupancy, for the room cards'
+/// occupancy badge.
+fn is_occupancy_sensor(attr: &StateBinarySensorAttributes) -> bool {
+    matches!(
+        attr.device_class.as_deref(),
+        Some("motion" | "occupancy" | "presence")
+    )
+}
+
+/// A weather warning surfaced by a `binary_sensor.*` entity with a `safety`
+/// device class (the shape used by the Met Office and NWS integrations).
+#[derive(Clone, Debug)]
+pub struct WeatherAlert {
+    pub message: Box<str>,
+}
+
+impl WeatherAlert {
+    fn from_state(state: &str, attr: &StateBinarySensorAttributes) -> Option<Self> {
+        if attr.device_class.as_deref() != Some("safety") || state != "on" {
+            return None;
+        }
+
+        Some(Self {
+            message: Box::from(attr.friendly_name.as_deref().unwrap_or("Weather alert")),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Camera {
+    pub name: Box<str>,
+    pub entity_picture: Url,
+}
+
+impl Camera {
+    pub fn new(value: &StateCameraAttributes, base: &Url) -> Self {
+        Self {
+            name: value.friendly_name.to_string().into_boxed_str(),
+            entity_picture: base.join(&value.entity_picture).unwrap(),
+        }
+    }
+}
+
+/// A robot vacuum. `map` is the vacuum's rendered map snapshot, when the
+/// integration publishes one as the entity's `entity_picture` (as Xiaomi and
+/// Roborock integrations do); it shows the robot's current position baked
+/// into the image itself, since `vacuum` entities don't expose a position
+/// attribute.
+#[derive(Clone, Debug)]
+pub struct Vacuum {
+    pub name: Box<str>,
+    pub status: Box<str>,
+    pub battery_level: Option<u8>,
+    pub fan_speed: Option<Box<str>>,
+    pub map: Option<Url>,
+}
+
+impl Vacuum {
+    pub fn new(value: &StateVacuumAttributes, state: &str, base: &Url) -> Self {
+        Self {
+            name: Box::from(value.friendly_name.as_deref().unwrap_or("Vacuum")),
+            status: Box::from(state),
+            battery_level: value.battery_level,
+            fan_speed: value.fan_speed.as_deref().map(Box::from),
+            map: value
+                .entity_picture
+                .as_deref()
+                .map(|path| base.join(path).unwrap()),
+        }
+    }
+}
+
+/// A `cover` entity. `device_class` distinguishes a garage door from blinds,
+/// shutters, etc.; [`Room::garage_covers`] is how callers restrict to the
+/// only device class with a dedicated card today.
+#[derive(Clone, Debug)]
+pub struct Cover {
+    pub friendly_name: Box<str>,
+    pub device_class: Option<Box<str>>,
+    pub state: Box<str>,
+}
+
+impl Cover {
+    pub fn new(value: &StateCoverAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Cover")),
+            device_class: value.device_class.as_deref().map(Box::from),
+            state: Box::from(state),
+        }
+    }
+
+    pub fn is_garage(&self) -> bool {
+        self.device_class.as_deref() == Some("garage")
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.state.as_ref() == "open"
+    }
+}
+
+/// A `humidifier` entity. `device_class` (`humidifier` or `dehumidifier`)
+/// only changes the icon/label; the control surface (target humidity, mode)
+/// is identical either way.
+#[derive(Clone, Debug)]
+pub struct Humidifier {
+    pub friendly_name: Box<str>,
+    pub device_class: Option<Box<str>>,
+    pub on: bool,
+    pub min_humidity: u8,
+    pub max_humidity: u8,
+    pub humidity: Option<u8>,
+    pub mode: Option<Box<str>>,
+    pub available_modes: Vec<Box<str>>,
+}
+
+impl Humidifier {
+    pub fn new(value: &StateHumidifierAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Humidifier")),
+            device_class: value.device_class.as_deref().map(Box::from),
+            on: state == "on",
+            min_humidity: value.min_humidity,
+            max_humidity: value.max_humidity,
+            humidity: value.humidity,
+            mode: value.mode.as_deref().map(Box::from),
+            available_modes: value
+                .available_modes
+                .iter()
+                .map(|mode| Box::from(mode.as_ref()))
+                .collect(),
+        }
+    }
+
+    pub fn is_dehumidifier(&self) -> bool {
+        self.device_class.as_deref() == Some("dehumidifier")
+    }
+}
+
+/// A `climate` (thermostat) entity. `hvac_mode` is the entity's `state`, not
+/// an attribute.
+#[derive(Clone, Debug)]
+pub struct Thermostat {
+    pub friendly_name: Box<str>,
+    pub hvac_mode: Box<str>,
+    pub hvac_modes: Vec<Box<str>>,
+    pub current_temperature: Option<f64>,
+    pub temperature: Option<f64>,
+    pub fan_mode: Option<Box<str>>,
+    pub fan_modes: Vec<Box<str>>,
+    pub preset_mode: Option<Box<str>>,
+    pub preset_modes: Vec<Box<str>>,
+}
+
+impl Thermostat {
+    pub fn new(value: &StateClimateAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Thermostat")),
+            hvac_mode: Box::from(state),
+            hvac_modes: value
+                .hvac_modes
+                .iter()
+                .map(|mode| Box::from(mode.as_ref()))
+                .collect(),
+            current_temperature: value.current_temperature,
+            temperature: value.temperature,
+            fan_mode: value.fan_mode.as_deref().map(Box::from),
+            fan_modes: value
+                .fan_modes
+                .iter()
+                .map(|mode| Box::from(mode.as_ref()))
+                .collect(),
+            preset_mode: value.preset_mode.as_deref().map(Box::from),
+            preset_modes: value
+                .preset_modes
+                .iter()
+                .map(|mode| Box::from(mode.as_ref()))
+                .collect(),
+        }
+    }
+}
+
+/// An `update` entity. `has_update` mirrors the entity's `state`, which is
+/// `"on"` while a newer version is available.
+#[derive(Clone, Debug)]
+pub struct Update {
+    pub friendly_name: Box<str>,
+    pub has_update: bool,
+    pub installed_version: Option<Box<str>>,
+    pub latest_version: Option<Box<str>>,
+    pub release_summary: Option<Box<str>>,
+    pub release_url: Option<Box<str>>,
+    pub in_progress: bool,
+}
+
+impl Update {
+    pub fn new(value: &StateUpdateAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Update")),
+            has_update: state == "on",
+            installed_version: value.installed_version.as_deref().map(Box::from),
+            latest_version: value.latest_version.as_deref().map(Box::from),
+            release_summary: value.release_summary.as_deref().map(Box::from),
+            release_url: value.release_url.as_deref().map(Box::from),
+            in_progress: value.in_progress,
+        }
+    }
+}
+
+/// A generic `sensor` entity, e.g. a systemmonitor CPU/RAM/disk/temperature
+/// reading. The reading itself is the entity's `state`, kept as a string
+/// since not every sensor is numeric; use [`Self::value`] to parse it.
+#[derive(Clone, Debug)]
+pub struct Sensor {
+    pub friendly_name: Box<str>,
+    pub state: Box<str>,
+    pub unit_of_measurement: Option<Box<str>>,
+}
+
+impl Sensor {
+    pub fn new(value: &StateSensorAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Sensor")),
+            state: Box::from(state),
+            unit_of_measurement: value.unit_of_measurement.as_deref().map(Box::from),
+        }
+    }
+
+    /// Parses the sensor's state as a number, e.g. for threshold-based
+    /// colouring. `None` if the state isn't numeric (e.g. `unavailable`).
+    pub fn value(&self) -> Option<f64> {
+        self.state.parse().ok()
+    }
+}
+
+/// A generic `number` entity, e.g. a Sonos speaker's bass/treble control
+/// (see [`MediaPlayerSpeaker::bass_entity`]).
+#[derive(Clone, Debug)]
+pub struct NumberEntity {
+    pub friendly_name: Box<str>,
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl NumberEntity {
+    pub fn new(value: &StateNumberAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Number")),
+            value: state.parse().unwrap_or(0.0),
+            min: value.min,
+            max: value.max,
+            step: value.step,
+        }
+    }
+}
+
+/// A generic `switch` entity, e.g. a Sonos speaker's loudness or night sound
+/// toggle (see [`MediaPlayerSpeaker::loudness_entity`]).
+#[derive(Clone, Debug)]
+pub struct SwitchEntity {
+    pub friendly_name: Box<str>,
+    pub is_on: bool,
+}
+
+impl SwitchEntity {
+    pub fn new(value: &StateSwitchAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Switch")),
+            is_on: state == "on",
+        }
+    }
+}
+
+/// The electricity price sensor configured via
+/// [`crate::config::EnergyConfig`], for the omni page's price card. `hourly`
+/// is today's per-hour rate curve if the sensor exposes one (e.g. a Nordpool
+/// integration's `today` attribute); it's empty for sensors that only report
+/// a single current rate, such as most Tibber or Octopus sensors.
+#[derive(Clone, Debug)]
+pub struct EnergyPrice {
+    pub current: f64,
+    pub unit: Box<str>,
+    pub hourly: Vec<f64>,
+}
+
+impl EnergyPrice {
+    fn from_state(value: &StateSensorAttributes, state: &str) -> Option<Self> {
+        Some(Self {
+            current: state.parse().ok()?,
+            unit: Box::from(value.unit_of_measurement.as_deref().unwrap_or("")),
+            hourly: value.today.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// Soil moisture below this percentage is shown as "needs water" in the
+/// plants card, matching Home Assistant's own plant integration default.
+const PLANT_NEEDS_WATER_BELOW: u8 = 20;
+
+/// A plant tracked via a `plant` entity, or a standalone `sensor` entity with
+/// `device_class: moisture` not attached to one, for the omni page's plants
+/// card.
+#[derive(Clone, Debug)]
+pub struct Plant {
+    pub friendly_name: Box<str>,
+    pub moisture: Option<u8>,
+}
+
+impl Plant {
+    fn new(value: &StatePlantAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Plant")),
+            moisture: value.moisture.or_else(|| state.parse().ok()),
+        }
+    }
+
+    fn from_moisture_sensor(value: &StateSensorAttributes, state: &str) -> Option<Self> {
+        Some(Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Plant")),
+            moisture: Some(state.parse().ok()?),
+        })
+    }
+
+    pub fn needs_water(&self) -> bool {
+        self.moisture
+            .is_some_and(|moisture| moisture < PLANT_NEEDS_WATER_BELOW)
+    }
+}
+
+/// A single bin/waste-stream's next collection, from a configured
+/// [`crate::config::BinCollectionConfig`] sensor entity. `next_collection` is
+/// the sensor's raw state (shown as-is, since integrations don't agree on a
+/// display format); [`Self::is_tomorrow`] additionally tries to parse it as
+/// an ISO 8601 date for the "reminder the evening before" highlight, and is
+/// simply `false` for sensors that report something else.
+#[derive(Clone, Debug)]
+pub struct BinCollection {
+    pub bin_type: Box<str>,
+    pub next_collection: Box<str>,
+    parsed_date: Option<time::Date>,
+}
+
+impl BinCollection {
+    fn new(friendly_name: Option<&str>, state: &str) -> Self {
+        Self {
+            bin_type: Box::from(friendly_name.unwrap_or("Bin")),
+            next_collection: Box::from(state),
+            parsed_date: parse_iso_date(state),
+        }
+    }
+
+    pub fn is_tomorrow(&self) -> bool {
+        let today = OffsetDateTime::now_local()
+            .unwrap_or_else(|_| OffsetDateTime::now_utc())
+            .date();
+
+        self.parsed_date
+            .is_some_and(|date| date == today + time::Duration::days(1))
+    }
+}
+
+fn parse_iso_date(state: &str) -> Option<time::Date> {
+    let format = time::format_description::parse("[year]-[month]-[day]").ok()?;
+    time::Date::parse(state, &format).ok()
+}
+
+/// Threshold below which a departure is highlighted as "leave now" on the
+/// omni page.
+const DEPARTING_SOON_MINUTES: i64 = 10;
+
+/// The next departure from a configured [`crate::config::TransportConfig`]
+/// sensor entity. `departure` is the sensor's raw state (shown as-is, since
+/// integrations disagree on whether it's a countdown string like `5 min` or
+/// a timestamp); [`Self::is_departing_soon`] additionally tries to parse it
+/// as an RFC 3339 timestamp for the "leave now" highlight, and is simply
+/// `false` for sensors that report a countdown string instead.
+#[derive(Clone, Debug)]
+pub struct Departure {
+    pub line: Box<str>,
+    pub departure: Box<str>,
+    parsed_at: Option<OffsetDateTime>,
+}
+
+impl Departure {
+    fn new(friendly_name: Option<&str>, state: &str) -> Self {
+        Self {
+            line: Box::from(friendly_name.unwrap_or("Departure")),
+            departure: Box::from(state),
+            parsed_at: OffsetDateTime::parse(state, &Rfc3339).ok(),
+        }
+    }
+
+    pub fn is_departing_soon(&self) -> bool {
+        self.parsed_at.is_some_and(|at| {
+            let minutes = (at - OffsetDateTime::now_utc()).whole_minutes();
+            (0..=DEPARTING_SOON_MINUTES).contains(&minutes)
+        })
+    }
+}
+
+/// A watched appliance entity from [`crate::config::ApplianceConfig`],
+/// tracking whether it's currently running so a state change can be told
+/// apart from a cycle actually finishing.
+#[derive(Clone, Debug)]
+struct ApplianceRule {
+    name: Box<str>,
+    running_state: Box<str>,
+    speaker_id: Option<&'static str>,
+    running: bool,
+}
+
+impl ApplianceRule {
+    fn from_config(value: crate::config::ApplianceConfig) -> Self {
+        Self {
+            name: Box::from(value.name.as_str()),
+            running_state: Box::from(value.running_state.as_str()),
+            speaker_id: value
+                .speaker_id
+                .map(|id| Intern::<str>::from(id.as_str()).as_ref()),
+            running: false,
+        }
+    }
+}
+
+/// A watched appliance's cycle finishing, broadcast for the toast/speaker
+/// announcement in `main.rs`.
+#[derive(Clone, Debug)]
+pub struct ApplianceFinished {
+    pub name: Box<str>,
+    pub speaker_id: Option<&'static str>,
+}
+
+/// A watched entity from [`crate::config::AnnouncementEventConfig`], tracking
+/// its last-seen state so a transition *into* `trigger_state` can be told
+/// apart from an unrelated update while already in that state.
+#[derive(Clone, Debug)]
+struct AnnouncementRule {
+    message: Box<str>,
+    trigger_state: Box<str>,
+    speaker_id: &'static str,
+    last_state: Box<str>,
+}
+
+impl AnnouncementRule {
+    fn from_config(value: crate::config::AnnouncementEventConfig) -> Self {
+        Self {
+            message: Box::from(value.message.as_str()),
+            trigger_state: Box::from(value.trigger_state.as_str()),
+            speaker_id: Intern::<str>::from(value.speaker_id.as_str()).as_ref(),
+            last_state: Box::from(""),
+        }
+    }
+}
+
+/// A watched entity transitioning into its configured `trigger_state`,
+/// broadcast for the chime/TTS announcement in `main.rs`.
+#[derive(Clone, Debug)]
+pub struct Announcement {
+    pub message: Box<str>,
+    pub speaker_id: &'static str,
+}
+
+/// A wake-up alarm from a configured [`crate::config::AlarmConfig`]. There's
+/// no `alarm` entity domain in Home Assistant, so alarms are entirely
+/// panel-side, tracked here and fired by [`Oracle::spawn_worker`].
+#[derive(Clone, Debug)]
+pub struct Alarm {
+    pub name: Box<str>,
+    /// Raw `HH:MM` from config, shown as-is on the alarms page.
+    pub time: Box<str>,
+    time_of_day: time::Time,
+    speaker_id: &'static str,
+    media_uri: Box<str>,
+    media_content_type: Box<str>,
+    starting_volume: f32,
+    target_volume: f32,
+    ramp_duration_secs: u64,
+    pub enabled: bool,
+}
+
+impl Alarm {
+    fn from_config(config: crate::config::AlarmConfig) -> Option<Self> {
+        Some(Self {
+            name: Box::from(config.name.as_str()),
+            time_of_day: parse_alarm_time(&config.time)?,
+            time: Box::from(config.time.as_str()),
+            speaker_id: Intern::<str>::from(config.speaker_id.as_str()).as_ref(),
+            media_uri: Box::from(config.media_uri.as_str()),
+            media_content_type: Box::from(config.media_content_type.as_str()),
+            starting_volume: config.starting_volume,
+            target_volume: config.target_volume,
+            ramp_duration_secs: config.ramp_duration_secs,
+            enabled: true,
+        })
+    }
+}
+
+fn parse_alarm_time(time: &str) -> Option<time::Time> {
+    let format = time::format_description::parse("[hour repr:24]:[minute]").ok()?;
+    time::Time::parse(time, &format).ok()
+}
+
+/// A `remote.*` entity (e.g. a Harmony hub or Broadlink blaster), for the
+/// remote page.
+#[derive(Clone, Debug)]
+pub struct Remote {
+    pub friendly_name: Box<str>,
+    pub on: bool,
+    pub activities: Vec<Box<str>>,
+    pub current_activity: Option<Box<str>>,
+}
+
+impl Remote {
+    pub fn new(value: &StateRemoteAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Remote")),
+            on: state == "on",
+            activities: value
+                .activity_list
+                .iter()
+                .map(|activity| Box::from(activity.as_ref()))
+                .collect(),
+            current_activity: value.current_activity.as_deref().map(Box::from),
+        }
+    }
+}
+
+/// A `button` entity, e.g. a `wake_on_lan` "Wake" button. Stateless: pressing
+/// it just calls `button.press`.
+#[derive(Clone, Debug)]
+pub struct Button {
+    pub friendly_name: Box<str>,
+}
+
+impl Button {
+    pub fn new(value: &StateButtonAttributes) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Button")),
+        }
+    }
+}
+
+/// A tracked `person` or `device_tracker` entity, for the map page.
+#[derive(Clone, Debug)]
+pub struct Person {
+    pub friendly_name: Box<str>,
+    /// The entity's state: a zone name such as `home`, `not_home`, or a
+    /// custom zone.
+    pub zone: Box<str>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl Person {
+    pub fn new(value: &StateDeviceTrackerAttributes, state: &str) -> Self {
+        Self {
+            friendly_name: Box::from(value.friendly_name.as_deref().unwrap_or("Person")),
+            zone: Box::from(state),
+            latitude: value.latitude,
+            longitude: value.longitude,
+        }
+    }
+}
+
+/// The map page's centre point and zoom level, from
+/// [`crate::config::MapConfig`], resolved once at startup.
+#[derive(Copy, Clone, Debug)]
+pub struct MapCentre {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub zoom: u8,
+}
+
+impl MapCentre {
+    fn from_config(config: crate::config::MapConfig) -> Option<Self> {
+        Some(Self {
+            latitude: config.home_latitude?,
+            longitude: config.home_longitude?,
+            zoom: config.zoom,
+        })
+    }
+}
+
+/// A routine button on the omni page, from
+/// [`crate::config::RoutineConfig`], resolved once at startup.
+#[derive(Clone, Debug)]
+pub struct Routine {
+    pub name: Box<str>,
+    actions: Vec<RoutineAction>,
+}
+
+#[derive(Clone, Debug)]
+struct RoutineAction {
+    domain: Box<str>,
+    service: Box<str>,
+    entity_id: &'static str,
+}
+
+impl Routine {
+    fn from_config(config: crate::config::RoutineConfig) -> Self {
+        Self {
+            name: Box::from(config.name.as_str()),
+            actions: config
+                .actions
+                .into_iter()
+                .map(|action| RoutineAction {
+                    domain: Box::from(action.domain.as_str()),
+                    service: Box::from(action.service.as_str()),
+                    entity_id: Intern::<str>::from(action.entity_id.as_str()).as_ref(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// One room reachable from the intercom picker, from
+/// [`crate::config::IntercomRoomConfig`].
+#[derive(Clone, Debug)]
+pub struct IntercomRoom {
+    pub name: Box<str>,
+    pub speaker_id: &'static str,
+}
+
+impl IntercomRoom {
+    fn from_config(config: crate::config::IntercomRoomConfig) -> Self {
+        Self {
+            name: Box::from(config.name.as_str()),
+            speaker_id: Intern::<str>::from(config.speaker_id.as_str()).as_ref(),
+        }
+    }
+}
+
+/// One button on the persistent quick-actions bar, from
+/// [`crate::config::QuickActionConfig`].
+#[derive(Clone, Debug)]
+pub struct QuickAction {
+    pub label: Box<str>,
+    pub kind: QuickActionKind,
+}
+
+/// What a [`QuickAction`] does when pressed, from
+/// [`crate::config::QuickActionKindConfig`].
+#[derive(Clone, Debug)]
+pub enum QuickActionKind {
+    RunRoutine(usize),
+    AllLightsOff,
+    OpenCamera(&'static str),
+    MuteAllSpeakers,
+}
+
+impl QuickAction {
+    fn from_config(config: crate::config::QuickActionConfig) -> Self {
+        let kind = match config.kind {
+            crate::config::QuickActionKindConfig::RunRoutine { index } => {
+                QuickActionKind::RunRoutine(index)
+            }
+            crate::config::QuickActionKindConfig::AllLightsOff => QuickActionKind::AllLightsOff,
+            crate::config::QuickActionKindConfig::OpenCamera { entity_id } => {
+                QuickActionKind::OpenCamera(Intern::<str>::from(entity_id.as_str()).as_ref())
+            }
+            crate::config::QuickActionKindConfig::MuteAllSpeakers => {
+                QuickActionKind::MuteAllSpeakers
+            }
+        };
+
+        Self {
+            label: Box::from(config.label.as_str()),
+            kind,
+        }
+    }
+}
+
+/// The floorplan page's plan and hotspots, from
+/// [`crate::config::FloorplanConfig`].
+#[derive(Clone, Debug)]
+pub struct FloorplanPlan {
+    pub svg_path: PathBuf,
+    pub hotspots: Vec<FloorplanHotspot>,
+}
+
+/// One tappable hotspot on a [`FloorplanPlan`], from
+/// [`crate::config::FloorplanHotspotConfig`].
+#[derive(Clone, Debug)]
+pub struct FloorplanHotspot {
+    pub element_id: Box<str>,
+    pub entity_id: &'static str,
+}
+
+impl FloorplanPlan {
+    fn from_config(config: crate::config::FloorplanConfig) -> Option<Self> {
+        Some(Self {
+            svg_path: PathBuf::from(config.svg_path?),
+            hotspots: config
+                .hotspots
+                .into_iter()
+                .map(|hotspot| FloorplanHotspot {
+                    element_id: Box::from(hotspot.element_id.as_str()),
+                    entity_id: Intern::<str>::from(hotspot.entity_id.as_str()).as_ref(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Maximum number of recent colours kept per light.
+const RECENT_COLOURS_LIMIT: usize = 5;
+
+/// Home Assistant's name for its built-in colour-cycling light effect,
+/// consulted by [`Oracle::set_light_colour_loop`] to decide whether a bulb
+/// can run the colour loop natively.
+const COLOUR_LOOP_EFFECT: &str = "colorloop";
+
+/// Hue degrees advanced per [`Oracle::step_colour_loops`] tick, for lights
+/// running the client-side colour loop.
+const COLOUR_LOOP_HUE_STEP: f32 = 4.0;
+
+/// Where recently used light colours are persisted, so they survive a
+/// restart. Relative to the working directory the panel is run from, same as
+/// `config.toml`.
+const RECENT_COLOURS_PATH: &str = "./recent_colours.json";
+
+/// One hue/saturation/brightness combination previously applied to a light,
+/// for the "Recent" swatches in the light context menu. Hue is in degrees
+/// (0-360); saturation/brightness are 0-1.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecentColour {
+    pub hue: f32,
+    pub saturation: f32,
+    pub brightness: f32,
+}
+
+/// Loads previously persisted recent colours from [`RECENT_COLOURS_PATH`].
+/// Returns an empty map if the file doesn't exist or can't be parsed, e.g. on
+/// first run.
+async fn load_recent_colours() -> BTreeMap<&'static str, Vec<RecentColour>> {
+    let Ok(json) = tokio::fs::read_to_string(RECENT_COLOURS_PATH).await else {
+        return BTreeMap::new();
+    };
 
-                let _res = self
-                    .entity_updates
-                    .send(Arc::from(state_changed.entity_id.as_ref()));
-            }
-        }
-    }
+    let Ok(recent_colours) = serde_json::from_str::<HashMap<String, Vec<RecentColour>>>(&json)
+    else {
+        return BTreeMap::new();
+    };
+
+    recent_colours
+        .into_iter()
+        .map(|(id, colours)| (Intern::<str>::from(id.as_str()).as_ref(), colours))
+        .collect()
 }
 
-/// Eloquent interface for interacting with a speaker. Does not hold any state
-/// of its own.
-pub struct EloquentSpeaker<'a> {
-    oracle: &'a Oracle,
-    speaker_id: &'static str,
+/// Where a snapshot of last-known light/media-player/weather state is
+/// persisted on shutdown, so it can be shown at the next startup instead of
+/// a blank loading page while [`Oracle::new`] waits on Home Assistant.
+/// Relative to the working directory the panel is run from, same as
+/// `config.toml`.
+const STATE_SNAPSHOT_PATH: &str = "./state_snapshot.json";
+
+/// A trimmed-down, serializable snapshot of [`Oracle`]'s lights/media
+/// players/weather, persisted to [`STATE_SNAPSHOT_PATH`] on shutdown and
+/// reloaded at the next startup. This intentionally doesn't reuse
+/// [`Light`]/[`MediaPlayer`] directly: most of their fields carry `Intern`ed
+/// `&'static str` entity ids and (for [`MediaPlayerSpeaker`]) a `url::Url`,
+/// which don't round-trip through `serde_json` here — only enough is kept to
+/// paint a "here's roughly what things looked like last time" picture.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateSnapshot {
+    lights: HashMap<String, LightSnapshot>,
+    media_players: HashMap<String, MediaPlayerSnapshot>,
+    weather: Option<Weather>,
 }
 
-impl EloquentSpeaker<'_> {
-    async fn call(&self, msg: CallServiceRequestMediaPlayer) {
-        let _res = self
-            .oracle
-            .client
-            .call_service(self.speaker_id, CallServiceRequestData::MediaPlayer(msg))
-            .await;
-    }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LightSnapshot {
+    friendly_name: String,
+    on: Option<bool>,
+    brightness: Option<f32>,
+}
 
-    pub async fn set_mute(&self, is_volume_muted: bool) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.muted = true;
-        }
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MediaPlayerSnapshot {
+    friendly_name: String,
+    state: MediaPlayerSpeakerState,
+    media_title: Option<String>,
+    media_artist: Option<String>,
+}
 
-        self.call(CallServiceRequestMediaPlayer::VolumeMute(
-            CallServiceRequestMediaPlayerVolumeMute { is_volume_muted },
-        ))
-        .await;
-    }
+/// Loads a previously persisted [`StateSnapshot`] from
+/// [`STATE_SNAPSHOT_PATH`]. Returns an empty snapshot if the file doesn't
+/// exist or can't be parsed, e.g. on first run.
+async fn load_state_snapshot() -> StateSnapshot {
+    let Ok(json) = tokio::fs::read_to_string(STATE_SNAPSHOT_PATH).await else {
+        return StateSnapshot::default();
+    };
 
-    pub async fn set_volume(&self, volume_level: f32) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.volume = volume_level;
-        }
+    serde_json::from_str(&json).unwrap_or_default()
+}
 
-        self.call(CallServiceRequestMediaPlayer::VolumeSet(
-            CallServiceRequestMediaPlayerVolumeSet { volume_level },
-        ))
-        .await;
-    }
+/// Where scheduled scene triggers are persisted, so they survive a restart.
+/// Relative to the working directory the panel is run from, same as
+/// `config.toml`.
+const SCHEDULED_SCENES_PATH: &str = "./scheduled_scenes.json";
 
-    pub async fn seek(&self, position: Duration) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.media_position = Some(position);
-            speaker.actual_media_position = Some(position);
-            speaker.media_position_updated_at = Some(OffsetDateTime::now_utc());
-        }
+/// A "turn on this scene at this time" entry created from the panel's
+/// scheduler page. There's no Home Assistant concept of this, so unlike most
+/// of `Oracle`'s state it isn't sourced from entities or `config.toml` at
+/// all: it's created, persisted, and fired entirely on the panel side, for
+/// people who'd rather not write an HA automation for something this simple.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScheduledScene {
+    pub id: u64,
+    pub name: Box<str>,
+    pub scene_entity_id: Box<str>,
+    pub hour: u8,
+    pub minute: u8,
+    pub enabled: bool,
+}
 
-        self.call(CallServiceRequestMediaPlayer::MediaSeek(
-            CallServiceRequestMediaPlayerMediaSeek {
-                seek_position: position,
-            },
-        ))
-        .await;
-    }
+/// Loads previously persisted scheduled scenes from
+/// [`SCHEDULED_SCENES_PATH`]. Returns an empty list if the file doesn't
+/// exist or can't be parsed, e.g. on first run.
+async fn load_scheduled_scenes() -> Vec<ScheduledScene> {
+    let Ok(json) = tokio::fs::read_to_string(SCHEDULED_SCENES_PATH).await else {
+        return Vec::new();
+    };
 
-    pub async fn set_shuffle(&self, shuffle: bool) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.shuffle = shuffle;
-        }
+    serde_json::from_str(&json).unwrap_or_default()
+}
 
-        self.call(CallServiceRequestMediaPlayer::ShuffleSet(
-            CallServiceRequestMediaPlayerShuffleSet { shuffle },
-        ))
-        .await;
-    }
+const HOUSEHOLD_NOTES_PATH: &str = "./household_notes.json";
 
-    pub async fn set_repeat(&self, repeat: MediaPlayerRepeat) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.repeat = repeat;
-        }
+/// A short message left on the omni page's note board by whoever's standing
+/// at the panel. There's no Home Assistant concept of this either, so like
+/// [`ScheduledScene`] it's created, persisted, and read entirely on the
+/// panel side.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HouseholdNote {
+    pub id: u64,
+    pub author: Box<str>,
+    pub message: Box<str>,
+}
 
-        self.call(CallServiceRequestMediaPlayer::RepeatSet(
-            CallServiceRequestMediaPlayerRepeatSet { repeat },
-        ))
-        .await;
-    }
+/// Loads previously persisted notes from [`HOUSEHOLD_NOTES_PATH`]. Returns
+/// an empty list if the file doesn't exist or can't be parsed, e.g. on
+/// first run.
+async fn load_household_notes() -> Vec<HouseholdNote> {
+    let Ok(json) = tokio::fs::read_to_string(HOUSEHOLD_NOTES_PATH).await else {
+        return Vec::new();
+    };
 
-    pub async fn play(&self) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.state = MediaPlayerSpeakerState::Playing;
-        }
+    serde_json::from_str(&json).unwrap_or_default()
+}
 
-        self.call(CallServiceRequestMediaPlayer::MediaPlay).await;
-    }
+const CHORES_STATE_PATH: &str = "./chores_state.json";
 
-    pub async fn pause(&self) {
-        if let MediaPlayer::Speaker(speaker) = self
-            .oracle
-            .media_players
-            .lock()
-            .get_mut(self.speaker_id)
-            .unwrap()
-        {
-            speaker.state = MediaPlayerSpeakerState::Paused;
-        }
+/// A recurring chore from a configured [`crate::config::ChoreConfig`],
+/// tracked on the omni page's chore tracker card. The chore list itself
+/// lives in `config.toml` like [`Alarm`]/[`AlarmConfig`], but which chores
+/// are currently ticked off is runtime state, persisted separately to
+/// [`CHORES_STATE_PATH`] by name.
+#[derive(Clone, Debug)]
+pub struct Chore {
+    pub name: Box<str>,
+    pub assignee: Box<str>,
+    pub due_date: Option<Box<str>>,
+    pub complete: bool,
+}
 
-        self.call(CallServiceRequestMediaPlayer::MediaPause).await;
+impl Chore {
+    fn from_config(config: crate::config::ChoreConfig, completed: &[String]) -> Self {
+        Self {
+            complete: completed.iter().any(|name| name == &config.name),
+            name: Box::from(config.name.as_str()),
+            assignee: Box::from(config.assignee.as_str()),
+            due_date: config.due_date.as_deref().map(Box::from),
+        }
     }
+}
 
-    pub async fn next(&self) {
-        self.call(CallServiceRequestMediaPlayer::MediaNextTrack)
-            .await;
-    }
+/// Loads the names of chores already ticked off from [`CHORES_STATE_PATH`].
+/// Returns an empty list if the file doesn't exist or can't be parsed, e.g.
+/// on first run.
+async fn load_completed_chores() -> Vec<String> {
+    let Ok(json) = tokio::fs::read_to_string(CHORES_STATE_PATH).await else {
+        return Vec::new();
+    };
 
-    pub async fn previous(&self) {
-        self.call(CallServiceRequestMediaPlayer::MediaPreviousTrack)
-            .await;
-    }
+    serde_json::from_str(&json).unwrap_or_default()
+}
 
-    pub async fn play_track(&self, uri: String) {
-        self.call(CallServiceRequestMediaPlayer::PlayMedia(
-            CallServiceRequestMediaPlayerPlayMedia {
-                media_content_id: uri,
-                media_content_type: CallServiceRequestMediaPlayerPlayMediaType::Music,
-                enqueue: CallServiceRequestMediaPlayerPlayMediaEnqueue::Play,
-            },
-        ))
-        .await;
-    }
+/// Parses a scheduler-page time input of the form `HH:MM`.
+fn parse_hour_minute(time: &str) -> Option<(u8, u8)> {
+    let (hour, minute) = time.trim().split_once(':')?;
+    let hour: u8 = hour.parse().ok()?;
+    let minute: u8 = minute.parse().ok()?;
+
+    (hour < 24 && minute < 60).then_some((hour, minute))
 }
 
-fn build_room(
-    room_devices: &HashMap<&str, Vec<&Vec<&Entity>>>,
-    room: &Area,
-) -> (&'static str, Room) {
-    let entities = room_devices
-        .get(room.area_id.as_ref())
-        .iter()
-        .flat_map(|v| v.iter())
-        .flat_map(|v| v.iter())
-        .map(|v| Intern::from(v.entity_id.as_ref()))
-        .collect::<Vec<Intern<str>>>();
+/// The systemmonitor entity ids configured in
+/// [`crate::config::SystemMonitorConfig`], interned once at startup.
+#[derive(Debug, Default)]
+struct SystemMonitorEntities {
+    cpu: Option<&'static str>,
+    memory: Option<&'static str>,
+    disk: Option<&'static str>,
+    temperature: Option<&'static str>,
+}
 
-    let speaker_id = entities
-        .iter()
-        .filter(|v| {
-            // TODO: support multiple media players in one room
-            v.as_ref() != "media_player.lg_webos_smart_tv"
-        })
-        .find(|v| v.starts_with("media_player."))
-        .copied();
+impl SystemMonitorEntities {
+    fn from_config(config: crate::config::SystemMonitorConfig) -> Self {
+        let intern = |id: Option<String>| id.map(|id| Intern::<str>::from(id.as_str()).as_ref());
 
-    let lights = entities
-        .iter()
-        .filter(|v| v.starts_with("light."))
-        .copied()
-        .collect();
+        Self {
+            cpu: intern(config.cpu_entity),
+            memory: intern(config.memory_entity),
+            disk: intern(config.disk_entity),
+            temperature: intern(config.temperature_entity),
+        }
+    }
+}
 
-    let area = Intern::<str>::from(room.area_id.as_ref()).as_ref();
-    let room = Room {
-        name: Intern::from(room.name.as_ref()),
-        entities,
-        speaker_id,
-        lights,
-    };
+/// A snapshot of the systemmonitor sensors shown on the omni page's system
+/// monitor card, as returned by [`Oracle::system_monitor`].
+#[derive(Clone, Debug, Default)]
+pub struct SystemMonitorStats {
+    pub cpu: Option<Sensor>,
+    pub memory: Option<Sensor>,
+    pub disk: Option<Sensor>,
+    pub temperature: Option<Sensor>,
+}
 
-    (area, room)
+impl SystemMonitorStats {
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none()
+            && self.memory.is_none()
+            && self.disk.is_none()
+            && self.temperature.is_none()
+    }
 }
 
+/// One tappable room on a vacuum's map, resolved from
+/// [`crate::config::VacuumRoomConfig`].
 #[derive(Clone, Debug)]
-pub struct Camera {
+pub struct VacuumRoom {
+    pub segment_id: u32,
     pub name: Box<str>,
-    pub entity_picture: Url,
 }
 
-impl Camera {
-    pub fn new(value: &StateCameraAttributes, base: &Url) -> Self {
+impl From<crate::config::VacuumRoomConfig> for VacuumRoom {
+    fn from(value: crate::config::VacuumRoomConfig) -> Self {
         Self {
-            name: value.friendly_name.to_string().into_boxed_str(),
-            entity_picture: base.join(&value.entity_picture).unwrap(),
+            segment_id: value.segment_id,
+            name: value.name.into_boxed_str(),
         }
     }
 }
@@ -571,6 +4458,7 @@ impl MediaPlayer {
                 .map(calculate_actual_media_position);
 
             MediaPlayer::Speaker(MediaPlayerSpeaker {
+                friendly_name: Box::from(attr.friendly_name.as_deref().unwrap_or("Speaker")),
                 state,
                 volume: attr.volume_level.unwrap(),
                 muted: attr.is_volume_muted.unwrap_or_default(),
@@ -588,6 +4476,19 @@ impl MediaPlayer {
                     .entity_picture
                     .as_deref()
                     .map(|path| base.join(path).unwrap()),
+                media_content_id: attr.media_content_id.as_ref().and_then(|id| match id {
+                    MediaContentId::Uri(uri) => Some(Box::from(uri.as_ref())),
+                    MediaContentId::Int(_) => None,
+                }),
+                group_members: attr
+                    .group_members
+                    .iter()
+                    .map(|id| Intern::<str>::from(id.as_ref()).as_ref())
+                    .collect(),
+                bass_entity: None,
+                treble_entity: None,
+                loudness_entity: None,
+                night_mode_entity: None,
             })
         } else {
             MediaPlayer::Tv(MediaPlayerTv {})
@@ -611,6 +4512,30 @@ pub struct Light {
     pub color_temp_kelvin: Option<u16>,
     pub color_temp: Option<u16>,
     pub hs_color: Option<(f32, f32)>,
+    pub rgbw_color: Option<(u8, u8, u8, u8)>,
+    pub rgbww_color: Option<(u8, u8, u8, u8, u8)>,
+    /// Effects the bulb supports natively, e.g. `["colorloop", "random"]`.
+    pub effect_list: Vec<Box<str>>,
+    /// The currently active effect, if any, e.g. `Some("colorloop")`.
+    pub effect: Option<Box<str>>,
+    /// The entity registry `icon` field, e.g. `mdi:sofa`, if the user has
+    /// overridden it. `None` means fall back to the generic bulb icon.
+    pub icon: Option<Box<str>>,
+    /// A `sensor` entity on the same device reporting current wattage
+    /// (`unit_of_measurement` of `W`), if any, for the light context menu.
+    pub power_entity: Option<&'static str>,
+    /// A `sensor` entity on the same device reporting energy used today
+    /// (`unit_of_measurement` of `kWh`), if any, for the light context menu.
+    pub energy_entity: Option<&'static str>,
+}
+
+/// A light's on/brightness/colour state, captured by
+/// [`Oracle::snapshot_lights`] and put back by [`Oracle::restore_lights`].
+#[derive(Debug, Clone)]
+struct LightRestoreSnapshot {
+    on: Option<bool>,
+    brightness: Option<f32>,
+    hs_color: Option<(f32, f32)>,
 }
 
 impl From<(StateLightAttributes<'_>, &str)> for Light {
@@ -637,12 +4562,59 @@ impl From<(StateLightAttributes<'_>, &str)> for Light {
             color_temp_kelvin: value.color_temp_kelvin,
             color_temp: value.color_temp,
             hs_color: value.hs_color,
+            rgbw_color: value.rgbw_color,
+            rgbww_color: value.rgbww_color,
+            effect_list: value
+                .effect_list
+                .into_iter()
+                .flatten()
+                .map(Box::from)
+                .collect(),
+            effect: value.effect.map(Box::from),
+            icon: None,
+            power_entity: None,
+            energy_entity: None,
+        }
+    }
+}
+
+impl Light {
+    /// A stand-in built from a persisted [`LightSnapshot`], used to seed
+    /// [`Oracle::new`]'s initial map so this entity shows *something* if the
+    /// upcoming `GetStates` round-trip doesn't come back with it (e.g. it's
+    /// since been removed from Home Assistant). Fields the snapshot didn't
+    /// keep take a bulb's out-of-the-box defaults; anything HA does report
+    /// overwrites this once the round-trip completes.
+    fn placeholder(snapshot: &LightSnapshot) -> Self {
+        Self {
+            on: snapshot.on,
+            min_color_temp_kelvin: None,
+            max_color_temp_kelvin: None,
+            min_mireds: None,
+            max_mireds: None,
+            supported_color_modes: Vec::new(),
+            mode: None,
+            dynamics: None,
+            friendly_name: Box::from(snapshot.friendly_name.as_str()),
+            color_mode: None,
+            brightness: snapshot.brightness,
+            color_temp_kelvin: None,
+            color_temp: None,
+            hs_color: None,
+            rgbw_color: None,
+            rgbww_color: None,
+            effect_list: Vec::new(),
+            effect: None,
+            icon: None,
+            power_entity: None,
+            energy_entity: None,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct MediaPlayerSpeaker {
+    pub friendly_name: Box<str>,
     pub state: MediaPlayerSpeakerState,
     pub volume: f32,
     pub muted: bool,
@@ -657,6 +4629,71 @@ pub struct MediaPlayerSpeaker {
     pub shuffle: bool,
     pub repeat: MediaPlayerRepeat,
     pub entity_picture: Option<Url>,
+    /// The raw `media_content_id` reported by Home Assistant, e.g.
+    /// `spotify:track:6habFhsOp2NvshLv26DqMb`. See [`Self::spotify_url`] for
+    /// turning this into a link someone else can open.
+    pub media_content_id: Option<Box<str>>,
+    /// Other speakers grouped with this one (e.g. a Sonos group), including
+    /// this speaker itself. A single-entry list means this speaker isn't
+    /// grouped.
+    pub group_members: Vec<&'static str>,
+    /// A `number.` entity on the same device controlling bass, for speakers
+    /// that expose one (e.g. Sonos).
+    pub bass_entity: Option<&'static str>,
+    /// A `number.` entity on the same device controlling treble.
+    pub treble_entity: Option<&'static str>,
+    /// A `switch.` entity on the same device toggling loudness compensation.
+    pub loudness_entity: Option<&'static str>,
+    /// A `switch.` entity on the same device toggling night sound / speech
+    /// enhancement mode.
+    pub night_mode_entity: Option<&'static str>,
+}
+
+impl MediaPlayerSpeaker {
+    /// A shareable `https://open.spotify.com/...` link for the currently
+    /// playing track, if this speaker is playing from Spotify. `None` for
+    /// any other source, since there's no equivalent web link to share.
+    pub fn spotify_url(&self) -> Option<String> {
+        let id = self.media_content_id.as_deref()?.strip_prefix("spotify:")?;
+        let (kind, id) = id.split_once(':')?;
+
+        Some(format!("https://open.spotify.com/{kind}/{id}"))
+    }
+
+    /// Whether this speaker is the coordinator of a multi-speaker group,
+    /// i.e. the one [`Self::group_members`] is reported against and the one
+    /// the omni/listen pages should show a group-volume slider for.
+    pub fn is_group_coordinator(&self) -> bool {
+        self.group_members.len() > 1
+    }
+
+    /// A stand-in built from a persisted [`MediaPlayerSnapshot`], for the
+    /// same reason as [`Light::placeholder`].
+    fn placeholder(snapshot: &MediaPlayerSnapshot) -> Self {
+        Self {
+            friendly_name: Box::from(snapshot.friendly_name.as_str()),
+            state: snapshot.state,
+            volume: 0.0,
+            muted: false,
+            source: Box::from(""),
+            media_duration: None,
+            media_position: None,
+            media_position_updated_at: None,
+            actual_media_position: None,
+            media_title: snapshot.media_title.as_deref().map(Box::from),
+            media_artist: snapshot.media_artist.as_deref().map(Box::from),
+            media_album_name: None,
+            shuffle: false,
+            repeat: MediaPlayerRepeat::Off,
+            entity_picture: None,
+            media_content_id: None,
+            group_members: Vec::new(),
+            bass_entity: None,
+            treble_entity: None,
+            loudness_entity: None,
+            night_mode_entity: None,
+        }
+    }
 }
 
 fn calculate_actual_media_position(
@@ -672,7 +4709,7 @@ fn calculate_actual_media_position(
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MediaPlayerSpeakerState {
     Playing,
     Unavailable,
@@ -696,6 +4733,17 @@ pub struct Room {
     pub entities: Vec<Intern<str>>,
     pub speaker_id: Option<Intern<str>>,
     pub lights: BTreeSet<Intern<str>>,
+    pub covers: BTreeSet<Intern<str>>,
+    pub humidifiers: BTreeSet<Intern<str>>,
+    pub thermostats: BTreeSet<Intern<str>>,
+    /// `binary_sensor.*` entities in this room; only those tracked by
+    /// [`is_occupancy_sensor`] actually report anything via
+    /// [`Room::is_occupied`].
+    pub binary_sensors: BTreeSet<Intern<str>>,
+    /// The Adaptive Lighting integration's `switch.adaptive_lighting_*`
+    /// entity for this room, if that integration is configured for it.
+    /// `None` for rooms without Adaptive Lighting set up.
+    pub adaptive_lighting_switch: Option<Intern<str>>,
 }
 
 impl Room {
@@ -721,9 +4769,65 @@ impl Room {
             .filter_map(|v| Some((*v).as_ref()).zip(lights.get(v.as_ref()).cloned()))
             .collect()
     }
+
+    /// Garage-door covers in this room. Other cover device classes (blinds,
+    /// shutters, ...) don't have a card yet, so they're left out here.
+    pub fn garage_covers(&self, oracle: &Oracle) -> BTreeMap<&'static str, Cover> {
+        let covers = oracle.covers.lock();
+
+        self.covers
+            .iter()
+            .filter_map(|v| Some((*v).as_ref()).zip(covers.get(v.as_ref()).cloned()))
+            .filter(|(_, cover)| cover.is_garage())
+            .collect()
+    }
+
+    pub fn humidifiers(&self, oracle: &Oracle) -> BTreeMap<&'static str, Humidifier> {
+        let humidifiers = oracle.humidifiers.lock();
+
+        self.humidifiers
+            .iter()
+            .filter_map(|v| Some((*v).as_ref()).zip(humidifiers.get(v.as_ref()).cloned()))
+            .collect()
+    }
+
+    pub fn thermostats(&self, oracle: &Oracle) -> BTreeMap<&'static str, Thermostat> {
+        let thermostats = oracle.thermostats.lock();
+
+        self.thermostats
+            .iter()
+            .filter_map(|v| Some((*v).as_ref()).zip(thermostats.get(v.as_ref()).cloned()))
+            .collect()
+    }
+
+    /// Whether any occupancy sensor in this room is currently reporting
+    /// motion/presence. `None` if the room has no tracked occupancy sensor,
+    /// so callers can distinguish "no sensor" from "sensor says clear".
+    pub fn is_occupied(&self, oracle: &Oracle) -> Option<bool> {
+        let occupancy = oracle.occupancy.lock();
+
+        let mut sensors = self
+            .binary_sensors
+            .iter()
+            .filter_map(|v| occupancy.get(v.as_ref()))
+            .peekable();
+
+        sensors.peek()?;
+
+        Some(sensors.any(|&on| on))
+    }
+
+    /// Whether this room's Adaptive Lighting switch is currently on, for the
+    /// room header and light cards' "Adaptive" badge. `None` if the room has
+    /// no Adaptive Lighting switch configured.
+    pub fn adaptive_lighting_on(&self, oracle: &Oracle) -> Option<bool> {
+        let switch = self.adaptive_lighting_switch?;
+
+        Some(oracle.switches.lock().get(switch.as_ref())?.is_on)
+    }
 }
 
-#[derive(Debug, Copy, Clone, NoUninit)]
+#[derive(Debug, Copy, Clone, NoUninit, Serialize, Deserialize)]
 #[repr(C)]
 pub struct Weather {
     pub temperature: i16,
@@ -737,11 +4841,18 @@ impl Weather {
         WeatherCondition::from_repr(self.condition).unwrap_or_default()
     }
 
+    /// High/low comes from `daily_forecast` (fetched via `weather.get_forecasts`)
+    /// when available, falling back to the `forecast` attribute for older Home
+    /// Assistant versions that still embed it on the state.
     #[allow(clippy::cast_possible_truncation)]
-    fn parse_from_state_and_attributes(state: &str, attributes: &StateWeatherAttributes) -> Self {
+    fn parse_from_state_and_attributes(
+        state: &str,
+        attributes: &StateWeatherAttributes,
+        daily_forecast: &[ForecastDay],
+    ) -> Self {
         let condition = WeatherCondition::from_str(state).unwrap_or_default();
 
-        let (high, low) =
+        let (high, low) = if daily_forecast.is_empty() {
             attributes
                 .forecast
                 .iter()
@@ -749,7 +4860,17 @@ impl Weather {
                     let temp = curr.temperature.round() as i16;
 
                     (high.max(temp), low.min(temp))
-                });
+                })
+        } else {
+            daily_forecast
+                .iter()
+                .fold((i16::MIN, i16::MAX), |(high, low), day| {
+                    (
+                        high.max(day.temperature),
+                        low.min(day.low.unwrap_or(day.temperature)),
+                    )
+                })
+        };
 
         Self {
             temperature: attributes.temperature.round() as i16,
@@ -759,7 +4880,7 @@ impl Weather {
         }
     }
 
-    fn parse_from_states(states: &StatesList) -> Self {
+    fn parse_from_states(states: &StatesList, daily_forecast: &[ForecastDay]) -> Self {
         let (state, attrs) = states
             .0
             .iter()
@@ -769,6 +4890,42 @@ impl Weather {
             })
             .unwrap();
 
-        Self::parse_from_state_and_attributes(state.as_ref(), attrs)
+        Self::parse_from_state_and_attributes(state.as_ref(), attrs, daily_forecast)
+    }
+}
+
+/// One entry of a `weather.get_forecasts` response, converted from the
+/// borrowed [`responses::ForecastEntry`] into an owned value the `Oracle` can
+/// hold onto.
+#[derive(Debug, Clone, Copy)]
+pub struct ForecastDay {
+    pub condition: WeatherCondition,
+    pub temperature: i16,
+    pub low: Option<i16>,
+}
+
+impl From<&responses::ForecastEntry<'_>> for ForecastDay {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(entry: &responses::ForecastEntry<'_>) -> Self {
+        Self {
+            condition: WeatherCondition::from_str(entry.condition.as_ref()).unwrap_or_default(),
+            temperature: entry.temperature.round() as i16,
+            low: entry.temperature_low.map(|low| low.round() as i16),
+        }
     }
 }
+
+async fn fetch_forecast(
+    client: &crate::hass_client::Client,
+    weather_entity_id: &'static str,
+    forecast_type: ForecastType,
+) -> Vec<ForecastDay> {
+    client
+        .weather_get_forecasts(weather_entity_id, forecast_type)
+        .await
+        .get()
+        .0
+        .get(weather_entity_id)
+        .map(|list| list.forecast.iter().map(ForecastDay::from).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,96 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, checkbox, column, container, row, scrollable, text},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::oracle::{Alarm, Oracle};
+
+/// Wake-up alarms, each starting a playlist/station on a speaker at a
+/// configured time with the volume gradually ramping up. Alarms themselves
+/// come entirely from `config.toml`; this page only lets you enable or
+/// disable them.
+#[derive(Debug)]
+pub struct Alarms {
+    oracle: Arc<Oracle>,
+    alarms: Vec<Alarm>,
+}
+
+impl Alarms {
+    pub fn new(oracle: Arc<Oracle>) -> Self {
+        Self {
+            alarms: oracle.alarms(),
+            oracle,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::ToggleEnabled(index, enabled) => {
+                if let Some(alarm) = self.alarms.get_mut(index) {
+                    alarm.enabled = enabled;
+                }
+                Some(Event::SetEnabled(index, enabled))
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Alarms").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let alarms =
+            self.alarms
+                .iter()
+                .enumerate()
+                .fold(column![].spacing(10), |col, (index, alarm)| {
+                    col.push(
+                        container(
+                            row![
+                                checkbox(alarm.name.as_ref(), alarm.enabled, move |v| {
+                                    Message::ToggleEnabled(index, v)
+                                }),
+                                text(alarm.time.as_ref()).size(20),
+                            ]
+                            .spacing(20)
+                            .align_items(Alignment::Center),
+                        )
+                        .padding(10)
+                        .width(Length::Fill),
+                    )
+                });
+
+        container(
+            column![header, scrollable(alarms).height(Length::Fill)]
+                .spacing(20)
+                .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    #[allow(clippy::unused_self)]
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::none()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    SetEnabled(usize, bool),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    ToggleEnabled(usize, bool),
+}
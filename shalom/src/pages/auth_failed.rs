@@ -0,0 +1,78 @@
+//! Shown in place of the loading screen when Home Assistant rejects the
+//! panel's access token (`auth_invalid`), instead of leaving the app hanging
+//! on [`crate::ActivePage::Loading`] forever. Lets someone paste a fresh
+//! long-lived access token and retry the connection without restarting the
+//! panel process.
+
+use iced::{
+    widget::{button, column, container, text, text_input},
+    Alignment, Element, Length, Renderer,
+};
+
+use crate::config::Config;
+
+#[derive(Debug)]
+pub struct AuthFailed {
+    reason: String,
+    config: Config,
+    token: String,
+}
+
+impl AuthFailed {
+    pub fn new(reason: String, config: Config) -> Self {
+        Self {
+            reason,
+            config,
+            token: String::new(),
+        }
+    }
+
+    /// The config the failed connection attempt used, so a retry can be
+    /// rebuilt from it with just the token swapped out.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::TokenChanged(token) => {
+                self.token = token;
+                None
+            }
+            Message::Retry => (!self.token.is_empty()).then(|| Event::Retry(self.token.clone())),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let content = column![
+            text("Couldn't connect to Home Assistant").size(32),
+            text(&self.reason).size(16),
+            text_input("Long-lived access token", &self.token)
+                .on_input(Message::TokenChanged)
+                .on_submit(Message::Retry)
+                .secure(true)
+                .width(Length::Fixed(400.0)),
+            button("Retry").on_press(Message::Retry),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Retry(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    TokenChanged(String),
+    Retry,
+}
@@ -0,0 +1,95 @@
+//! First-run screen that lists Home Assistant instances discovered via mDNS
+//! ([`crate::discovery`]) and lets someone tap one to pre-fill `uri` in a
+//! freshly generated `config.toml`, instead of typing it in by hand. Only
+//! shown when no `config.toml` exists yet; see [`crate::main`]'s boot
+//! sequence. Only compiled in with the `discovery` feature.
+
+use iced::{
+    widget::{button, column, container, scrollable, text},
+    Alignment, Element, Length, Renderer,
+};
+
+use crate::discovery::DiscoveredInstance;
+
+#[derive(Debug, Default)]
+pub struct Discovery {
+    instances: Vec<DiscoveredInstance>,
+    written: Option<Box<str>>,
+}
+
+impl Discovery {
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::InstanceFound(instance) => {
+                if !self.instances.contains(&instance) {
+                    self.instances.push(instance);
+                }
+                None
+            }
+            Message::InstanceSelected(index) => {
+                self.instances.get(index).cloned().map(Event::Selected)
+            }
+            Message::ConfigSaved(uri) => {
+                self.written = Some(uri);
+                None
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let content = if let Some(uri) = &self.written {
+            column![
+                text("Saved!").size(32),
+                text(format!(
+                    "Added {uri} to config.toml. Add your long-lived access token, \
+                     then restart the panel."
+                ))
+                .size(18),
+            ]
+        } else {
+            let header = text("Select your Home Assistant").size(32);
+
+            let instances = self.instances.iter().enumerate().fold(
+                column![].spacing(10),
+                |col, (index, instance)| {
+                    col.push(
+                        button(column![
+                            text(instance.name.as_ref()).size(20),
+                            text(instance.uri.as_ref()).size(14),
+                        ])
+                        .width(Length::Fill)
+                        .on_press(Message::InstanceSelected(index)),
+                    )
+                },
+            );
+
+            column![header, instances].spacing(20)
+        };
+
+        container(scrollable(content.align_items(Alignment::Start)))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .padding(40)
+            .into()
+    }
+
+    pub fn subscription(&self) -> iced::Subscription<Message> {
+        if self.written.is_some() {
+            iced::Subscription::none()
+        } else {
+            crate::discovery::subscription().map(Message::InstanceFound)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Selected(DiscoveredInstance),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    InstanceFound(DiscoveredInstance),
+    InstanceSelected(usize),
+    ConfigSaved(Box<str>),
+}
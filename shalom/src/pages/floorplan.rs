@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use iced::{
+    widget::{button, column, container, mouse_area, row, svg, text, vertical_space},
+    Alignment, Background, Element, Length, Renderer, Subscription, Theme,
+};
+
+use crate::{
+    oracle::{FloorplanPlan, Oracle},
+    theme::colours::{GREEN_500, SLATE_400},
+    widgets::floating_element::{Anchor, FloatingElement},
+};
+
+/// Diameter, in pixels, of a hotspot marker on the plan.
+const MARKER_SIZE: f32 = 24.0;
+
+/// An optional floorplan page (`floorplan` in `config.toml`): a user-supplied
+/// SVG plan of the home with tappable hotspots for individual lights. A
+/// hotspot's position on the plan comes from its matching SVG element's
+/// `cx`/`cy` (or `x`/`y`) attributes, so it lines up with the artwork without
+/// duplicating coordinates in `config.toml`. The plan is rendered at its
+/// native SVG size (read from the root `<svg>` element) rather than scaled to
+/// fill the page, so those coordinates stay accurate.
+#[derive(Debug)]
+pub struct Floorplan {
+    oracle: Arc<Oracle>,
+    svg_handle: svg::Handle,
+    plan_size: (f32, f32),
+    hotspots: Vec<Hotspot>,
+}
+
+#[derive(Debug)]
+struct Hotspot {
+    entity_id: &'static str,
+    x: f32,
+    y: f32,
+    on: bool,
+}
+
+impl Floorplan {
+    pub fn new(oracle: Arc<Oracle>, plan: &FloorplanPlan) -> Self {
+        let svg_source = std::fs::read_to_string(&plan.svg_path).unwrap_or_else(|err| {
+            eprintln!("floorplan: couldn't read {:?}: {err}", plan.svg_path);
+            String::new()
+        });
+
+        let plan_size = find_svg_size(&svg_source).unwrap_or((800.0, 600.0));
+
+        let hotspots = plan
+            .hotspots
+            .iter()
+            .map(|hotspot| {
+                let (x, y) =
+                    find_element_position(&svg_source, &hotspot.element_id).unwrap_or((0.0, 0.0));
+
+                Hotspot {
+                    entity_id: hotspot.entity_id,
+                    x,
+                    y,
+                    on: light_is_on(&oracle, hotspot.entity_id),
+                }
+            })
+            .collect();
+
+        Self {
+            svg_handle: svg::Handle::from_path(&plan.svg_path),
+            oracle,
+            plan_size,
+            hotspots,
+        }
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::HotspotPressed(entity_id) => Some(Event::ToggleLight(entity_id)),
+            Message::HotspotsChanged => {
+                for hotspot in &mut self.hotspots {
+                    hotspot.on = light_is_on(&self.oracle, hotspot.entity_id);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Floorplan").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let mut plan = Element::from(
+            svg(self.svg_handle.clone())
+                .width(self.plan_size.0)
+                .height(self.plan_size.1),
+        );
+
+        for hotspot in &self.hotspots {
+            let colour = if hotspot.on { GREEN_500 } else { SLATE_400 };
+
+            let marker = mouse_area(
+                container(vertical_space(0))
+                    .width(MARKER_SIZE)
+                    .height(MARKER_SIZE)
+                    .style(move |_theme: &Theme| container::Appearance {
+                        background: Some(Background::Color(colour)),
+                        ..container::Appearance::default()
+                    }),
+            )
+            .on_press(Message::HotspotPressed(hotspot.entity_id));
+
+            plan = FloatingElement::new(plan, marker)
+                .anchor(Anchor::NorthWest)
+                .offset([hotspot.x - MARKER_SIZE / 2.0, hotspot.y - MARKER_SIZE / 2.0])
+                .into();
+        }
+
+        container(column![header, plan].spacing(20).padding(40))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch(self.hotspots.iter().map(|hotspot| {
+            self.oracle
+                .subscribe_id(hotspot.entity_id)
+                .map(|()| Message::HotspotsChanged)
+        }))
+    }
+}
+
+fn light_is_on(oracle: &Oracle, entity_id: &'static str) -> bool {
+    oracle
+        .fetch_light(entity_id)
+        .and_then(|light| light.on)
+        .unwrap_or(false)
+}
+
+/// Reads the root `<svg>` element's `width`/`height` attributes, so the plan
+/// can be rendered at its native size and hotspot coordinates line up.
+fn find_svg_size(svg_source: &str) -> Option<(f32, f32)> {
+    let tag_start = svg_source.find("<svg")?;
+    let tag_end = tag_start + svg_source[tag_start..].find('>')?;
+    let tag = &svg_source[tag_start..tag_end];
+
+    read_attr(tag, "width").zip(read_attr(tag, "height"))
+}
+
+/// Finds the element with `id="element_id"` in `svg_source` and reads its
+/// position from `cx`/`cy` (circles) or `x`/`y` (everything else). Returns
+/// `None` if the element or its position attributes aren't found -- the
+/// hotspot is then placed at the plan's origin rather than failing the whole
+/// page.
+fn find_element_position(svg_source: &str, element_id: &str) -> Option<(f32, f32)> {
+    let needle = format!("id=\"{element_id}\"");
+    let tag_start = svg_source[..svg_source.find(&needle)?].rfind('<')?;
+    let tag_end = tag_start + svg_source[tag_start..].find('>')?;
+    let tag = &svg_source[tag_start..tag_end];
+
+    read_attr(tag, "cx")
+        .zip(read_attr(tag, "cy"))
+        .or_else(|| read_attr(tag, "x").zip(read_attr(tag, "y")))
+}
+
+/// Reads a bare numeric attribute (e.g. `x="42"`) from a single SVG tag.
+fn read_attr(tag: &str, name: &str) -> Option<f32> {
+    let needle = format!("{name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = start + tag[start..].find('"')?;
+    tag[start..end].parse().ok()
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    ToggleLight(&'static str),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    HotspotPressed(&'static str),
+    HotspotsChanged,
+}
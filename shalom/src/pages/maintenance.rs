@@ -0,0 +1,144 @@
+use std::{any::TypeId, sync::Arc};
+
+use iced::{
+    subscription,
+    widget::{button, column, container, row, scrollable, text},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::oracle::{Oracle, Update};
+
+/// Firmware updates and Home Assistant server controls. The restart/reload
+/// all/check config buttons are gated behind the `maintenance` PIN, matching
+/// how the quick-settings pull-down is gated behind `settings`.
+#[derive(Debug)]
+pub struct Maintenance {
+    oracle: Arc<Oracle>,
+    updates: Vec<(&'static str, Update)>,
+}
+
+impl Maintenance {
+    pub fn new(oracle: Arc<Oracle>) -> Self {
+        Self {
+            updates: oracle.updates().into_iter().collect(),
+            oracle,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::UpdatesChanged => {
+                self.updates = self.oracle.updates().into_iter().collect();
+                None
+            }
+            Message::Install(id) => Some(Event::Install(id)),
+            Message::RestartHomeAssistant => Some(Event::RestartHomeAssistant),
+            Message::ReloadAll => Some(Event::ReloadAll),
+            Message::CheckConfig => Some(Event::CheckConfig),
+            Message::ExportDiagnostics => Some(Event::ExportDiagnostics),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Firmware Updates").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let updates = self
+            .updates
+            .iter()
+            .fold(column![].spacing(10), |col, (id, update)| {
+                let versions = row![
+                    text(update.installed_version.as_deref().unwrap_or("?")).size(16),
+                    text("→").size(16),
+                    text(update.latest_version.as_deref().unwrap_or("?")).size(16),
+                ]
+                .spacing(10);
+
+                let mut item =
+                    column![text(update.friendly_name.as_ref()).size(20), versions,].spacing(5);
+
+                if let Some(summary) = &update.release_summary {
+                    item = item.push(text(summary.as_ref()).size(14));
+                }
+
+                let install_button = if update.in_progress {
+                    button("Installing...")
+                } else {
+                    button("Install").on_press(Message::Install(*id))
+                };
+
+                col.push(
+                    container(
+                        row![item, install_button]
+                            .spacing(20)
+                            .align_items(Alignment::Center),
+                    )
+                    .padding(10)
+                    .width(Length::Fill),
+                )
+            });
+
+        let server_controls = column![
+            text("Home Assistant Server").size(24),
+            row![
+                button("Restart").on_press(Message::RestartHomeAssistant),
+                button("Reload All").on_press(Message::ReloadAll),
+                button("Check Config").on_press(Message::CheckConfig),
+                button("Export Diagnostics").on_press(Message::ExportDiagnostics),
+            ]
+            .spacing(10),
+        ]
+        .spacing(10);
+
+        container(
+            column![
+                header,
+                scrollable(updates).height(Length::Fill),
+                server_controls,
+            ]
+            .spacing(20)
+            .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        pub struct MaintenanceSubscription;
+
+        subscription::run_with_id(
+            TypeId::of::<MaintenanceSubscription>(),
+            iced::futures::StreamExt::map(self.oracle.subscribe_id_prefix("update."), |()| {
+                Message::UpdatesChanged
+            }),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    Install(&'static str),
+    RestartHomeAssistant,
+    ReloadAll,
+    CheckConfig,
+    ExportDiagnostics,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    UpdatesChanged,
+    Install(&'static str),
+    RestartHomeAssistant,
+    ReloadAll,
+    CheckConfig,
+    ExportDiagnostics,
+}
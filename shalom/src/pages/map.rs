@@ -0,0 +1,200 @@
+use std::{any::TypeId, convert::identity, sync::Arc};
+
+use iced::{
+    futures::StreamExt,
+    subscription,
+    theme::Text,
+    widget::{
+        button, column, container, image, row, scrollable, text, vertical_space, Column, Row,
+    },
+    Alignment, Element, Length, Renderer, Subscription,
+};
+use itertools::Itertools;
+use url::Url;
+
+use crate::{
+    oracle::{MapCentre, Oracle, Person},
+    subscriptions::download_image,
+    theme::colours::{GREEN_500, ORANGE},
+};
+
+/// Radius, in tiles, of the OSM mosaic fetched around the configured centre
+/// point -- a `3x3` grid.
+const GRID_RADIUS: i32 = 1;
+const TILE_SIZE: f32 = 256.0;
+
+/// A family-location dashboard: a static OpenStreetMap tile mosaic centred on
+/// [`crate::config::MapConfig`]'s home coordinates, with a list of tracked
+/// `person`/`device_tracker` entities and their current zone underneath.
+/// Each person's GPS position is not plotted as a pin over the tiles, so the
+/// zone list (not the tiles) is the source of truth for "who's where".
+#[derive(Debug)]
+pub struct Map {
+    oracle: Arc<Oracle>,
+    tiles: Vec<Tile>,
+    people: Vec<(&'static str, Person)>,
+}
+
+#[derive(Debug)]
+enum Tile {
+    Unresolved(Url),
+    Resolved(Url, image::Handle),
+}
+
+impl Map {
+    pub fn new(oracle: Arc<Oracle>, centre: MapCentre) -> Self {
+        let (centre_x, centre_y) = latlon_to_tile(centre.latitude, centre.longitude, centre.zoom);
+
+        let tiles = (-GRID_RADIUS..=GRID_RADIUS)
+            .flat_map(|dy| (-GRID_RADIUS..=GRID_RADIUS).map(move |dx| (dx, dy)))
+            .map(|(dx, dy)| Tile::Unresolved(tile_url(centre.zoom, centre_x + dx, centre_y + dy)))
+            .collect();
+
+        Self {
+            people: oracle.people().into_iter().collect(),
+            oracle,
+            tiles,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::PeopleChanged => {
+                self.people = self.oracle.people().into_iter().collect();
+                None
+            }
+            Message::TileDownloaded(index, url, handle) => {
+                self.tiles[index] = Tile::Resolved(url, handle);
+                None
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![button("Back").on_press(Message::Exit), text("Map").size(40),]
+            .spacing(20)
+            .align_items(Alignment::Center);
+
+        let grid_width = (GRID_RADIUS * 2 + 1) as usize;
+        let tiles = self
+            .tiles
+            .iter()
+            .map(|tile| match tile {
+                Tile::Resolved(_, handle) => {
+                    Element::from(image(handle.clone()).width(TILE_SIZE).height(TILE_SIZE))
+                }
+                Tile::Unresolved(_) => Element::from(
+                    container(vertical_space(0))
+                        .width(TILE_SIZE)
+                        .height(TILE_SIZE),
+                ),
+            })
+            .chunks(grid_width)
+            .into_iter()
+            .map(|children| children.into_iter().fold(Row::new(), Row::push))
+            .fold(Column::new(), Column::push);
+
+        let people = self.people.iter().fold(
+            column![text("People").size(24)].spacing(10),
+            |col, (_, person)| {
+                let colour = if person.zone.as_ref() == "home" {
+                    GREEN_500
+                } else {
+                    ORANGE
+                };
+
+                col.push(
+                    row![
+                        text(person.friendly_name.as_ref()).size(20),
+                        text(person.zone.as_ref())
+                            .size(20)
+                            .style(Text::Color(colour)),
+                    ]
+                    .spacing(20),
+                )
+            },
+        );
+
+        container(
+            column![
+                header,
+                scrollable(column![tiles, people].spacing(20)).height(Length::Fill),
+            ]
+            .spacing(20)
+            .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        pub struct PersonSubscription;
+        pub struct DeviceTrackerSubscription;
+
+        let person_subscription = subscription::run_with_id(
+            TypeId::of::<PersonSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("person.")
+                .map(|()| Message::PeopleChanged),
+        );
+
+        let device_tracker_subscription = subscription::run_with_id(
+            TypeId::of::<DeviceTrackerSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("device_tracker.")
+                .map(|()| Message::PeopleChanged),
+        );
+
+        let tile_downloads =
+            Subscription::batch(self.tiles.iter().enumerate().filter_map(|(index, tile)| {
+                if let Tile::Unresolved(url) = tile {
+                    let url = url.clone();
+
+                    Some(download_image(url.clone(), identity, move |handle| {
+                        Message::TileDownloaded(index, url, handle)
+                    }))
+                } else {
+                    None
+                }
+            }));
+
+        Subscription::batch([
+            person_subscription,
+            device_tracker_subscription,
+            tile_downloads,
+        ])
+    }
+}
+
+/// Converts a latitude/longitude to the `(x, y)` OSM tile containing it, per
+/// the standard slippy map tilename formula: <https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames>.
+fn latlon_to_tile(latitude: f64, longitude: f64, zoom: u8) -> (i32, i32) {
+    let n = 2f64.powi(i32::from(zoom));
+    let x = (longitude + 180.0) / 360.0 * n;
+    let lat_rad = latitude.to_radians();
+    let y = (1.0 - (lat_rad.tan() + 1.0 / lat_rad.cos()).ln() / std::f64::consts::PI) / 2.0 * n;
+
+    (x.floor() as i32, y.floor() as i32)
+}
+
+fn tile_url(zoom: u8, x: i32, y: i32) -> Url {
+    Url::parse(&format!(
+        "https://tile.openstreetmap.org/{zoom}/{x}/{y}.png"
+    ))
+    .unwrap()
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    PeopleChanged,
+    TileDownloaded(usize, Url, image::Handle),
+}
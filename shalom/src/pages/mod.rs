@@ -1,2 +1,12 @@
+pub mod alarms;
+pub mod auth_failed;
+#[cfg(feature = "discovery")]
+pub mod discovery;
+pub mod floorplan;
+pub mod maintenance;
+pub mod map;
 pub mod omni;
+pub mod remote;
 pub mod room;
+pub mod scheduler;
+pub mod shopping_list;
@@ -1,29 +1,85 @@
-use std::{any::TypeId, collections::BTreeMap, convert::identity, sync::Arc};
+use std::{
+    any::TypeId,
+    collections::{BTreeMap, VecDeque},
+    convert::identity,
+    sync::Arc,
+    time::Duration,
+};
 
 use iced::{
     advanced::graphics::core::Element,
     font::{Stretch, Weight},
     futures::StreamExt,
     subscription,
-    widget::{column, container, image, scrollable, text, vertical_space, Column, Row},
-    Font, Renderer, Subscription,
+    theme::Text,
+    widget::{
+        checkbox, column, container, horizontal_space, image, mouse_area, row, scrollable, svg,
+        text, text_input, vertical_space, Column, Row,
+    },
+    Alignment, Background, Font, Length, Renderer, Subscription, Theme,
 };
 use itertools::Itertools;
 use time::OffsetDateTime;
 use url::Url;
 
 use crate::{
-    oracle::{Oracle, Weather},
-    subscriptions::download_image,
-    theme::Image,
-    widgets::image_card,
+    oracle::{
+        BinCollection, Button, Chore, Departure, EnergyPrice, HouseholdNote, IntercomRoom,
+        LocalTimer, Oracle, Plant, Room, Routine, Sensor, SystemMonitorStats, Timer, Vacuum,
+        VacuumRoom, Weather, WeatherAlert,
+    },
+    subscriptions,
+    subscriptions::{download_image, NewsHeadline},
+    theme,
+    theme::{
+        colours::{GREEN_500, ORANGE, RED_500, SYSTEM_GRAY6},
+        FontSize, Icon, Image,
+    },
+    widgets::{
+        floating_element::{Anchor, FloatingElement},
+        image_background::image_background,
+        image_card,
+    },
 };
 
+/// Number of past snapshots kept per camera for the camera detail view's
+/// history strip.
+const CAMERA_HISTORY_LIMIT: usize = 8;
+
 #[derive(Debug)]
 pub struct Omni {
     oracle: Arc<Oracle>,
     weather: Weather,
+    weather_alert: Option<WeatherAlert>,
+    timers: BTreeMap<&'static str, Timer>,
     cameras: BTreeMap<&'static str, CameraImage>,
+    camera_history: BTreeMap<&'static str, VecDeque<image::Handle>>,
+    vacuums: BTreeMap<&'static str, VacuumWidget>,
+    system_monitor: SystemMonitorStats,
+    routines: Vec<Routine>,
+    energy_price: Option<EnergyPrice>,
+    plants: BTreeMap<&'static str, Plant>,
+    bins: BTreeMap<&'static str, BinCollection>,
+    departures: BTreeMap<&'static str, Departure>,
+    news: Vec<NewsHeadline>,
+    buttons: BTreeMap<&'static str, Button>,
+    local_timers: Vec<LocalTimer>,
+    new_timer_label: String,
+    new_timer_minutes: String,
+    household_notes: Vec<HouseholdNote>,
+    new_note_author: String,
+    new_note_message: String,
+    chores: Vec<Chore>,
+    intercom_rooms: Vec<IntercomRoom>,
+    intercom_selected_room: Option<&'static str>,
+    intercom_recording: bool,
+}
+
+#[derive(Debug)]
+pub struct VacuumWidget {
+    vacuum: Vacuum,
+    rooms: Vec<VacuumRoom>,
+    map: Option<iced::widget::image::Handle>,
 }
 
 #[derive(Debug)]
@@ -36,11 +92,46 @@ impl Omni {
     pub fn new(oracle: Arc<Oracle>) -> Self {
         Self {
             weather: oracle.current_weather(),
+            weather_alert: oracle.current_weather_alert(),
+            timers: oracle.timers(),
             cameras: oracle
                 .cameras()
                 .into_iter()
                 .map(|(k, v)| (k, CameraImage::Unresolved(v.entity_picture, None)))
                 .collect(),
+            camera_history: BTreeMap::new(),
+            vacuums: oracle
+                .vacuums()
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        k,
+                        VacuumWidget {
+                            rooms: oracle.vacuum_rooms(k).to_vec(),
+                            vacuum: v,
+                            map: None,
+                        },
+                    )
+                })
+                .collect(),
+            system_monitor: oracle.system_monitor(),
+            routines: oracle.routines().to_vec(),
+            energy_price: oracle.energy_price(),
+            plants: oracle.plants(),
+            bins: oracle.bins(),
+            departures: oracle.departures(),
+            news: Vec::new(),
+            buttons: oracle.buttons(),
+            local_timers: oracle.local_timers(),
+            new_timer_label: String::new(),
+            new_timer_minutes: String::new(),
+            household_notes: oracle.household_notes(),
+            new_note_author: String::new(),
+            new_note_message: String::new(),
+            chores: oracle.chores(),
+            intercom_selected_room: oracle.intercom_rooms().first().map(|room| room.speaker_id),
+            intercom_rooms: oracle.intercom_rooms().to_vec(),
+            intercom_recording: false,
             oracle,
         }
     }
@@ -55,10 +146,111 @@ impl Omni {
     pub fn update(&mut self, event: Message) -> Option<Event> {
         match event {
             Message::OpenRoom(room) => Some(Event::OpenRoom(room)),
+            Message::OpenRoomSummary(room) => Some(Event::OpenRoomSummary(room)),
+            Message::RoomBadgesChanged => None,
+            Message::OpenShoppingList => Some(Event::OpenShoppingList),
+            Message::OpenMaintenance => Some(Event::OpenMaintenance),
+            Message::OpenMap => Some(Event::OpenMap),
+            Message::OpenFloorplan => Some(Event::OpenFloorplan),
+            Message::OpenAlarms => Some(Event::OpenAlarms),
+            Message::OpenRemote => Some(Event::OpenRemote),
+            Message::OpenScheduler => Some(Event::OpenScheduler),
+            Message::OpenCameraDetail(id) => Some(Event::OpenCameraDetail(id)),
+            Message::RunRoutine(index) => Some(Event::RunRoutine(index)),
+            Message::AllLightsOff => Some(Event::AllLightsOff),
+            Message::OpenQuickSettings => Some(Event::OpenQuickSettings),
             Message::UpdateWeather => {
                 self.weather = self.oracle.current_weather();
                 None
             }
+            Message::UpdateWeatherAlert => {
+                self.weather_alert = self.oracle.current_weather_alert();
+                None
+            }
+            Message::UpdateRooms => {
+                // Rooms aren't cached on `Omni` (`view` reads
+                // `self.oracle.rooms()` live); this message exists purely to
+                // trigger a redraw when the room list changes.
+                None
+            }
+            Message::OpenWeatherAlert => self
+                .weather_alert
+                .as_ref()
+                .map(|alert| Event::ShowWeatherAlert(alert.message.clone())),
+            Message::UpdateTimers => {
+                self.timers = self.oracle.timers();
+                None
+            }
+            Message::StartTimer(id) => Some(Event::StartTimer(id)),
+            Message::CancelTimer(id) => Some(Event::CancelTimer(id)),
+            Message::UpdateLocalTimers => {
+                self.local_timers = self.oracle.local_timers();
+                None
+            }
+            Message::NewTimerLabelChanged(v) => {
+                self.new_timer_label = v;
+                None
+            }
+            Message::NewTimerMinutesChanged(v) => {
+                self.new_timer_minutes = v;
+                None
+            }
+            Message::StartLocalTimer => {
+                let minutes: u32 = self.new_timer_minutes.trim().parse().ok()?;
+                let label = std::mem::take(&mut self.new_timer_label);
+                self.new_timer_minutes.clear();
+
+                let label = if label.trim().is_empty() {
+                    "Timer".to_string()
+                } else {
+                    label
+                };
+
+                Some(Event::StartLocalTimer(label, minutes * 60))
+            }
+            Message::CancelLocalTimer(id) => Some(Event::CancelLocalTimer(id)),
+            Message::UpdateHouseholdNotes => {
+                self.household_notes = self.oracle.household_notes();
+                None
+            }
+            Message::NewNoteAuthorChanged(v) => {
+                self.new_note_author = v;
+                None
+            }
+            Message::NewNoteMessageChanged(v) => {
+                self.new_note_message = v;
+                None
+            }
+            Message::AddHouseholdNote => {
+                let author = std::mem::take(&mut self.new_note_author);
+                let message = std::mem::take(&mut self.new_note_message);
+
+                if author.trim().is_empty() || message.trim().is_empty() {
+                    None
+                } else {
+                    Some(Event::AddHouseholdNote(author, message))
+                }
+            }
+            Message::RemoveHouseholdNote(id) => Some(Event::RemoveHouseholdNote(id)),
+            Message::ToggleChoreComplete(index, complete) => {
+                if let Some(chore) = self.chores.get_mut(index) {
+                    chore.complete = complete;
+                }
+                Some(Event::SetChoreComplete(index, complete))
+            }
+            Message::IntercomRoomSelected(speaker_id) => {
+                self.intercom_selected_room = Some(speaker_id);
+                None
+            }
+            Message::IntercomToggleRecording => {
+                self.intercom_recording = !self.intercom_recording;
+
+                if self.intercom_recording {
+                    Some(Event::StartIntercomRecording)
+                } else {
+                    self.intercom_selected_room.map(Event::SendIntercomClip)
+                }
+            }
             Message::UpdateCameras => {
                 self.cameras = self
                     .oracle
@@ -85,13 +277,86 @@ impl Omni {
                 None
             }
             Message::CameraImageDownloaded(id, url, handle) => {
+                let history = self.camera_history.entry(id).or_default();
+                history.push_front(handle.clone());
+                history.truncate(CAMERA_HISTORY_LIMIT);
+
                 self.cameras.insert(id, CameraImage::Resolved(url, handle));
                 None
             }
+            Message::UpdateVacuums => {
+                self.vacuums =
+                    self.oracle
+                        .vacuums()
+                        .into_iter()
+                        .map(|(k, v)| {
+                            let map = self.vacuums.remove(k).and_then(|old| {
+                                (old.vacuum.map == v.map).then_some(old.map).flatten()
+                            });
+
+                            (
+                                k,
+                                VacuumWidget {
+                                    rooms: self.oracle.vacuum_rooms(k).to_vec(),
+                                    vacuum: v,
+                                    map,
+                                },
+                            )
+                        })
+                        .collect();
+                None
+            }
+            Message::VacuumMapDownloaded(id, handle) => {
+                if let Some(vacuum) = self.vacuums.get_mut(id) {
+                    vacuum.map = Some(handle);
+                }
+                None
+            }
+            Message::CleanVacuumSegment(id, segment_id) => {
+                Some(Event::CleanVacuumSegment(id, segment_id))
+            }
+            Message::UpdateSystemMonitor => {
+                self.system_monitor = self.oracle.system_monitor();
+                self.energy_price = self.oracle.energy_price();
+                None
+            }
+            Message::UpdatePlants => {
+                self.plants = self.oracle.plants();
+                None
+            }
+            Message::UpdateBinCollection => {
+                self.bins = self.oracle.bins();
+                None
+            }
+            Message::UpdateDepartures => {
+                self.departures = self.oracle.departures();
+                None
+            }
+            Message::NewsUpdated(headlines) => {
+                self.news = headlines;
+                None
+            }
+            Message::UpdateButtons => {
+                self.buttons = self.oracle.buttons();
+                None
+            }
+            Message::PressButton(id) => Some(Event::PressButton(id)),
         }
     }
 
+    /// A camera's name plus its snapshot history, most recent first, for the
+    /// camera detail context menu. `None` if no snapshot has been downloaded
+    /// for this camera yet.
+    pub fn camera_detail(&self, id: &str) -> Option<(Box<str>, Vec<image::Handle>)> {
+        let name = self.oracle.cameras().remove(id)?.name;
+        let history = self.camera_history.get(id)?.iter().cloned().collect();
+
+        Some((name, history))
+    }
+
     pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let day_time = matches!(OffsetDateTime::now_utc().hour(), 5..=19);
+
         let greeting = match OffsetDateTime::now_utc().hour() {
             5..=11 => "Good morning!",
             12..=16 => "Good afternoon!",
@@ -99,28 +364,137 @@ impl Omni {
             _ => "Hello!",
         };
 
-        let greeting = text(greeting).size(60).font(Font {
-            weight: Weight::Bold,
-            stretch: Stretch::Condensed,
-            ..Font::with_name("Helvetica Neue")
+        let greeting = text(greeting)
+            .size(theme::scaled(theme::font_size(FontSize::Header)))
+            .font(Font {
+                weight: Weight::Bold,
+                stretch: Stretch::Condensed,
+                ..Font::with_name("Helvetica Neue")
+            });
+
+        let greeting = if self.oracle.backlight_entity().is_some() {
+            Element::from(
+                row![
+                    greeting,
+                    horizontal_space(Length::Fill),
+                    mouse_area(svg(Icon::Hamburger).width(24).height(24))
+                        .on_press(Message::OpenQuickSettings),
+                ]
+                .align_items(Alignment::Center),
+            )
+        } else {
+            Element::from(greeting)
+        };
+
+        let alert_banner = self.weather_alert.as_ref().map(|alert| {
+            mouse_area(
+                container(row![text(alert.message.as_ref()).size(18)].padding(15))
+                    .width(iced::Length::Fill)
+                    .style(iced::theme::Container::Custom(Box::new(AlertBannerStyle))),
+            )
+            .on_press(Message::OpenWeatherAlert)
         });
 
-        let room = |id, room, image| {
-            image_card::image_card(image, room).on_press(Message::OpenRoom(id))
+        let badge = |content: String| {
+            container(
+                text(content)
+                    .size(12)
+                    .style(Text::Color(iced::Color::WHITE)),
+            )
+            .padding([2, 8])
+            .style(iced::theme::Container::Custom(Box::new(RoomBadgeStyle)))
+        };
+
+        let room = |id, room: &Room, image| {
             // .height(Length::Fixed(128.0))
             // .width(Length::FillPortion(1))
+            let mut card = Element::from(
+                image_card::image_card(image, room.name.as_ref())
+                    .on_press(Message::OpenRoom(id))
+                    .on_long_press(Message::OpenRoomSummary(id)),
+            );
+
+            let lights_on = room
+                .lights(&self.oracle)
+                .into_values()
+                .filter(|light| light.on == Some(true))
+                .count();
+
+            if lights_on > 0 {
+                card = FloatingElement::new(card, badge(format!("💡 {lights_on}")))
+                    .anchor(Anchor::NorthEast)
+                    .offset([8.0, 8.0])
+                    .into();
+            }
+
+            if let Some(true) = room.is_occupied(&self.oracle) {
+                let occupied = container(text("●").size(12).style(Text::Color(iced::Color::WHITE)))
+                    .padding([2, 8])
+                    .style(iced::theme::Container::Custom(Box::new(
+                        OccupancyBadgeStyle,
+                    )));
+
+                card = FloatingElement::new(card, occupied)
+                    .anchor(Anchor::NorthWest)
+                    .offset([8.0, 8.0])
+                    .into();
+            }
+
+            let temperature = room
+                .thermostats(&self.oracle)
+                .into_values()
+                .find_map(|thermostat| thermostat.current_temperature);
+
+            if let Some(temperature) = temperature {
+                card = FloatingElement::new(card, badge(format!("{temperature}°")))
+                    .anchor(Anchor::SouthEast)
+                    .offset([8.0, 8.0])
+                    .into();
+            }
+
+            // A static speaker icon stands in for a proper animated
+            // equalizer bar, which iced 0.12's widget tree doesn't have a
+            // convenient per-frame-tick primitive for outside of the
+            // `keyframe`-driven overlays; revisit once one exists.
+            let now_playing = room.speaker(&self.oracle).and_then(|(_, speaker)| {
+                speaker
+                    .state
+                    .is_playing()
+                    .then_some(speaker.media_title)
+                    .flatten()
+            });
+
+            if let Some(title) = now_playing {
+                let now_playing = row![
+                    svg(Icon::SpeakerFull).width(12).height(12),
+                    badge(title.to_string())
+                ]
+                .spacing(4)
+                .align_items(Alignment::Center);
+
+                card = FloatingElement::new(card, now_playing)
+                    .anchor(Anchor::South)
+                    .offset([0.0, 32.0])
+                    .into();
+            }
+
+            card
         };
 
         let cameras = self
             .cameras
-            .values()
-            .map(|v| match v {
-                CameraImage::Unresolved(_, Some(handle)) | CameraImage::Resolved(_, handle) => {
-                    Element::from(image(handle.clone()).width(512.).height(288.))
-                }
-                CameraImage::Unresolved(..) => {
-                    Element::from(container(vertical_space(0)).width(512.).height(288.))
-                }
+            .iter()
+            .map(|(id, v)| {
+                let image = match v {
+                    CameraImage::Unresolved(_, Some(handle)) | CameraImage::Resolved(_, handle) => {
+                        Element::from(image(handle.clone()).width(512.).height(288.))
+                    }
+                    CameraImage::Unresolved(..) => {
+                        Element::from(container(vertical_space(0)).width(512.).height(288.))
+                    }
+                };
+
+                Element::from(mouse_area(image).on_press(Message::OpenCameraDetail(*id)))
             })
             .chunks(2)
             .into_iter()
@@ -130,22 +504,595 @@ impl Omni {
         let rooms = self
             .oracle
             .rooms()
-            .map(|(id, r)| room(id, r.name.as_ref(), determine_image(&r.name)))
+            .into_iter()
+            .map(|(id, r)| room(id, &r, determine_image(&r.name)))
             .chunks(2)
             .into_iter()
             .map(|children| children.into_iter().fold(Row::new().spacing(10), Row::push))
             .fold(Column::new().spacing(10), Column::push);
 
-        scrollable(
+        let timers = if self.timers.is_empty() {
+            None
+        } else {
+            let rows = self
+                .timers
+                .iter()
+                .fold(Column::new().spacing(10), |col, (id, timer)| {
+                    let remaining = timer
+                        .remaining()
+                        .map(|d| format!("{:02}:{:02}", d.as_secs() / 60, d.as_secs() % 60))
+                        .unwrap_or_else(|| "--:--".to_string());
+
+                    col.push(
+                        row![
+                            text(timer.friendly_name.as_ref()).size(20),
+                            text(remaining).size(20),
+                            mouse_area(text("Cancel").size(16)).on_press(Message::CancelTimer(*id)),
+                        ]
+                        .spacing(20),
+                    )
+                });
+
+            let quick_start =
+                self.timers.keys().next().map(|id| {
+                    mouse_area(text("+5 min").size(16)).on_press(Message::StartTimer(*id))
+                });
+
+            let mut header = row![text("Timers").size(24)].spacing(20);
+            if let Some(quick_start) = quick_start {
+                header = header.push(quick_start);
+            }
+
+            Some(column![header, rows].spacing(10).padding(20))
+        };
+
+        let local_timers = {
+            let rows = self
+                .local_timers
+                .iter()
+                .fold(Column::new().spacing(10), |col, timer| {
+                    let remaining = timer.remaining();
+
+                    col.push(
+                        row![
+                            text(timer.label.as_ref()).size(24),
+                            text(format!(
+                                "{:02}:{:02}",
+                                remaining.as_secs() / 60,
+                                remaining.as_secs() % 60
+                            ))
+                            .size(40),
+                            mouse_area(text("Cancel").size(16))
+                                .on_press(Message::CancelLocalTimer(timer.id)),
+                        ]
+                        .spacing(20)
+                        .align_items(Alignment::Center),
+                    )
+                });
+
+            let add_row = row![
+                text_input("Label", &self.new_timer_label)
+                    .on_input(Message::NewTimerLabelChanged)
+                    .width(Length::Fixed(160.0)),
+                text_input("Minutes", &self.new_timer_minutes)
+                    .on_input(Message::NewTimerMinutesChanged)
+                    .on_submit(Message::StartLocalTimer)
+                    .width(Length::Fixed(80.0)),
+                mouse_area(text("Start Timer").size(16)).on_press(Message::StartLocalTimer),
+            ]
+            .spacing(20)
+            .align_items(Alignment::Center);
+
             column![
-                greeting,
-                crate::widgets::cards::weather::WeatherCard::new(self.weather),
-                rooms,
-                cameras,
+                row![text("Kitchen Timers").size(24)].spacing(20),
+                rows,
+                add_row
+            ]
+            .spacing(10)
+            .padding(20)
+        };
+
+        let household_notes = {
+            let rows = self
+                .household_notes
+                .iter()
+                .fold(Column::new().spacing(10), |col, note| {
+                    col.push(
+                        row![
+                            text(note.author.as_ref())
+                                .size(16)
+                                .style(Text::Color(GREEN_500)),
+                            text(note.message.as_ref()).size(16),
+                            mouse_area(text("Remove").size(14))
+                                .on_press(Message::RemoveHouseholdNote(note.id)),
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                    )
+                });
+
+            let add_row = row![
+                text_input("Name", &self.new_note_author)
+                    .on_input(Message::NewNoteAuthorChanged)
+                    .width(Length::Fixed(120.0)),
+                text_input("Message", &self.new_note_message)
+                    .on_input(Message::NewNoteMessageChanged)
+                    .on_submit(Message::AddHouseholdNote)
+                    .width(Length::Fill),
+                mouse_area(text("Post").size(16)).on_press(Message::AddHouseholdNote),
             ]
             .spacing(20)
-            .padding(40),
+            .align_items(Alignment::Center);
+
+            column![row![text("Notes").size(24)].spacing(20), rows, add_row]
+                .spacing(10)
+                .padding(20)
+        };
+
+        let chores = (!self.chores.is_empty()).then(|| {
+            let rows = self.chores.iter().enumerate().fold(
+                Column::new().spacing(10),
+                |col, (index, chore)| {
+                    let label = chore.due_date.as_ref().map_or_else(
+                        || format!("{} ({})", chore.name, chore.assignee),
+                        |due_date| format!("{} ({}, due {due_date})", chore.name, chore.assignee),
+                    );
+
+                    col.push(checkbox(label, chore.complete, move |v| {
+                        Message::ToggleChoreComplete(index, v)
+                    }))
+                },
+            );
+
+            column![text("Chores").size(24), rows]
+                .spacing(10)
+                .padding(20)
+        });
+
+        let intercom = (!self.intercom_rooms.is_empty()).then(|| {
+            let room_buttons = self
+                .intercom_rooms
+                .iter()
+                .fold(row![].spacing(10), |row, room| {
+                    row.push(
+                        mouse_area(container(text(room.name.as_ref()).size(16)).padding(10))
+                            .on_press(Message::IntercomRoomSelected(room.speaker_id)),
+                    )
+                });
+
+            let selected_room_name = self
+                .intercom_selected_room
+                .and_then(|speaker_id| {
+                    self.intercom_rooms
+                        .iter()
+                        .find(|room| room.speaker_id == speaker_id)
+                })
+                .map(|room| room.name.as_ref());
+
+            let record_label = match (self.intercom_recording, selected_room_name) {
+                (true, Some(name)) => format!("Send to {name}"),
+                (true, None) => "Send".to_string(),
+                (false, Some(name)) => format!("Record for {name}"),
+                (false, None) => "Record".to_string(),
+            };
+
+            let record_button = mouse_area(container(text(record_label).size(20)).padding(15))
+                .on_press(Message::IntercomToggleRecording);
+
+            column![text("Intercom").size(24), room_buttons, record_button]
+                .spacing(10)
+                .padding(20)
+        });
+
+        let system_monitor = if self.system_monitor.is_empty() {
+            None
+        } else {
+            let stat =
+                |label: &'static str, sensor: &Option<Sensor>, warn_at: f64, crit_at: f64| {
+                    sensor.as_ref().map(|sensor| {
+                        let value_text = format!(
+                            "{}{}",
+                            sensor.state.as_ref(),
+                            sensor.unit_of_measurement.as_deref().unwrap_or("")
+                        );
+
+                        let colour = match sensor.value() {
+                            Some(value) if value >= crit_at => RED_500,
+                            Some(value) if value >= warn_at => ORANGE,
+                            _ => GREEN_500,
+                        };
+
+                        row![
+                            text(label).size(16),
+                            text(value_text).size(16).style(Text::Color(colour)),
+                        ]
+                        .spacing(10)
+                    })
+                };
+
+            let rows = [
+                stat("CPU", &self.system_monitor.cpu, 70.0, 90.0),
+                stat("RAM", &self.system_monitor.memory, 80.0, 95.0),
+                stat("Disk", &self.system_monitor.disk, 80.0, 95.0),
+                stat("Temp", &self.system_monitor.temperature, 65.0, 80.0),
+            ]
+            .into_iter()
+            .flatten()
+            .fold(Column::new().spacing(10), Column::push);
+
+            Some(
+                column![text("System").size(24), rows]
+                    .spacing(10)
+                    .padding(20),
+            )
+        };
+
+        let energy_price = self.energy_price.as_ref().map(|price| {
+            let current_text = format!("{:.2}{}", price.current, price.unit);
+
+            #[allow(clippy::cast_precision_loss)]
+            let average = if price.hourly.is_empty() {
+                price.current
+            } else {
+                price.hourly.iter().sum::<f64>() / price.hourly.len() as f64
+            };
+
+            let curve = price
+                .hourly
+                .iter()
+                .fold(Row::new().spacing(2), |row, rate| {
+                    let colour = if *rate <= average * 0.9 {
+                        GREEN_500
+                    } else if *rate >= average * 1.1 {
+                        RED_500
+                    } else {
+                        ORANGE
+                    };
+
+                    #[allow(clippy::cast_possible_truncation)]
+                    let height = if average > 0.0 {
+                        (rate / average * 30.0).clamp(4.0, 60.0)
+                    } else {
+                        4.0
+                    } as f32;
+
+                    row.push(container(vertical_space(0)).width(8).height(height).style(
+                        move |_theme: &Theme| container::Appearance {
+                            background: Some(Background::Color(colour)),
+                            ..container::Appearance::default()
+                        },
+                    ))
+                });
+
+            column![
+                row![
+                    text("Electricity Price").size(24),
+                    text(current_text).size(20),
+                ]
+                .spacing(20)
+                .align_items(Alignment::Center),
+                curve.align_items(Alignment::End),
+            ]
+            .spacing(10)
+            .padding(20)
+        });
+
+        let plants = (!self.plants.is_empty()).then(|| {
+            let rows = self
+                .plants
+                .values()
+                .fold(Column::new().spacing(10), |col, plant| {
+                    let moisture_text = plant
+                        .moisture
+                        .map_or_else(|| "?".to_string(), |moisture| format!("{moisture}%"));
+
+                    let colour = if plant.needs_water() {
+                        RED_500
+                    } else {
+                        GREEN_500
+                    };
+
+                    col.push(
+                        row![
+                            text(plant.friendly_name.as_ref()).size(16),
+                            text(moisture_text).size(16).style(Text::Color(colour)),
+                            text(if plant.needs_water() {
+                                "Needs water"
+                            } else {
+                                ""
+                            })
+                            .size(16),
+                        ]
+                        .spacing(10),
+                    )
+                });
+
+            column![text("Plants").size(24), rows]
+                .spacing(10)
+                .padding(20)
+        });
+
+        let bin_collection = (!self.bins.is_empty()).then(|| {
+            let rows = self
+                .bins
+                .values()
+                .fold(Column::new().spacing(10), |col, bin| {
+                    let colour = if bin.is_tomorrow() { ORANGE } else { GREEN_500 };
+
+                    col.push(
+                        row![
+                            text(bin.bin_type.as_ref()).size(16),
+                            text(bin.next_collection.as_ref())
+                                .size(16)
+                                .style(Text::Color(colour)),
+                            text(if bin.is_tomorrow() { "Tomorrow" } else { "" }).size(16),
+                        ]
+                        .spacing(10),
+                    )
+                });
+
+            column![text("Bin Collection").size(24), rows]
+                .spacing(10)
+                .padding(20)
+        });
+
+        let departures = (!self.departures.is_empty()).then(|| {
+            let rows =
+                self.departures
+                    .values()
+                    .fold(Column::new().spacing(10), |col, departure| {
+                        let colour = if departure.is_departing_soon() {
+                            ORANGE
+                        } else {
+                            GREEN_500
+                        };
+
+                        col.push(
+                            row![
+                                text(departure.line.as_ref()).size(16),
+                                text(departure.departure.as_ref())
+                                    .size(16)
+                                    .style(Text::Color(colour)),
+                                text(if departure.is_departing_soon() {
+                                    "Leave now"
+                                } else {
+                                    ""
+                                })
+                                .size(16),
+                            ]
+                            .spacing(10),
+                        )
+                    });
+
+            column![text("Departures").size(24), rows]
+                .spacing(10)
+                .padding(20)
+        });
+
+        let news = (!self.news.is_empty()).then(|| {
+            let rows = self
+                .news
+                .iter()
+                .fold(Column::new().spacing(10), |col, headline| {
+                    col.push(
+                        row![
+                            text(headline.source.as_ref()).size(14),
+                            text(headline.title.as_ref()).size(16),
+                        ]
+                        .spacing(10),
+                    )
+                });
+
+            column![text("News").size(24), rows].spacing(10).padding(20)
+        });
+
+        let vacuums = self
+            .vacuums
+            .iter()
+            .fold(Column::new().spacing(10), |col, (id, v)| {
+                let map: Element<'_, Message, Renderer> = match &v.map {
+                    Some(handle) => Element::from(image(handle.clone()).width(512.).height(288.)),
+                    None => Element::from(container(vertical_space(0)).width(512.).height(288.)),
+                };
+
+                let status = row![
+                    text(v.vacuum.name.as_ref()).size(20),
+                    text(v.vacuum.status.as_ref()).size(16),
+                    text(
+                        v.vacuum
+                            .battery_level
+                            .map_or_else(String::new, |level| format!("{level}%"))
+                    )
+                    .size(16),
+                ]
+                .spacing(20);
+
+                let rooms = v.rooms.iter().fold(Row::new().spacing(10), |row, room| {
+                    row.push(
+                        mouse_area(text(room.name.as_ref()).size(16))
+                            .on_press(Message::CleanVacuumSegment(*id, room.segment_id)),
+                    )
+                });
+
+                col.push(column![status, map, rooms].spacing(10).padding(20))
+            });
+
+        let shopping_list_link = mouse_area(
+            container(text("Shopping List").size(24))
+                .padding(20)
+                .width(iced::Length::Fill),
+        )
+        .on_press(Message::OpenShoppingList);
+
+        let maintenance_link = mouse_area(
+            container(text("Firmware Updates").size(24))
+                .padding(20)
+                .width(iced::Length::Fill),
+        )
+        .on_press(Message::OpenMaintenance);
+
+        let all_lights_off_link = mouse_area(
+            container(text("All Off").size(24))
+                .padding(20)
+                .width(iced::Length::Fill),
+        )
+        .on_press(Message::AllLightsOff);
+
+        let map_link = self.oracle.map_centre().is_some().then(|| {
+            mouse_area(
+                container(text("Map").size(24))
+                    .padding(20)
+                    .width(iced::Length::Fill),
+            )
+            .on_press(Message::OpenMap)
+        });
+
+        let floorplan_link = self.oracle.floorplan().is_some().then(|| {
+            mouse_area(
+                container(text("Floorplan").size(24))
+                    .padding(20)
+                    .width(iced::Length::Fill),
+            )
+            .on_press(Message::OpenFloorplan)
+        });
+
+        let alarms_link = (!self.oracle.alarms().is_empty()).then(|| {
+            mouse_area(
+                container(text("Alarms").size(24))
+                    .padding(20)
+                    .width(iced::Length::Fill),
+            )
+            .on_press(Message::OpenAlarms)
+        });
+
+        let remote_link = (!self.oracle.remotes().is_empty()).then(|| {
+            mouse_area(
+                container(text("Remotes").size(24))
+                    .padding(20)
+                    .width(iced::Length::Fill),
+            )
+            .on_press(Message::OpenRemote)
+        });
+
+        let scheduler_link = mouse_area(
+            container(text("Scheduler").size(24))
+                .padding(20)
+                .width(iced::Length::Fill),
         )
+        .on_press(Message::OpenScheduler);
+
+        let routines = (!self.routines.is_empty()).then(|| {
+            self.routines
+                .iter()
+                .enumerate()
+                .fold(row![].spacing(20), |row, (index, routine)| {
+                    row.push(
+                        mouse_area(container(text(routine.name.as_ref()).size(20)).padding(15))
+                            .on_press(Message::RunRoutine(index)),
+                    )
+                })
+        });
+
+        let buttons = (!self.buttons.is_empty()).then(|| {
+            self.buttons
+                .iter()
+                .fold(row![].spacing(20), |row, (id, button)| {
+                    row.push(
+                        mouse_area(
+                            container(text(button.friendly_name.as_ref()).size(20)).padding(15),
+                        )
+                        .on_press(Message::PressButton(*id)),
+                    )
+                })
+        });
+
+        let mut content = column![greeting].spacing(20).padding(40);
+
+        if let Some(alert_banner) = alert_banner {
+            content = content.push(alert_banner);
+        }
+
+        if let Some(routines) = routines {
+            content = content.push(routines);
+        }
+
+        if let Some(buttons) = buttons {
+            content = content.push(buttons);
+        }
+
+        if let Some(timers) = timers {
+            content = content.push(timers);
+        }
+
+        content = content.push(local_timers);
+        content = content.push(household_notes);
+
+        if let Some(chores) = chores {
+            content = content.push(chores);
+        }
+
+        if let Some(intercom) = intercom {
+            content = content.push(intercom);
+        }
+
+        if let Some(system_monitor) = system_monitor {
+            content = content.push(system_monitor);
+        }
+
+        if let Some(energy_price) = energy_price {
+            content = content.push(energy_price);
+        }
+
+        if let Some(plants) = plants {
+            content = content.push(plants);
+        }
+
+        if let Some(bin_collection) = bin_collection {
+            content = content.push(bin_collection);
+        }
+
+        if let Some(departures) = departures {
+            content = content.push(departures);
+        }
+
+        if let Some(news) = news {
+            content = content.push(news);
+        }
+
+        content = content
+            .push(crate::widgets::cards::weather::WeatherCard::new(
+                self.weather,
+            ))
+            .push(rooms)
+            .push(shopping_list_link)
+            .push(maintenance_link)
+            .push(all_lights_off_link)
+            .push(scheduler_link);
+
+        if let Some(map_link) = map_link {
+            content = content.push(map_link);
+        }
+
+        if let Some(floorplan_link) = floorplan_link {
+            content = content.push(floorplan_link);
+        }
+
+        if let Some(alarms_link) = alarms_link {
+            content = content.push(alarms_link);
+        }
+
+        if let Some(remote_link) = remote_link {
+            content = content.push(remote_link);
+        }
+
+        let background = theme::weather_background(self.weather.condition, day_time);
+
+        image_background(
+            background,
+            scrollable(content.push(cameras).push(vacuums)).into(),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
         .into()
     }
 
@@ -167,6 +1114,97 @@ impl Omni {
                 .map(|()| Message::UpdateCameras),
         );
 
+        pub struct WeatherAlertSubscription;
+        let weather_alert_subscription = subscription::run_with_id(
+            TypeId::of::<WeatherAlertSubscription>(),
+            self.oracle
+                .subscribe_weather_alert()
+                .map(|()| Message::UpdateWeatherAlert),
+        );
+
+        pub struct RoomsSubscription;
+        let rooms_subscription = subscription::run_with_id(
+            TypeId::of::<RoomsSubscription>(),
+            self.oracle.subscribe_rooms().map(|()| Message::UpdateRooms),
+        );
+
+        pub struct TimersSubscription;
+        let timers_subscription = subscription::run_with_id(
+            TypeId::of::<TimersSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("timer.")
+                .map(|()| Message::UpdateTimers),
+        );
+
+        pub struct SystemMonitorSubscription;
+        let system_monitor_subscription = subscription::run_with_id(
+            TypeId::of::<SystemMonitorSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("sensor.")
+                .map(|()| Message::UpdateSystemMonitor),
+        );
+
+        pub struct PlantSensorSubscription;
+        pub struct PlantEntitySubscription;
+        let plants_subscription = Subscription::batch([
+            subscription::run_with_id(
+                TypeId::of::<PlantSensorSubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("sensor.")
+                    .map(|()| Message::UpdatePlants),
+            ),
+            subscription::run_with_id(
+                TypeId::of::<PlantEntitySubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("plant.")
+                    .map(|()| Message::UpdatePlants),
+            ),
+        ]);
+
+        pub struct BinCollectionSubscription;
+        let bin_collection_subscription = subscription::run_with_id(
+            TypeId::of::<BinCollectionSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("sensor.")
+                .map(|()| Message::UpdateBinCollection),
+        );
+
+        pub struct ButtonsSubscription;
+        let buttons_subscription = subscription::run_with_id(
+            TypeId::of::<ButtonsSubscription>(),
+            self.oracle
+                .subscribe_id_prefix("button.")
+                .map(|()| Message::UpdateButtons),
+        );
+
+        pub struct LocalTimersSubscription;
+        let local_timers_subscription = subscription::run_with_id(
+            TypeId::of::<LocalTimersSubscription>(),
+            self.oracle
+                .subscribe_local_timers()
+                .map(|()| Message::UpdateLocalTimers),
+        );
+
+        pub struct HouseholdNotesSubscription;
+        let household_notes_subscription = subscription::run_with_id(
+            TypeId::of::<HouseholdNotesSubscription>(),
+            self.oracle
+                .subscribe_household_notes()
+                .map(|()| Message::UpdateHouseholdNotes),
+        );
+
+        pub struct DeparturesSubscription;
+        let departures_subscription = subscription::run_with_id(
+            TypeId::of::<DeparturesSubscription>(),
+            iced::time::every(Duration::from_secs(60)).map(|_| Message::UpdateDepartures),
+        );
+
+        let news_subscription = subscriptions::poll_news_feeds(
+            self.oracle.news_feeds(),
+            self.oracle.news_refresh_interval(),
+            Message::NewsUpdated,
+        );
+
         let camera_image_downloads =
             Subscription::batch(self.cameras.iter().filter_map(|(k, v)| {
                 if let CameraImage::Unresolved(url, _) = v {
@@ -181,14 +1219,127 @@ impl Omni {
                 }
             }));
 
+        pub struct RoomLightBadgesSubscription;
+        pub struct RoomOccupancyBadgesSubscription;
+        pub struct RoomThermostatBadgesSubscription;
+        pub struct RoomNowPlayingBadgesSubscription;
+        let room_badges_subscription = Subscription::batch([
+            subscription::run_with_id(
+                TypeId::of::<RoomLightBadgesSubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("light.")
+                    .map(|()| Message::RoomBadgesChanged),
+            ),
+            subscription::run_with_id(
+                TypeId::of::<RoomOccupancyBadgesSubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("binary_sensor.")
+                    .map(|()| Message::RoomBadgesChanged),
+            ),
+            subscription::run_with_id(
+                TypeId::of::<RoomThermostatBadgesSubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("climate.")
+                    .map(|()| Message::RoomBadgesChanged),
+            ),
+            subscription::run_with_id(
+                TypeId::of::<RoomNowPlayingBadgesSubscription>(),
+                self.oracle
+                    .subscribe_id_prefix("media_player.")
+                    .map(|()| Message::RoomBadgesChanged),
+            ),
+        ]);
+
+        pub struct VacuumSubscription;
+        let vacuum_subscription = subscription::run_with_id(
+            TypeId::of::<VacuumSubscription>(),
+            self.oracle
+                .subscribe_all_vacuums()
+                .map(|()| Message::UpdateVacuums),
+        );
+
+        let vacuum_map_downloads = Subscription::batch(self.vacuums.iter().filter_map(|(k, v)| {
+            if v.map.is_none() {
+                let k = *k;
+                let url = v.vacuum.map.clone()?;
+
+                Some(download_image(url, identity, move |handle| {
+                    Message::VacuumMapDownloaded(k, handle)
+                }))
+            } else {
+                None
+            }
+        }));
+
         Subscription::batch([
             weather_subscription,
+            weather_alert_subscription,
+            rooms_subscription,
+            timers_subscription,
+            system_monitor_subscription,
             camera_subscription,
             camera_image_downloads,
+            vacuum_subscription,
+            vacuum_map_downloads,
+            plants_subscription,
+            bin_collection_subscription,
+            departures_subscription,
+            news_subscription,
+            buttons_subscription,
+            local_timers_subscription,
+            household_notes_subscription,
+            room_badges_subscription,
         ])
     }
 }
 
+#[derive(Debug, Default)]
+struct AlertBannerStyle;
+
+impl container::StyleSheet for AlertBannerStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(ORANGE.into()),
+            text_color: Some(iced::Color::WHITE),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+/// Pill background for the lights-on/temperature/now-playing badges floated
+/// over a room card on the omni page.
+struct RoomBadgeStyle;
+
+impl container::StyleSheet for RoomBadgeStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(SYSTEM_GRAY6.into()),
+            border_radius: 10.0.into(),
+            ..container::Appearance::default()
+        }
+    }
+}
+
+/// Green pill for the occupancy badge, so a room with motion detected stands
+/// out from the neutral lights/temperature badges.
+struct OccupancyBadgeStyle;
+
+impl container::StyleSheet for OccupancyBadgeStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(GREEN_500.into()),
+            border_radius: 10.0.into(),
+            ..container::Appearance::default()
+        }
+    }
+}
+
 fn determine_image(name: &str) -> Image {
     match name {
         "Kitchen" => Image::Kitchen,
@@ -205,12 +1356,78 @@ pub struct State {}
 #[derive(Clone, Debug)]
 pub enum Event {
     OpenRoom(&'static str),
+    OpenRoomSummary(&'static str),
+    OpenShoppingList,
+    OpenMaintenance,
+    OpenMap,
+    OpenFloorplan,
+    OpenAlarms,
+    OpenRemote,
+    OpenScheduler,
+    OpenQuickSettings,
+    ShowWeatherAlert(Box<str>),
+    StartTimer(&'static str),
+    CancelTimer(&'static str),
+    CleanVacuumSegment(&'static str, u32),
+    OpenCameraDetail(&'static str),
+    RunRoutine(usize),
+    AllLightsOff,
+    PressButton(&'static str),
+    StartLocalTimer(String, u32),
+    CancelLocalTimer(u64),
+    AddHouseholdNote(String, String),
+    RemoveHouseholdNote(u64),
+    SetChoreComplete(usize, bool),
+    StartIntercomRecording,
+    SendIntercomClip(&'static str),
 }
 
 #[derive(Clone, Debug)]
 pub enum Message {
     OpenRoom(&'static str),
+    OpenRoomSummary(&'static str),
+    RoomBadgesChanged,
+    OpenShoppingList,
+    OpenMaintenance,
+    OpenMap,
+    OpenFloorplan,
+    OpenAlarms,
+    OpenRemote,
+    OpenScheduler,
+    OpenQuickSettings,
     UpdateWeather,
+    UpdateWeatherAlert,
+    UpdateRooms,
+    OpenWeatherAlert,
+    UpdateTimers,
+    StartTimer(&'static str),
+    CancelTimer(&'static str),
     UpdateCameras,
     CameraImageDownloaded(&'static str, Url, iced::widget::image::Handle),
+    UpdateVacuums,
+    VacuumMapDownloaded(&'static str, iced::widget::image::Handle),
+    CleanVacuumSegment(&'static str, u32),
+    UpdateSystemMonitor,
+    OpenCameraDetail(&'static str),
+    RunRoutine(usize),
+    AllLightsOff,
+    UpdatePlants,
+    UpdateBinCollection,
+    UpdateDepartures,
+    NewsUpdated(Vec<NewsHeadline>),
+    UpdateButtons,
+    PressButton(&'static str),
+    UpdateLocalTimers,
+    NewTimerLabelChanged(String),
+    NewTimerMinutesChanged(String),
+    StartLocalTimer,
+    CancelLocalTimer(u64),
+    UpdateHouseholdNotes,
+    NewNoteAuthorChanged(String),
+    NewNoteMessageChanged(String),
+    AddHouseholdNote,
+    RemoveHouseholdNote(u64),
+    ToggleChoreComplete(usize, bool),
+    IntercomRoomSelected(&'static str),
+    IntercomToggleRecording,
 }
@@ -0,0 +1,180 @@
+use std::{any::TypeId, collections::BTreeMap, sync::Arc};
+
+use iced::{
+    subscription,
+    widget::{button, column, container, mouse_area, row, scrollable, text, text_input},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::oracle::{Oracle, Remote};
+
+/// Universal remotes (Harmony hubs, Broadlink blasters): start/stop
+/// activities, mash a D-pad, or send a raw typed command. `remote.*`
+/// entities aren't associated with a room, so unlike speakers this is a
+/// single page listing all of them rather than something nested under
+/// `pages::room`.
+#[derive(Debug)]
+pub struct Remotes {
+    oracle: Arc<Oracle>,
+    remotes: Vec<(&'static str, Remote)>,
+    custom_commands: BTreeMap<&'static str, String>,
+}
+
+impl Remotes {
+    pub fn new(oracle: Arc<Oracle>) -> Self {
+        Self {
+            remotes: oracle.remotes().into_iter().collect(),
+            custom_commands: BTreeMap::new(),
+            oracle,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps, clippy::needless_pass_by_value)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::RemotesChanged => {
+                self.remotes = self.oracle.remotes().into_iter().collect();
+                None
+            }
+            Message::StartActivity(id, activity) => Some(Event::StartActivity(id, activity)),
+            Message::StopActivity(id) => Some(Event::StopActivity(id)),
+            Message::SendCommand(id, command) => Some(Event::SendCommand(id, command)),
+            Message::CustomCommandChanged(id, value) => {
+                self.custom_commands.insert(id, value);
+                None
+            }
+            Message::SendCustomCommand(id) => {
+                let command = self.custom_commands.entry(id).or_default();
+                if command.trim().is_empty() {
+                    None
+                } else {
+                    Some(Event::SendCommand(id, std::mem::take(command)))
+                }
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Remotes").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let dpad_button = |label: &'static str, id: &'static str, command: &'static str| {
+            mouse_area(
+                container(text(label).size(20))
+                    .width(Length::Fixed(70.0))
+                    .height(Length::Fixed(70.0))
+                    .align_x(iced::alignment::Horizontal::Center)
+                    .align_y(iced::alignment::Vertical::Center),
+            )
+            .on_press(Message::SendCommand(id, command.to_string()))
+        };
+
+        let remotes = self
+            .remotes
+            .iter()
+            .fold(column![].spacing(20), |col, (id, remote)| {
+                let mut activity_buttons = row![].spacing(10);
+                for activity in &remote.activities {
+                    let pressed = remote.current_activity.as_deref() == Some(activity);
+                    let label = if pressed {
+                        format!("{activity} (active)")
+                    } else {
+                        activity.to_string()
+                    };
+                    activity_buttons = activity_buttons.push(
+                        button(text(label))
+                            .on_press(Message::StartActivity(id, Some(activity.to_string()))),
+                    );
+                }
+                if remote.activities.is_empty() {
+                    activity_buttons = activity_buttons
+                        .push(button("Turn On").on_press(Message::StartActivity(id, None)));
+                }
+                activity_buttons =
+                    activity_buttons.push(button("Stop").on_press(Message::StopActivity(id)));
+
+                let dpad = column![
+                    row![dpad_button("▲", id, "DIRECTION_UP")].spacing(10),
+                    row![
+                        dpad_button("◀", id, "DIRECTION_LEFT"),
+                        dpad_button("OK", id, "DPAD_MIDDLE"),
+                        dpad_button("▶", id, "DIRECTION_RIGHT"),
+                    ]
+                    .spacing(10),
+                    row![dpad_button("▼", id, "DIRECTION_DOWN")].spacing(10),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center);
+
+                let custom_command = self.custom_commands.get(id).cloned().unwrap_or_default();
+
+                let send_command_row = row![
+                    text_input("Send a custom command...", &custom_command)
+                        .on_input(|v| Message::CustomCommandChanged(id, v))
+                        .on_submit(Message::SendCustomCommand(id))
+                        .width(Length::Fill),
+                    button("Send").on_press(Message::SendCustomCommand(id)),
+                ]
+                .spacing(10);
+
+                col.push(
+                    container(
+                        column![
+                            text(remote.friendly_name.as_ref()).size(24),
+                            activity_buttons,
+                            dpad,
+                            send_command_row,
+                        ]
+                        .spacing(15)
+                        .align_items(Alignment::Center),
+                    )
+                    .padding(20)
+                    .width(Length::Fill),
+                )
+            });
+
+        container(
+            column![header, scrollable(remotes).height(Length::Fill)]
+                .spacing(20)
+                .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        pub struct RemotesSubscription;
+
+        subscription::run_with_id(
+            TypeId::of::<RemotesSubscription>(),
+            iced::futures::StreamExt::map(self.oracle.subscribe_all_remotes(), |()| {
+                Message::RemotesChanged
+            }),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    StartActivity(&'static str, Option<String>),
+    StopActivity(&'static str),
+    SendCommand(&'static str, String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    RemotesChanged,
+    StartActivity(&'static str, Option<String>),
+    StopActivity(&'static str),
+    SendCommand(&'static str, String),
+    CustomCommandChanged(&'static str, String),
+    SendCustomCommand(&'static str),
+}
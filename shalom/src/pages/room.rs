@@ -1,3 +1,5 @@
+pub mod climate;
+pub mod covers;
 pub mod lights;
 pub mod listen;
 
@@ -8,11 +10,11 @@ use iced::{
     font::{Stretch, Weight},
     theme,
     widget::{
-        container, row, scrollable,
+        container, mouse_area, row, scrollable,
         scrollable::{Direction, Properties, Viewport},
         text, Column,
     },
-    Color, Font, Length, Renderer, Subscription, Theme,
+    Alignment, Color, Font, Length, Renderer, Subscription, Theme,
 };
 
 use crate::{
@@ -32,7 +34,10 @@ const SPACE_TOP: u16 = 51;
 pub struct Room {
     id: &'static str,
     room: crate::oracle::Room,
+    oracle: Arc<Oracle>,
     lights: lights::Lights,
+    covers: covers::Covers,
+    climate: climate::Climate,
     listen: listen::Listen,
     current_page: Page,
     dy: f32,
@@ -40,18 +45,22 @@ pub struct Room {
 }
 
 impl Room {
-    pub fn new(id: &'static str, oracle: Arc<Oracle>, config: Arc<Config>) -> Self {
-        let room = oracle.room(id).clone();
+    /// `None` if `id` isn't a known room -- see [`Oracle::room`].
+    pub fn new(id: &'static str, oracle: Arc<Oracle>, config: Arc<Config>) -> Option<Self> {
+        let room = oracle.room(id)?;
 
-        Self {
+        Some(Self {
             id,
             listen: listen::Listen::new(oracle.clone(), &room, config),
-            lights: lights::Lights::new(oracle, &room),
+            lights: lights::Lights::new(oracle.clone(), &room),
+            covers: covers::Covers::new(oracle.clone(), &room),
+            climate: climate::Climate::new(oracle.clone(), &room),
+            oracle,
             room,
             current_page: Page::Listen,
             dy: 0.0,
             pending_visible_toggle: false,
-        }
+        })
     }
 
     pub fn room_id(&self) -> &'static str {
@@ -61,6 +70,8 @@ impl Room {
     pub fn update(&mut self, event: Message) -> Option<Event> {
         match event {
             Message::Lights(v) => self.lights.update(v).map(Event::Lights),
+            Message::Covers(v) => self.covers.update(v).map(Event::Covers),
+            Message::Climate(v) => self.climate.update(v).map(Event::Climate),
             Message::Listen(listen::Message::OnSearchVisibleToggle)
                 if self.listen.search.is_open() && self.dy > 0.0 =>
             {
@@ -93,12 +104,15 @@ impl Room {
                     None
                 }
             }
+            Message::OpenSaveScene => Some(Event::OpenSaveScene(self.id)),
         }
     }
 
     pub fn view(&self, style: &Theme) -> Element<'_, Message, Renderer> {
         let header = text(self.room.name.as_ref())
-            .size(60)
+            .size(crate::theme::scaled(crate::theme::font_size(
+                crate::theme::FontSize::Header,
+            )))
             .font(Font {
                 weight: Weight::Bold,
                 stretch: Stretch::Condensed,
@@ -107,17 +121,36 @@ impl Room {
             .style(theme::Text::Color(Color::WHITE));
 
         let (mut current, needs_scrollable) = match self.current_page {
-            Page::Climate => (Element::from(row![]), false),
-            Page::Listen => (
-                self.listen.view(style).map(Message::Listen),
-                self.listen.search.is_open(),
-            ),
-            Page::Lights => (
-                container(self.lights.view().map(Message::Lights))
+            Page::Climate => (
+                container(self.climate.view().map(Message::Climate))
                     .padding([0, PADDING, 0, PADDING])
                     .into(),
                 false,
             ),
+            Page::Listen => (
+                self.listen.view(style).map(Message::Listen),
+                self.listen.search.is_open(),
+            ),
+            Page::Lights => {
+                let mut col = Column::new()
+                    .spacing(20)
+                    .push(self.lights.view().map(Message::Lights));
+
+                if !self.covers.is_empty() {
+                    col = col.push(self.covers.view().map(Message::Covers));
+                }
+
+                if !self.room.lights.is_empty() {
+                    col = col.push(
+                        mouse_area(text("Save as Scene").size(16)).on_press(Message::OpenSaveScene),
+                    );
+                }
+
+                (
+                    container(col).padding([0, PADDING, 0, PADDING]).into(),
+                    false,
+                )
+            }
         };
 
         let (header, padding_mult) = if let Page::Listen = self.current_page {
@@ -133,6 +166,29 @@ impl Room {
                     .map(Message::Listen),
                 padding_mult,
             )
+        } else if let Some(is_on) = self.room.adaptive_lighting_on(&self.oracle) {
+            let badge = mouse_area(
+                container(
+                    text("Adaptive")
+                        .size(14)
+                        .style(theme::Text::Color(Color::WHITE)),
+                )
+                .padding([4, 12])
+                .style(theme::Container::Custom(Box::new(
+                    lights::AdaptiveBadgeStyle(is_on),
+                ))),
+            )
+            .on_press(Message::Lights(lights::Message::ToggleAdaptiveLighting(
+                !is_on,
+            )));
+
+            (
+                row![header, badge]
+                    .align_items(Alignment::Center)
+                    .spacing(12)
+                    .into(),
+                0.0,
+            )
         } else {
             (Element::from(header), 0.0)
         };
@@ -192,22 +248,30 @@ impl Room {
         Subscription::batch([
             self.listen.subscription().map(Message::Listen),
             self.lights.subscription().map(Message::Lights),
+            self.covers.subscription().map(Message::Covers),
+            self.climate.subscription().map(Message::Climate),
         ])
     }
 }
 
 pub enum Event {
     Lights(lights::Event),
+    Covers(covers::Event),
+    Climate(climate::Event),
     Listen(listen::Event),
+    OpenSaveScene(&'static str),
     Exit,
 }
 
 #[derive(Clone, Debug)]
 pub enum Message {
     Lights(lights::Message),
+    Covers(covers::Message),
+    Climate(climate::Message),
     Listen(listen::Message),
     ChangePage(Page),
     OnContentScroll(Viewport),
     OnContentAnimateFinished,
+    OpenSaveScene,
     Exit,
 }
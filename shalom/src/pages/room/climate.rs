@@ -0,0 +1,288 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use iced::{
+    subscription,
+    widget::{column, container, mouse_area, row, slider, svg, text, Column, Row},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::{
+    oracle::{Humidifier, Oracle, Room, Thermostat},
+    theme::Icon,
+};
+
+/// The room climate page: humidifier/dehumidifier controls and thermostat
+/// hvac/fan/preset mode selection. Target temperature isn't wired up yet.
+#[derive(Debug)]
+pub struct Climate {
+    humidifiers: BTreeMap<&'static str, Humidifier>,
+    thermostats: BTreeMap<&'static str, Thermostat>,
+    oracle: Arc<Oracle>,
+}
+
+impl Climate {
+    pub fn new(oracle: Arc<Oracle>, room: &Room) -> Self {
+        let humidifiers = room.humidifiers(&oracle);
+        let thermostats = room.thermostats(&oracle);
+
+        Self {
+            humidifiers,
+            thermostats,
+            oracle,
+        }
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::SetTargetHumidity(id, humidity) => {
+                if let Some(humidifier) = self.humidifiers.get_mut(id) {
+                    humidifier.humidity = Some(humidity);
+                }
+
+                None
+            }
+            Message::ReleaseTargetHumidity(id) => {
+                let humidity = self.humidifiers.get(id)?.humidity?;
+
+                Some(Event::SetTargetHumidity(id, humidity))
+            }
+            Message::SetMode(id, mode) => {
+                if let Some(humidifier) = self.humidifiers.get_mut(id) {
+                    humidifier.mode = Some(mode.clone());
+                }
+
+                Some(Event::SetMode(id, mode))
+            }
+            Message::UpdateHumidifier(entity_id) => {
+                if let Some(humidifier) = self.oracle.fetch_humidifier(entity_id) {
+                    self.humidifiers.insert(entity_id, humidifier);
+                }
+
+                None
+            }
+            Message::SetHvacMode(id, mode) => {
+                if let Some(thermostat) = self.thermostats.get_mut(id) {
+                    thermostat.hvac_mode = mode.clone();
+                }
+
+                Some(Event::SetHvacMode(id, mode))
+            }
+            Message::SetFanMode(id, mode) => {
+                if let Some(thermostat) = self.thermostats.get_mut(id) {
+                    thermostat.fan_mode = Some(mode.clone());
+                }
+
+                Some(Event::SetFanMode(id, mode))
+            }
+            Message::SetPresetMode(id, mode) => {
+                if let Some(thermostat) = self.thermostats.get_mut(id) {
+                    thermostat.preset_mode = Some(mode.clone());
+                }
+
+                Some(Event::SetPresetMode(id, mode))
+            }
+            Message::UpdateThermostat(entity_id) => {
+                if let Some(thermostat) = self.oracle.fetch_thermostat(entity_id) {
+                    self.thermostats.insert(entity_id, thermostat);
+                }
+
+                None
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let humidifier = |id, humidifier: &Humidifier| {
+            let icon = if humidifier.is_dehumidifier() {
+                Icon::Dehumidifier
+            } else {
+                Icon::Humidifier
+            };
+
+            let header = row![
+                svg(icon).height(28).width(28),
+                text(humidifier.friendly_name.as_ref()).size(20),
+                text(
+                    humidifier
+                        .humidity
+                        .map_or_else(|| "--%".to_string(), |v| format!("{v}%"))
+                )
+                .size(20),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+            let target_slider = slider(
+                f64::from(humidifier.min_humidity)..=f64::from(humidifier.max_humidity),
+                f64::from(humidifier.humidity.unwrap_or(humidifier.min_humidity)),
+                move |v| {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    Message::SetTargetHumidity(id, v as u8)
+                },
+            )
+            .step(1.0)
+            .on_release(Message::ReleaseTargetHumidity(id))
+            .width(Length::Fill);
+
+            let modes = mode_selector(
+                &humidifier.available_modes,
+                humidifier.mode.as_deref(),
+                move |mode| Message::SetMode(id, mode),
+            );
+
+            container(
+                column![header, target_slider, modes]
+                    .spacing(15)
+                    .align_items(Alignment::Start),
+            )
+            .width(Length::Fill)
+            .padding(20)
+        };
+
+        let thermostat = |id, thermostat: &Thermostat| {
+            let header = row![
+                svg(Icon::Hvac).height(28).width(28),
+                text(thermostat.friendly_name.as_ref()).size(20),
+                text(
+                    thermostat
+                        .current_temperature
+                        .map_or_else(|| "--°".to_string(), |v| format!("{v}°"))
+                )
+                .size(20),
+            ]
+            .spacing(10)
+            .align_items(Alignment::Center);
+
+            let hvac_modes = mode_selector(
+                &thermostat.hvac_modes,
+                Some(thermostat.hvac_mode.as_ref()),
+                move |mode| Message::SetHvacMode(id, mode),
+            );
+
+            let fan_modes = mode_selector(
+                &thermostat.fan_modes,
+                thermostat.fan_mode.as_deref(),
+                move |mode| Message::SetFanMode(id, mode),
+            );
+
+            let preset_modes = mode_selector(
+                &thermostat.preset_modes,
+                thermostat.preset_mode.as_deref(),
+                move |mode| Message::SetPresetMode(id, mode),
+            );
+
+            container(
+                column![header, hvac_modes, fan_modes, preset_modes]
+                    .spacing(15)
+                    .align_items(Alignment::Start),
+            )
+            .width(Length::Fill)
+            .padding(20)
+        };
+
+        Column::with_children(
+            self.humidifiers
+                .iter()
+                .map(|(id, item)| humidifier(*id, item))
+                .map(Element::from)
+                .chain(
+                    self.thermostats
+                        .iter()
+                        .map(|(id, item)| thermostat(*id, item))
+                        .map(Element::from),
+                )
+                .collect::<Vec<_>>(),
+        )
+        .spacing(20)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch(
+            self.humidifiers
+                .keys()
+                .copied()
+                .map(|key| {
+                    subscription::run_with_id(
+                        key,
+                        self.oracle
+                            .subscribe_id(key)
+                            .map(|()| Message::UpdateHumidifier(key)),
+                    )
+                })
+                .chain(self.thermostats.keys().copied().map(|key| {
+                    subscription::run_with_id(
+                        key,
+                        self.oracle
+                            .subscribe_id(key)
+                            .map(|()| Message::UpdateThermostat(key)),
+                    )
+                })),
+        )
+    }
+}
+
+/// A row of tappable mode-name buttons, used for humidifier modes and
+/// thermostat hvac/fan modes alike.
+fn mode_selector<'a>(
+    modes: &'a [Box<str>],
+    active: Option<&str>,
+    on_select: impl Fn(Box<str>) -> Message + 'a,
+) -> Row<'a, Message, Renderer> {
+    modes.iter().fold(row![].spacing(10), |row, mode| {
+        let is_active = active == Some(mode.as_ref());
+
+        row.push(
+            mouse_area(
+                container(text(mode.as_ref()).size(16))
+                    .padding([6, 12])
+                    .style(if is_active {
+                        iced::theme::Container::Custom(Box::new(ActiveMode))
+                    } else {
+                        iced::theme::Container::Transparent
+                    }),
+            )
+            .on_press(on_select(mode.clone())),
+        )
+    })
+}
+
+struct ActiveMode;
+
+impl container::StyleSheet for ActiveMode {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(iced::Background::Color(iced::Color {
+                a: 0.8,
+                ..iced::Color::WHITE
+            })),
+            text_color: Some(iced::Color::BLACK),
+            border_radius: 10.0.into(),
+            border_width: 0.0,
+            border_color: iced::Color::TRANSPARENT,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum Event {
+    SetTargetHumidity(&'static str, u8),
+    SetMode(&'static str, Box<str>),
+    SetHvacMode(&'static str, Box<str>),
+    SetFanMode(&'static str, Box<str>),
+    SetPresetMode(&'static str, Box<str>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    SetTargetHumidity(&'static str, u8),
+    ReleaseTargetHumidity(&'static str),
+    SetMode(&'static str, Box<str>),
+    UpdateHumidifier(&'static str),
+    SetHvacMode(&'static str, Box<str>),
+    SetFanMode(&'static str, Box<str>),
+    SetPresetMode(&'static str, Box<str>),
+    UpdateThermostat(&'static str),
+}
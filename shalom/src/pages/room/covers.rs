@@ -0,0 +1,88 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use iced::{subscription, widget::Row, Element, Length, Renderer, Subscription};
+
+use crate::{
+    oracle::{Cover, Oracle, Room},
+    theme::Icon,
+    widgets,
+};
+
+/// Garage doors, shown as a dedicated card per [`Room::garage_covers`]. A
+/// regular tap closes the door; opening it requires a press-and-hold, so a
+/// stray touch can't send the door open unattended.
+#[derive(Debug)]
+pub struct Covers {
+    covers: BTreeMap<&'static str, Cover>,
+    oracle: Arc<Oracle>,
+}
+
+impl Covers {
+    pub fn new(oracle: Arc<Oracle>, room: &Room) -> Self {
+        let covers = room.garage_covers(&oracle);
+
+        Self { covers, oracle }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.covers.is_empty()
+    }
+
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::OpenCover(id) => Some(Event::OpenCover(id)),
+            Message::CloseCover(id) => Some(Event::CloseCover(id)),
+            Message::UpdateCover(entity_id) => {
+                if let Some(cover) = self.oracle.fetch_cover(entity_id) {
+                    self.covers.insert(entity_id, cover);
+                }
+
+                None
+            }
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let cover = |id, cover: &Cover| {
+            widgets::toggle_card::toggle_card(&cover.friendly_name, cover.is_open(), false)
+                .width(Length::Shrink)
+                .icon(Icon::Garage)
+                .on_press(Message::CloseCover(id))
+                .on_long_press(Message::OpenCover(id))
+        };
+
+        Row::with_children(
+            self.covers
+                .iter()
+                .map(|(id, item)| cover(*id, item))
+                .map(Element::from)
+                .collect::<Vec<_>>(),
+        )
+        .spacing(10)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch(self.covers.keys().copied().map(|key| {
+            subscription::run_with_id(
+                key,
+                self.oracle
+                    .subscribe_id(key)
+                    .map(|()| Message::UpdateCover(key)),
+            )
+        }))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum Event {
+    OpenCover(&'static str),
+    CloseCover(&'static str),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    OpenCover(&'static str),
+    CloseCover(&'static str),
+    UpdateCover(&'static str),
+}
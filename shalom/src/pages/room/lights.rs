@@ -1,26 +1,47 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use iced::{
-    futures::StreamExt, subscription, widget::Row, Element, Length, Renderer, Subscription,
+    futures::StreamExt,
+    subscription, theme,
+    widget::{container, mouse_area, svg, text, Row},
+    Element, Length, Renderer, Subscription, Theme,
 };
 
 use crate::{
     oracle::{Light, Oracle, Room},
-    theme::Icon,
-    widgets::{self, colour_picker::colour_from_hsb},
+    subscriptions::{find_mdi_icon, mdi_icon_name},
+    theme::{
+        colours::{GREEN_500, SYSTEM_GRAY6},
+        Icon,
+    },
+    widgets::{
+        self,
+        colour_picker::colour_from_hsb,
+        floating_element::{Anchor, FloatingElement},
+    },
 };
 
 #[derive(Debug)]
 pub struct Lights {
     lights: BTreeMap<&'static str, Light>,
+    icons: HashMap<Box<str>, svg::Handle>,
     oracle: Arc<Oracle>,
+    adaptive_lighting_switch: Option<&'static str>,
 }
 
 impl Lights {
     pub fn new(oracle: Arc<Oracle>, room: &Room) -> Self {
         let lights = room.lights(&oracle);
 
-        Self { lights, oracle }
+        Self {
+            lights,
+            icons: HashMap::new(),
+            oracle,
+            adaptive_lighting_switch: room.adaptive_lighting_switch.map(|v| v.as_ref()),
+        }
     }
 
     pub fn update(&mut self, event: Message) -> Option<Event> {
@@ -41,22 +62,51 @@ impl Lights {
 
                 None
             }
+            Message::MdiIconLoaded(icon, handle) => {
+                self.icons.insert(icon, handle);
+                None
+            }
+            Message::ToggleAdaptiveLighting(enabled) => self
+                .adaptive_lighting_switch
+                .map(|id| Event::SetAdaptiveLighting(id, enabled)),
+            Message::AdjustBrightness(id, delta) => {
+                // give instant feedback before we get the event back from hass
+                if let Some(light) = self.lights.get_mut(id) {
+                    let brightness =
+                        (light.brightness.unwrap_or_default() / 255. + delta).clamp(0.0, 1.0);
+                    light.brightness = Some(brightness * 255.);
+                }
+
+                Some(Event::AdjustBrightness(id, delta))
+            }
+            Message::SetFullBrightness(id) => {
+                // give instant feedback before we get the event back from hass
+                if let Some(light) = self.lights.get_mut(id) {
+                    light.on = Some(true);
+                    light.brightness = Some(255.);
+                    light.hs_color = Some((0.0, 0.0));
+                }
+
+                Some(Event::SetFullBrightness(id))
+            }
         }
     }
 
     pub fn view(&self) -> Element<'_, Message, Renderer> {
         let light = |id, light: &Light| {
+            let state = match light.on {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "unavailable",
+            };
+
             let mut toggle_card = widgets::toggle_card::toggle_card(
                 &light.friendly_name,
                 light.on.unwrap_or_default(),
                 light.on.is_none(),
             )
-            .icon(if light.on.is_none() {
-                Icon::Dead
-            } else {
-                Icon::Bulb
-            })
             .width(Length::Shrink)
+            .description(format!("{}, {state}", light.friendly_name))
             .active_icon_colour(
                 light
                     .hs_color
@@ -64,20 +114,60 @@ impl Lights {
                     .map(|((h, s), b)| colour_from_hsb(h, s, b / 255.)),
             );
 
+            toggle_card = match light.icon.as_deref().and_then(|icon| self.icons.get(icon)) {
+                Some(handle) => toggle_card.icon(handle.clone()),
+                None if light.on.is_none() => toggle_card.icon(Icon::Dead),
+                None => toggle_card.icon(Icon::Bulb),
+            };
+
             if let Some(state) = light.on {
                 toggle_card = toggle_card
                     .on_press(Message::SetLightState(id, !state))
                     .on_long_press(Message::OpenLightOptions(id));
             }
 
+            if !light.supported_color_modes.is_empty() {
+                toggle_card = toggle_card
+                    .on_drag(move |delta| Message::AdjustBrightness(id, delta))
+                    .on_double_tap(Message::SetFullBrightness(id));
+            }
+
             toggle_card
         };
 
+        let adaptive_lighting_on = self
+            .adaptive_lighting_switch
+            .and_then(|id| self.oracle.fetch_switch(id))
+            .map(|switch| switch.is_on);
+
         Row::with_children(
             self.lights
                 .iter()
-                .map(|(id, item)| light(*id, item))
-                .map(Element::from)
+                .map(|(id, item)| {
+                    let card = Element::from(light(*id, item));
+
+                    let Some(is_on) = adaptive_lighting_on else {
+                        return card;
+                    };
+
+                    let badge = mouse_area(
+                        container(
+                            text("A")
+                                .size(12)
+                                .style(theme::Text::Color(iced::Color::WHITE)),
+                        )
+                        .padding([2, 6])
+                        .style(theme::Container::Custom(Box::new(AdaptiveBadgeStyle(
+                            is_on,
+                        )))),
+                    )
+                    .on_press(Message::ToggleAdaptiveLighting(!is_on));
+
+                    FloatingElement::new(card, badge)
+                        .anchor(Anchor::NorthEast)
+                        .offset([4.0, 4.0])
+                        .into()
+                })
                 .collect::<Vec<_>>(),
         )
         .spacing(10)
@@ -85,14 +175,31 @@ impl Lights {
     }
 
     pub fn subscription(&self) -> Subscription<Message> {
-        Subscription::batch(self.lights.keys().copied().map(|key| {
+        let light_subscriptions = self.lights.keys().copied().map(|key| {
             subscription::run_with_id(
                 key,
                 self.oracle
                     .subscribe_id(key)
                     .map(|()| Message::UpdateLight(key)),
             )
-        }))
+        });
+
+        let icon_subscriptions = self.lights.values().filter_map(|light| {
+            let icon = light.icon.as_deref()?;
+
+            if self.icons.contains_key(icon) {
+                return None;
+            }
+
+            let name = mdi_icon_name(icon)?.to_string();
+            let icon: Box<str> = Box::from(icon);
+
+            Some(find_mdi_icon(name, move |handle| {
+                Message::MdiIconLoaded(icon, handle)
+            }))
+        });
+
+        Subscription::batch(light_subscriptions.chain(icon_subscriptions))
     }
 }
 
@@ -100,11 +207,35 @@ impl Lights {
 pub enum Event {
     OpenLightContextMenu(&'static str),
     SetLightState(&'static str, bool),
+    SetAdaptiveLighting(&'static str, bool),
+    AdjustBrightness(&'static str, f32),
+    SetFullBrightness(&'static str),
 }
 
-#[derive(Clone, Debug, Copy)]
+#[derive(Clone, Debug)]
 pub enum Message {
     SetLightState(&'static str, bool),
     UpdateLight(&'static str),
     OpenLightOptions(&'static str),
+    MdiIconLoaded(Box<str>, svg::Handle),
+    ToggleAdaptiveLighting(bool),
+    AdjustBrightness(&'static str, f32),
+    SetFullBrightness(&'static str),
+}
+
+/// Green when Adaptive Lighting is on for the room, grey when off. Used by
+/// the light cards' "A" badge ([`Lights::view`]) and the room header's
+/// "Adaptive" badge (`super::view`).
+pub(crate) struct AdaptiveBadgeStyle(pub bool);
+
+impl container::StyleSheet for AdaptiveBadgeStyle {
+    type Style = Theme;
+
+    fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        container::Appearance {
+            background: Some(if self.0 { GREEN_500 } else { SYSTEM_GRAY6 }.into()),
+            border_radius: 10.0.into(),
+            ..container::Appearance::default()
+        }
+    }
 }
@@ -1,12 +1,22 @@
 mod search;
 
-use std::{borrow::Cow, convert::identity, iter, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    convert::identity,
+    iter,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use iced::{
     futures::{future, future::Either, stream, stream::FuturesUnordered, FutureExt, StreamExt},
     subscription,
-    widget::{container, image::Handle, Column, Text},
-    Element, Length, Renderer, Subscription, Theme,
+    widget::{
+        column, container, image, image::Handle, mouse_area, progress_bar, row, scrollable, text,
+        Column, Row, Text,
+    },
+    Alignment, Element, Length, Renderer, Subscription, Theme,
 };
 use itertools::Itertools;
 use serde::Deserialize;
@@ -17,15 +27,21 @@ use crate::{
     config::Config,
     hass_client::MediaPlayerRepeat,
     magic::header_search::header_search,
-    oracle::{MediaPlayerSpeaker, MediaPlayerSpeakerState, Oracle, Room},
-    pages::room::listen::search::SearchResult,
+    network,
+    oracle::{MediaItem, MediaPlayerSpeaker, MediaPlayerSpeakerState, Oracle, Room},
+    pages::room::listen::search::{ResultMetadata, SearchResult},
     subscriptions::{
         download_image, find_fanart_urls, find_musicbrainz_artist, load_image, MaybePendingImage,
     },
     theme::{darken_image, trim_transparent_padding, Image},
     widgets,
+    widgets::image_card,
 };
 
+/// How long music must play uninterrupted before [`Listen::view`] switches
+/// to the full-screen ambient now-playing display.
+const AMBIENT_MODE_IDLE: Duration = Duration::from_secs(120);
+
 #[derive(Debug)]
 pub struct Listen {
     room: Room,
@@ -36,7 +52,19 @@ pub struct Listen {
     pub background: Option<MaybePendingImage>,
     artist_logo: Option<MaybePendingImage>,
     pub search: SearchState,
+    pub browse: BrowseState,
+    pub devices: DevicesState,
+    pub media_browse: MediaBrowseState,
+    pub detail: Option<(DetailKind, Result<Vec<SearchResult>, String>)>,
     config: Arc<Config>,
+    /// When the current track started playing uninterrupted, used to enter
+    /// [`Self::ambient`] mode after [`AMBIENT_MODE_IDLE`]. Reset to `None`
+    /// whenever playback pauses/stops.
+    playing_since: Option<Instant>,
+    /// Whether the full-screen ambient now-playing display is active.
+    /// Entered automatically once music has played uninterrupted for
+    /// [`AMBIENT_MODE_IDLE`], exited on any touch.
+    ambient: bool,
 }
 
 impl Listen {
@@ -52,7 +80,13 @@ impl Listen {
             background: None,
             artist_logo: None,
             search: SearchState::Closed,
+            browse: BrowseState::Closed,
+            devices: DevicesState::Closed,
+            media_browse: MediaBrowseState::Closed,
+            detail: None,
             config,
+            playing_since: None,
+            ambient: false,
         }
     }
 
@@ -87,8 +121,8 @@ impl Listen {
                 None
             }
             Message::MusicbrainzArtistLoaded(v) => {
-                eprintln!("musicbrainz artist {v}");
-                self.musicbrainz_artist_id = Some(v);
+                eprintln!("musicbrainz artist {v:?}");
+                self.musicbrainz_artist_id = Some(v.unwrap_or_default());
                 None
             }
             Message::UpdateSpeaker => {
@@ -120,15 +154,63 @@ impl Listen {
                     self.musicbrainz_artist_id = None;
                 }
 
+                let was_playing = self
+                    .speaker
+                    .as_ref()
+                    .is_some_and(|(_, v)| v.state.is_playing());
+                let now_playing = new.as_ref().is_some_and(|(_, v)| v.state.is_playing());
+
+                if now_playing && !was_playing {
+                    self.playing_since = Some(Instant::now());
+                } else if !now_playing {
+                    self.playing_since = None;
+                    self.ambient = false;
+                }
+
                 self.speaker = new;
 
                 None
             }
+            Message::AmbientTick => {
+                if !self.ambient
+                    && self
+                        .playing_since
+                        .is_some_and(|since| since.elapsed() >= AMBIENT_MODE_IDLE)
+                {
+                    self.ambient = true;
+                }
+                None
+            }
+            Message::ExitAmbient => {
+                self.ambient = false;
+                self.playing_since = Some(Instant::now());
+                None
+            }
             Message::OnSpeakerVolumeChange(new) => {
                 let (id, speaker) = self.speaker.as_mut()?;
                 speaker.volume = new;
                 Some(Event::SetSpeakerVolume(id, new))
             }
+            Message::OnGroupVolumeChange(new) => {
+                let (id, _) = self.speaker.as_ref()?;
+                Some(Event::SetGroupVolume(id, new))
+            }
+            Message::OnSpeakerBassChange(new) => {
+                let (id, _) = self.speaker.as_ref()?;
+                Some(Event::SetSpeakerBass(id, new))
+            }
+            Message::OnSpeakerTrebleChange(new) => {
+                let (id, _) = self.speaker.as_ref()?;
+                Some(Event::SetSpeakerTreble(id, new))
+            }
+            Message::OnSpeakerLoudnessChange(new) => {
+                let (id, _) = self.speaker.as_ref()?;
+                Some(Event::SetSpeakerLoudness(id, new))
+            }
+            Message::OnSpeakerNightModeChange(new) => {
+                let (id, _) = self.speaker.as_ref()?;
+                Some(Event::SetSpeakerNightMode(id, new))
+            }
             Message::OnSpeakerPositionChange(new) => {
                 let (id, speaker) = self.speaker.as_mut()?;
                 speaker.actual_media_position = Some(new);
@@ -162,6 +244,10 @@ impl Listen {
                 speaker.shuffle = new;
                 Some(Event::SetSpeakerShuffle(id, new))
             }
+            Message::OnSpeakerShare => {
+                let (_, speaker) = self.speaker.as_ref()?;
+                Some(Event::ShareNowPlaying(speaker.spotify_url()?))
+            }
             Message::BackgroundDownloaded(handle) => {
                 self.background = Some(MaybePendingImage::Downloaded(handle));
                 None
@@ -212,12 +298,250 @@ impl Listen {
 
                 None
             }
-            Message::OnPlayTrack(uri) => Some(Event::PlayTrack(self.speaker.as_ref()?.0, uri)),
+            Message::OnPlayTrack(uri, metadata) => match metadata {
+                ResultMetadata::Album => {
+                    self.detail = Some((DetailKind::Album(uri), Ok(vec![])));
+                    None
+                }
+                ResultMetadata::Artist => {
+                    self.detail = Some((DetailKind::Artist(uri), Ok(vec![])));
+                    None
+                }
+                ResultMetadata::Track(_) | ResultMetadata::Playlist => {
+                    Some(Event::PlayTrack(self.speaker.as_ref()?.0, uri))
+                }
+            },
+            Message::DetailClose => {
+                self.detail = None;
+                None
+            }
+            Message::DetailTrackResult(res) => {
+                if let Some((_, tracks)) = &mut self.detail {
+                    if let Ok(tracks) = tracks {
+                        tracks.push(res);
+                    } else {
+                        *tracks = Ok(vec![res]);
+                    }
+                }
+                None
+            }
+            Message::DetailTrackResultError(err) => {
+                if let Some((_, tracks)) = &mut self.detail {
+                    *tracks = Err(err);
+                }
+                None
+            }
+            Message::DetailPlayTrack(uri) => Some(Event::PlayTrack(self.speaker.as_ref()?.0, uri)),
+            Message::DetailQueueTrack(uri) => {
+                Some(Event::QueueTrack(self.speaker.as_ref()?.0, uri))
+            }
+            Message::RecentlyPlayedPress(uri) => {
+                Some(Event::PlayTrack(self.speaker.as_ref()?.0, uri))
+            }
+            Message::OnBrowseToggle => {
+                self.browse = if matches!(self.browse, BrowseState::Closed) {
+                    BrowseState::Open {
+                        results: Ok(vec![]),
+                    }
+                } else {
+                    BrowseState::Closed
+                };
+                None
+            }
+            Message::SpotifyBrowseResult(res) => {
+                if let BrowseState::Open { results } = &mut self.browse {
+                    if let Ok(results) = results {
+                        results.push(res);
+                    } else {
+                        *results = Ok(vec![res]);
+                    }
+                }
+                None
+            }
+            Message::SpotifyBrowseResultError(err) => {
+                if let BrowseState::Open { results } = &mut self.browse {
+                    *results = Err(err);
+                }
+                None
+            }
+            Message::OnDevicesToggle => {
+                self.devices = if matches!(self.devices, DevicesState::Closed) {
+                    DevicesState::Open {
+                        results: Ok(vec![]),
+                    }
+                } else {
+                    DevicesState::Closed
+                };
+                None
+            }
+            Message::SpotifyDevicesResult(devices) => {
+                if let DevicesState::Open { results } = &mut self.devices {
+                    *results = Ok(devices);
+                }
+                None
+            }
+            Message::SpotifyDevicesResultError(err) => {
+                if let DevicesState::Open { results } = &mut self.devices {
+                    *results = Err(err);
+                }
+                None
+            }
+            Message::TransferPlayback(device_id) => {
+                self.devices = DevicesState::Closed;
+                Some(Event::TransferPlayback(device_id))
+            }
+            Message::OnMediaBrowseToggle => {
+                self.media_browse = if matches!(self.media_browse, MediaBrowseState::Closed) {
+                    MediaBrowseState::Open {
+                        stack: vec![(None, None)],
+                        items: vec![],
+                        thumbnails: BTreeMap::new(),
+                    }
+                } else {
+                    MediaBrowseState::Closed
+                };
+                None
+            }
+            Message::MediaBrowseOpen(content_id, content_type) => {
+                if let MediaBrowseState::Open {
+                    stack,
+                    items,
+                    thumbnails,
+                } = &mut self.media_browse
+                {
+                    stack.push((content_id, content_type));
+                    items.clear();
+                    thumbnails.clear();
+                }
+                None
+            }
+            Message::MediaBrowseBack => {
+                if let MediaBrowseState::Open {
+                    stack,
+                    items,
+                    thumbnails,
+                } = &mut self.media_browse
+                {
+                    if stack.len() > 1 {
+                        stack.pop();
+                    }
+                    items.clear();
+                    thumbnails.clear();
+                }
+                None
+            }
+            Message::MediaBrowseLoaded(new_items) => {
+                if let MediaBrowseState::Open { items, .. } = &mut self.media_browse {
+                    *items = new_items;
+                }
+                None
+            }
+            Message::MediaBrowseThumbnailLoaded(content_id, handle) => {
+                if let MediaBrowseState::Open { thumbnails, .. } = &mut self.media_browse {
+                    thumbnails.insert(content_id, handle);
+                }
+                None
+            }
+            Message::MediaBrowsePlay(content_id, content_type) => Some(Event::PlayMedia(
+                self.speaker.as_ref()?.0,
+                content_id,
+                content_type,
+            )),
         }
     }
 
     pub fn view(&self, style: &Theme) -> Element<'_, Message, Renderer> {
-        if self.search.is_open() {
+        if self.ambient {
+            let Some((_, speaker)) = self.speaker.clone() else {
+                return Column::new().into();
+            };
+
+            let art: Element<'_, Message, Renderer> = self.album_art_image.clone().map_or_else(
+                || Element::from(Column::new()),
+                |handle| {
+                    Element::from(
+                        image(handle)
+                            .width(Length::Fill)
+                            .height(Length::FillPortion(4)),
+                    )
+                },
+            );
+
+            let artist_logo = self
+                .artist_logo
+                .as_ref()
+                .and_then(MaybePendingImage::handle)
+                .map(|handle| image(handle).width(200).height(80));
+
+            let position = speaker.actual_media_position.unwrap_or_default();
+            let duration = speaker.media_duration.unwrap_or_default();
+            let progress = if duration.is_zero() {
+                0.0
+            } else {
+                position.as_secs_f32() / duration.as_secs_f32()
+            };
+
+            let mut content = column![art].spacing(20).align_items(Alignment::Center);
+
+            if let Some(artist_logo) = artist_logo {
+                content = content.push(artist_logo);
+            }
+
+            content = content.push(progress_bar(0.0..=1.0, progress).height(4));
+
+            return mouse_area(
+                container(content)
+                    .padding(40)
+                    .width(Length::Fill)
+                    .height(Length::Fill),
+            )
+            .on_press(Message::ExitAmbient)
+            .into();
+        }
+
+        if let Some((kind, tracks)) = &self.detail {
+            let title = match kind {
+                DetailKind::Album(_) => "Album",
+                DetailKind::Artist(_) => "Top Tracks",
+            };
+
+            let rows = match tracks {
+                Ok(tracks) => tracks.iter().fold(column![].spacing(10), |col, track| {
+                    let uri = track.uri().to_string();
+
+                    col.push(
+                        row![
+                            image(track.image()).width(48).height(48),
+                            text(track.title().to_string()).size(18).width(Length::Fill),
+                            mouse_area(text("Play").size(16))
+                                .on_press(Message::DetailPlayTrack(uri.clone())),
+                            mouse_area(text("Queue").size(16))
+                                .on_press(Message::DetailQueueTrack(uri)),
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                    )
+                }),
+                Err(err) => column![text(err.clone())],
+            };
+
+            container(
+                column![
+                    row![
+                        mouse_area(text("Close").size(16)).on_press(Message::DetailClose),
+                        text(title).size(24),
+                    ]
+                    .spacing(20),
+                    scrollable(rows).height(Length::Fill),
+                ]
+                .spacing(10)
+                .height(Length::Fill),
+            )
+            .padding([0, 40, 40, 40])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+        } else if self.search.is_open() {
             container(
                 search::search(style.clone(), self.search.results())
                     .on_track_press(Message::OnPlayTrack),
@@ -225,22 +549,163 @@ impl Listen {
             .padding([0, 40, 40, 40])
             .width(Length::Fill)
             .into()
-        } else if let Some((_, speaker)) = self.speaker.clone() {
-            container(
-                widgets::media_player::media_player(speaker, self.album_art_image.clone())
-                    .with_artist_logo(
-                        self.artist_logo
-                            .as_ref()
-                            .and_then(MaybePendingImage::handle),
+        } else if self.search.search() == Some("") {
+            let recently_played = self
+                .speaker
+                .as_ref()
+                .map(|(id, _)| self.oracle.speaker(*id).recently_played())
+                .unwrap_or_default();
+
+            let rows = recently_played
+                .into_iter()
+                .fold(column![].spacing(10), |col, uri| {
+                    col.push(
+                        mouse_area(
+                            row![text(uri.clone()).size(18).width(Length::Fill)]
+                                .align_items(Alignment::Center),
+                        )
+                        .on_press(Message::RecentlyPlayedPress(uri)),
                     )
-                    .on_volume_change(Message::OnSpeakerVolumeChange)
-                    .on_mute_change(Message::OnSpeakerMuteChange)
-                    .on_repeat_change(Message::OnSpeakerRepeatChange)
-                    .on_state_change(Message::OnSpeakerStateChange)
-                    .on_position_change(Message::OnSpeakerPositionChange)
-                    .on_next_track(Message::OnSpeakerNextTrack)
-                    .on_previous_track(Message::OnSpeakerPreviousTrack)
-                    .on_shuffle_change(Message::OnSpeakerShuffleChange),
+                });
+
+            container(column![text("Recently played").size(24), rows].spacing(20))
+                .padding([0, 40, 40, 40])
+                .width(Length::Fill)
+                .into()
+        } else if self.browse.is_open() {
+            container(
+                column![
+                    mouse_area(text("Close").size(16)).on_press(Message::OnBrowseToggle),
+                    search::search(style.clone(), self.browse.results())
+                        .on_track_press(Message::OnPlayTrack),
+                ]
+                .spacing(10),
+            )
+            .padding([0, 40, 40, 40])
+            .width(Length::Fill)
+            .into()
+        } else if let DevicesState::Open { results } = &self.devices {
+            let rows = match results {
+                Ok(devices) => devices.iter().fold(column![].spacing(10), |col, device| {
+                    let label = if device.is_active {
+                        format!("{} (playing here)", device.name)
+                    } else {
+                        device.name.clone()
+                    };
+
+                    col.push(match &device.id {
+                        Some(id) => Element::from(
+                            mouse_area(text(label).size(18))
+                                .on_press(Message::TransferPlayback(id.clone())),
+                        ),
+                        None => Element::from(text(label).size(18)),
+                    })
+                }),
+                Err(err) => column![text(err.clone())],
+            };
+
+            container(
+                column![
+                    mouse_area(text("Close").size(16)).on_press(Message::OnDevicesToggle),
+                    text("Transfer Playback").size(24),
+                    rows,
+                ]
+                .spacing(10),
+            )
+            .padding([0, 40, 40, 40])
+            .width(Length::Fill)
+            .into()
+        } else if let MediaBrowseState::Open {
+            stack,
+            items,
+            thumbnails,
+        } = &self.media_browse
+        {
+            let cards = items
+                .iter()
+                .map(|item| {
+                    let content_id = item.media_content_id.to_string();
+                    let content_type = item.media_content_type.to_string();
+
+                    let handle = thumbnails
+                        .get(&item.media_content_id)
+                        .cloned()
+                        .unwrap_or_else(|| Image::UnknownArtist.into());
+
+                    let card = image_card::image_card(handle, &item.title);
+
+                    // Poster art in a media browser is almost always either a
+                    // playable item (a movie/episode) or a folder to expand
+                    // (a show/season), never both, so one press target per
+                    // card covers the Jellyfin/Plex browsing case fine.
+                    if item.can_play {
+                        card.on_press(Message::MediaBrowsePlay(content_id, content_type))
+                    } else if item.can_expand {
+                        card.on_press(Message::MediaBrowseOpen(
+                            Some(content_id),
+                            Some(content_type),
+                        ))
+                    } else {
+                        card
+                    }
+                })
+                .chunks(3)
+                .into_iter()
+                .map(|children| children.fold(row![].spacing(10), Row::push))
+                .fold(column![].spacing(10), Column::push);
+
+            let mut header =
+                row![mouse_area(text("Close").size(16)).on_press(Message::OnMediaBrowseToggle)]
+                    .spacing(20);
+            if stack.len() > 1 {
+                header = header
+                    .push(mouse_area(text("Back").size(16)).on_press(Message::MediaBrowseBack));
+            }
+
+            container(
+                column![header, scrollable(cards).height(Length::Fill)]
+                    .spacing(10)
+                    .height(Length::Fill),
+            )
+            .padding([0, 40, 40, 40])
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+        } else if let Some((speaker_id, speaker)) = self.speaker.clone() {
+            container(
+                column![
+                    mouse_area(text("Browse Library").size(16)).on_press(Message::OnBrowseToggle),
+                    mouse_area(text("Browse Media Sources").size(16))
+                        .on_press(Message::OnMediaBrowseToggle),
+                    mouse_area(text("Transfer Playback").size(16))
+                        .on_press(Message::OnDevicesToggle),
+                    widgets::media_player::media_player(speaker, self.album_art_image.clone())
+                        .with_artist_logo(
+                            self.artist_logo
+                                .as_ref()
+                                .and_then(MaybePendingImage::handle),
+                        )
+                        .with_group_volume(self.oracle.speaker(speaker_id).group_volume())
+                        .on_volume_change(Message::OnSpeakerVolumeChange)
+                        .on_group_volume_change(Message::OnGroupVolumeChange)
+                        .with_bass(self.oracle.speaker(speaker_id).bass())
+                        .on_bass_change(Message::OnSpeakerBassChange)
+                        .with_treble(self.oracle.speaker(speaker_id).treble())
+                        .on_treble_change(Message::OnSpeakerTrebleChange)
+                        .with_loudness(self.oracle.speaker(speaker_id).loudness())
+                        .on_loudness_change(Message::OnSpeakerLoudnessChange)
+                        .with_night_mode(self.oracle.speaker(speaker_id).night_mode())
+                        .on_night_mode_change(Message::OnSpeakerNightModeChange)
+                        .on_mute_change(Message::OnSpeakerMuteChange)
+                        .on_repeat_change(Message::OnSpeakerRepeatChange)
+                        .on_state_change(Message::OnSpeakerStateChange)
+                        .on_position_change(Message::OnSpeakerPositionChange)
+                        .on_next_track(Message::OnSpeakerNextTrack)
+                        .on_previous_track(Message::OnSpeakerPreviousTrack)
+                        .on_shuffle_change(Message::OnSpeakerShuffleChange)
+                        .on_share(Message::OnSpeakerShare),
+                ]
+                .spacing(10),
             )
             .into()
         } else {
@@ -276,7 +741,11 @@ impl Listen {
             &self.artist_logo,
             &self.musicbrainz_artist_id,
         ) {
-            find_fanart_urls(musicbrainz_id.clone(), Message::FanArtLoaded)
+            if musicbrainz_id.is_empty() {
+                Subscription::none()
+            } else {
+                find_fanart_urls(musicbrainz_id.clone(), Message::FanArtLoaded)
+            }
         } else {
             Subscription::none()
         };
@@ -320,14 +789,79 @@ impl Listen {
             Subscription::none()
         };
 
+        let browse_result = if self.browse.is_open() {
+            browse_spotify(&self.config.spotify.token)
+        } else {
+            Subscription::none()
+        };
+
+        let devices_result = if self.devices.is_open() {
+            spotify_devices(&self.config.spotify.token)
+        } else {
+            Subscription::none()
+        };
+
+        let media_browse_result = if let (Some((speaker_id, _)), Some((content_id, content_type))) =
+            (&self.speaker, self.media_browse.current())
+        {
+            media_browse(
+                self.oracle.clone(),
+                *speaker_id,
+                content_id.clone(),
+                content_type.clone(),
+            )
+        } else {
+            Subscription::none()
+        };
+
+        let media_browse_thumbnails = if let MediaBrowseState::Open {
+            items, thumbnails, ..
+        } = &self.media_browse
+        {
+            Subscription::batch(items.iter().filter_map(|item| {
+                if thumbnails.contains_key(&item.media_content_id) {
+                    return None;
+                }
+
+                let url = item.thumbnail.clone()?;
+                let content_id = item.media_content_id.clone();
+
+                Some(download_image(url, identity, move |handle| {
+                    Message::MediaBrowseThumbnailLoaded(content_id, handle)
+                }))
+            }))
+        } else {
+            Subscription::none()
+        };
+
+        let detail_result = match &self.detail {
+            Some((DetailKind::Album(uri), _)) => album_tracks(uri, &self.config.spotify.token),
+            Some((DetailKind::Artist(uri), _)) => {
+                artist_top_tracks(uri, &self.config.spotify.token)
+            }
+            None => Subscription::none(),
+        };
+
+        let ambient_tick_subscription = if self.playing_since.is_some() && !self.ambient {
+            iced::time::every(Duration::from_secs(5)).map(|_| Message::AmbientTick)
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch([
             album_art_subscription,
             speaker_subscription,
+            ambient_tick_subscription,
             musicbrainz_artist_id_subscription,
             background_subscription,
             logo_subscription,
             fanart_subscription,
             spotify_result,
+            browse_result,
+            devices_result,
+            media_browse_result,
+            media_browse_thumbnails,
+            detail_result,
         ])
     }
 }
@@ -385,9 +919,76 @@ impl SearchState {
     }
 }
 
+#[derive(Debug, Hash, Clone)]
+pub enum BrowseState {
+    Open {
+        results: Result<Vec<SearchResult>, String>,
+    },
+    Closed,
+}
+
+impl BrowseState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+
+    pub fn results(&self) -> search::SearchState<'_> {
+        match self {
+            Self::Open { results } => match results {
+                Ok(v) if v.is_empty() => search::SearchState::NotReady,
+                Ok(v) => search::SearchState::Ready(v.as_slice()),
+                Err(e) => search::SearchState::Error(e),
+            },
+            Self::Closed => search::SearchState::NotReady,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DevicesState {
+    Open {
+        results: Result<Vec<SpotifyDevice>, String>,
+    },
+    Closed,
+}
+
+impl DevicesState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum MediaBrowseState {
+    Open {
+        stack: Vec<(Option<String>, Option<String>)>,
+        items: Vec<MediaItem>,
+        thumbnails: BTreeMap<Box<str>, Handle>,
+    },
+    Closed,
+}
+
+impl MediaBrowseState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+
+    pub fn current(&self) -> Option<&(Option<String>, Option<String>)> {
+        match self {
+            Self::Open { stack, .. } => stack.last(),
+            Self::Closed => None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum Event {
     SetSpeakerVolume(&'static str, f32),
+    SetGroupVolume(&'static str, f32),
+    SetSpeakerBass(&'static str, f32),
+    SetSpeakerTreble(&'static str, f32),
+    SetSpeakerLoudness(&'static str, bool),
+    SetSpeakerNightMode(&'static str, bool),
     SetSpeakerPosition(&'static str, Duration),
     SetSpeakerPlaying(&'static str, bool),
     SetSpeakerMuted(&'static str, bool),
@@ -396,6 +997,16 @@ pub enum Event {
     SpeakerNextTrack(&'static str),
     SpeakerPreviousTrack(&'static str),
     PlayTrack(&'static str, String),
+    PlayMedia(&'static str, String, String),
+    QueueTrack(&'static str, String),
+    ShareNowPlaying(String),
+    TransferPlayback(String),
+}
+
+#[derive(Debug, Clone)]
+pub enum DetailKind {
+    Album(String),
+    Artist(String),
 }
 
 #[derive(Clone, Debug)]
@@ -403,10 +1014,17 @@ pub enum Message {
     AlbumArtImageLoaded(Handle),
     BackgroundDownloaded(Handle),
     ArtistLogoDownloaded(Handle),
-    MusicbrainzArtistLoaded(String),
+    MusicbrainzArtistLoaded(Option<String>),
     FanArtLoaded(Option<Url>, Option<Url>),
     UpdateSpeaker,
+    AmbientTick,
+    ExitAmbient,
     OnSpeakerVolumeChange(f32),
+    OnGroupVolumeChange(f32),
+    OnSpeakerBassChange(f32),
+    OnSpeakerTrebleChange(f32),
+    OnSpeakerLoudnessChange(bool),
+    OnSpeakerNightModeChange(bool),
     OnSpeakerPositionChange(Duration),
     OnSpeakerStateChange(bool),
     OnSpeakerMuteChange(bool),
@@ -414,11 +1032,31 @@ pub enum Message {
     OnSpeakerRepeatChange(MediaPlayerRepeat),
     OnSpeakerNextTrack,
     OnSpeakerPreviousTrack,
+    OnSpeakerShare,
     OnSearchTerm(String),
     OnSearchVisibleToggle,
     SpotifySearchResult((SearchResult, String)),
     SpotifySearchResultError((String, String)),
-    OnPlayTrack(String),
+    OnPlayTrack(String, ResultMetadata),
+    OnBrowseToggle,
+    SpotifyBrowseResult(SearchResult),
+    SpotifyBrowseResultError(String),
+    OnDevicesToggle,
+    SpotifyDevicesResult(Vec<SpotifyDevice>),
+    SpotifyDevicesResultError(String),
+    TransferPlayback(String),
+    OnMediaBrowseToggle,
+    MediaBrowseOpen(Option<String>, Option<String>),
+    MediaBrowseBack,
+    MediaBrowseLoaded(Vec<MediaItem>),
+    MediaBrowseThumbnailLoaded(Box<str>, Handle),
+    MediaBrowsePlay(String, String),
+    DetailClose,
+    DetailTrackResult(SearchResult),
+    DetailTrackResultError(String),
+    DetailPlayTrack(String),
+    DetailQueueTrack(String),
+    RecentlyPlayedPress(String),
 }
 
 fn search_spotify(search_param: &str, token: &str) -> Subscription<Message> {
@@ -432,6 +1070,11 @@ fn search_spotify(search_param: &str, token: &str) -> Subscription<Message> {
     subscription::run_with_id(
         format!("search-{search}"),
         stream::once(async move {
+            // Debounce: if another keystroke changes the search term before this
+            // fires, iced drops this subscription (and the pending sleep) in
+            // favour of the new one, so only the last keystroke's search runs.
+            tokio::time::sleep(Duration::from_millis(300)).await;
+
             eprintln!("sending search {search}");
 
             let mut url = Url::parse("https://api.spotify.com/v1/search").unwrap();
@@ -441,7 +1084,7 @@ fn search_spotify(search_param: &str, token: &str) -> Subscription<Message> {
                 .append_pair("market", "GB")
                 .append_pair("limit", "20");
 
-            let res = reqwest::Client::new()
+            let res = network::client()
                 .get(url)
                 .header("Authorization", format!("Bearer {token}"))
                 .send()
@@ -538,6 +1181,296 @@ fn search_spotify(search_param: &str, token: &str) -> Subscription<Message> {
     )
 }
 
+fn browse_spotify(token: &str) -> Subscription<Message> {
+    let token = token.to_string();
+
+    subscription::run_with_id(
+        "browse",
+        stream::once(async move {
+            let client = network::client();
+            let fetch = |url: &'static str| {
+                let client = client.clone();
+                let token = token.clone();
+                async move {
+                    client
+                        .get(url)
+                        .header("Authorization", format!("Bearer {token}"))
+                        .send()
+                        .await
+                        .unwrap()
+                        .text()
+                        .await
+                        .unwrap()
+                }
+            };
+
+            let (playlists, albums, tracks) = future::join3(
+                fetch("https://api.spotify.com/v1/me/playlists?limit=50"),
+                fetch("https://api.spotify.com/v1/me/albums?limit=50"),
+                fetch("https://api.spotify.com/v1/me/tracks?limit=50"),
+            )
+            .await;
+
+            (
+                Yoke::attach_to_cart(playlists, |s| serde_json::from_str(s).unwrap()),
+                Yoke::attach_to_cart(albums, |s| serde_json::from_str(s).unwrap()),
+                Yoke::attach_to_cart(tracks, |s| serde_json::from_str(s).unwrap()),
+            )
+        })
+        .flat_map(
+            |(playlists, albums, tracks): (
+                Yoke<SpotifyPlaylistsResponse<'static>, String>,
+                Yoke<SpotifySavedAlbumsResponse<'static>, String>,
+                Yoke<SpotifySavedTracksResponse<'static>, String>,
+            )| {
+                if let Some(error) = playlists
+                    .get()
+                    .error
+                    .as_ref()
+                    .or(albums.get().error.as_ref())
+                    .or(tracks.get().error.as_ref())
+                {
+                    return Either::Left(stream::iter(iter::once(
+                        Message::SpotifyBrowseResultError(error.message.to_string()),
+                    )));
+                }
+
+                let results = FuturesUnordered::new();
+
+                for playlist in &playlists.get().items {
+                    let image_url = playlist.images.last().map(|v| v.url.to_string());
+                    let name = playlist.name.to_string();
+                    let uri = playlist.uri.to_string();
+
+                    results.push(tokio::spawn(
+                        async move {
+                            let image = load_album_art(image_url).await;
+                            SearchResult::playlist(image, name, uri)
+                        }
+                        .boxed(),
+                    ));
+                }
+
+                for saved in &albums.get().items {
+                    let image_url = saved.album.images.last().map(|v| v.url.to_string());
+                    let name = saved.album.name.to_string();
+                    let uri = saved.album.uri.to_string();
+
+                    results.push(tokio::spawn(
+                        async move {
+                            let image = load_album_art(image_url).await;
+                            SearchResult::album(image, name, uri)
+                        }
+                        .boxed(),
+                    ));
+                }
+
+                for saved in &tracks.get().items {
+                    let track = &saved.track;
+                    let image_url = track.album.images.last().map(|v| v.url.to_string());
+                    let track_name = track.name.to_string();
+                    let artist_name = track.artists.iter().map(|v| &v.name).join(", ");
+                    let uri = track.uri.to_string();
+
+                    results.push(tokio::spawn(
+                        async move {
+                            let image = load_album_art(image_url).await;
+                            SearchResult::track(image, track_name, artist_name, uri)
+                        }
+                        .boxed(),
+                    ));
+                }
+
+                Either::Right(
+                    results
+                        .filter_map(|v| future::ready(v.ok()))
+                        .map(Message::SpotifyBrowseResult),
+                )
+            },
+        ),
+    )
+}
+
+/// Fetches the Spotify Connect devices currently visible to this account
+/// (room speakers, TVs, phones, ...) so playback can be transferred between
+/// them from the [`DevicesState`] picker.
+fn spotify_devices(token: &str) -> Subscription<Message> {
+    let token = token.to_string();
+
+    subscription::run_with_id(
+        "devices",
+        stream::once(async move {
+            let text = network::client()
+                .get("https://api.spotify.com/v1/me/player/devices")
+                .header("Authorization", format!("Bearer {token}"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+
+            serde_json::from_str::<SpotifyDevicesResponse>(&text).unwrap_or(
+                SpotifyDevicesResponse {
+                    devices: vec![],
+                    error: Some(SpotifyErrorOwned {
+                        message: "Failed to parse Spotify response".to_string(),
+                    }),
+                },
+            )
+        })
+        .map(|res| match res.error {
+            Some(err) => Message::SpotifyDevicesResultError(err.message),
+            None => Message::SpotifyDevicesResult(res.devices),
+        }),
+    )
+}
+
+/// Transfers Spotify Connect playback to `device_id`, one of the devices
+/// returned by [`spotify_devices`].
+pub async fn transfer_playback(token: &str, device_id: String) {
+    let _ = network::client()
+        .put("https://api.spotify.com/v1/me/player")
+        .header("Authorization", format!("Bearer {token}"))
+        .json(&serde_json::json!({
+            "device_ids": [device_id],
+            "play": true,
+        }))
+        .send()
+        .await;
+}
+
+fn album_tracks(uri: &str, token: &str) -> Subscription<Message> {
+    let id = uri.rsplit(':').next().unwrap_or_default().to_string();
+    let token = token.to_string();
+
+    subscription::run_with_id(
+        format!("album-tracks-{uri}"),
+        stream::once(async move {
+            let url = format!("https://api.spotify.com/v1/albums/{id}/tracks?limit=50");
+
+            let res = network::client()
+                .get(url)
+                .header("Authorization", format!("Bearer {token}"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+
+            Yoke::attach_to_cart(res, |s| serde_json::from_str(s).unwrap())
+        })
+        .flat_map(|res: Yoke<SpotifyAlbumTracksResponse<'static>, String>| {
+            let res = res.get();
+
+            if let Some(error) = &res.error {
+                return Either::Left(stream::iter(iter::once(Message::DetailTrackResultError(
+                    error.message.to_string(),
+                ))));
+            }
+
+            let results = FuturesUnordered::new();
+
+            for track in &res.items {
+                let track_name = track.name.to_string();
+                let artist_name = track.artists.iter().map(|v| &v.name).join(", ");
+                let uri = track.uri.to_string();
+
+                results.push(tokio::spawn(
+                    async move {
+                        let image = load_album_art(None).await;
+                        SearchResult::track(image, track_name, artist_name, uri)
+                    }
+                    .boxed(),
+                ));
+            }
+
+            Either::Right(
+                results
+                    .filter_map(|v| future::ready(v.ok()))
+                    .map(Message::DetailTrackResult),
+            )
+        }),
+    )
+}
+
+fn artist_top_tracks(uri: &str, token: &str) -> Subscription<Message> {
+    let id = uri.rsplit(':').next().unwrap_or_default().to_string();
+    let token = token.to_string();
+
+    subscription::run_with_id(
+        format!("artist-top-tracks-{uri}"),
+        stream::once(async move {
+            let url = format!("https://api.spotify.com/v1/artists/{id}/top-tracks?market=GB");
+
+            let res = network::client()
+                .get(url)
+                .header("Authorization", format!("Bearer {token}"))
+                .send()
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap();
+
+            Yoke::attach_to_cart(res, |s| serde_json::from_str(s).unwrap())
+        })
+        .flat_map(
+            |res: Yoke<SpotifyArtistTopTracksResponse<'static>, String>| {
+                let res = res.get();
+
+                if let Some(error) = &res.error {
+                    return Either::Left(stream::iter(iter::once(
+                        Message::DetailTrackResultError(error.message.to_string()),
+                    )));
+                }
+
+                let results = FuturesUnordered::new();
+
+                for track in &res.tracks {
+                    let image_url = track.album.images.last().map(|v| v.url.to_string());
+                    let track_name = track.name.to_string();
+                    let artist_name = track.artists.iter().map(|v| &v.name).join(", ");
+                    let uri = track.uri.to_string();
+
+                    results.push(tokio::spawn(
+                        async move {
+                            let image = load_album_art(image_url).await;
+                            SearchResult::track(image, track_name, artist_name, uri)
+                        }
+                        .boxed(),
+                    ));
+                }
+
+                Either::Right(
+                    results
+                        .filter_map(|v| future::ready(v.ok()))
+                        .map(Message::DetailTrackResult),
+                )
+            },
+        ),
+    )
+}
+
+fn media_browse(
+    oracle: Arc<Oracle>,
+    speaker_id: &'static str,
+    content_id: Option<String>,
+    content_type: Option<String>,
+) -> Subscription<Message> {
+    subscription::run_with_id(
+        (speaker_id, content_id.clone(), content_type.clone()),
+        stream::once(async move {
+            oracle
+                .speaker(speaker_id)
+                .browse_media(content_id, content_type)
+                .await
+        })
+        .map(Message::MediaBrowseLoaded),
+    )
+}
+
 async fn load_album_art(image_url: Option<String>) -> Handle {
     if let Some(image_url) = image_url {
         load_image(image_url, identity).await
@@ -627,3 +1560,86 @@ pub struct SpotifyImage<'a> {
     #[serde(borrow)]
     url: Cow<'a, str>,
 }
+
+#[derive(Deserialize)]
+pub struct SpotifyDevicesResponse {
+    #[serde(default)]
+    devices: Vec<SpotifyDevice>,
+    error: Option<SpotifyErrorOwned>,
+}
+
+#[derive(Deserialize)]
+pub struct SpotifyErrorOwned {
+    message: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpotifyDevice {
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Yokeable)]
+pub struct SpotifyPlaylistsResponse<'a> {
+    #[serde(borrow, default)]
+    items: Vec<SpotifyPlaylist<'a>>,
+    #[serde(borrow, default)]
+    error: Option<SpotifyError<'a>>,
+}
+
+#[derive(Deserialize, Yokeable)]
+pub struct SpotifySavedAlbumsResponse<'a> {
+    #[serde(borrow, default)]
+    items: Vec<SpotifySavedAlbum<'a>>,
+    #[serde(borrow, default)]
+    error: Option<SpotifyError<'a>>,
+}
+
+#[derive(Deserialize)]
+pub struct SpotifySavedAlbum<'a> {
+    #[serde(borrow)]
+    album: SpotifyAlbum<'a>,
+}
+
+#[derive(Deserialize, Yokeable)]
+pub struct SpotifySavedTracksResponse<'a> {
+    #[serde(borrow, default)]
+    items: Vec<SpotifySavedTrack<'a>>,
+    #[serde(borrow, default)]
+    error: Option<SpotifyError<'a>>,
+}
+
+#[derive(Deserialize)]
+pub struct SpotifySavedTrack<'a> {
+    #[serde(borrow)]
+    track: SpotifyTrack<'a>,
+}
+
+#[derive(Deserialize, Yokeable)]
+pub struct SpotifyAlbumTracksResponse<'a> {
+    #[serde(borrow, default)]
+    items: Vec<SpotifyAlbumTrack<'a>>,
+    #[serde(borrow, default)]
+    error: Option<SpotifyError<'a>>,
+}
+
+#[derive(Deserialize)]
+pub struct SpotifyAlbumTrack<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(borrow)]
+    artists: Vec<SpotifyArtist<'a>>,
+    #[serde(borrow)]
+    uri: Cow<'a, str>,
+}
+
+#[derive(Deserialize, Yokeable)]
+pub struct SpotifyArtistTopTracksResponse<'a> {
+    #[serde(borrow, default)]
+    tracks: Vec<SpotifyTrack<'a>>,
+    #[serde(borrow, default)]
+    error: Option<SpotifyError<'a>>,
+}
@@ -5,12 +5,12 @@ use iced::{
     theme,
     widget::{
         column, component, container, container::Appearance, image, image::Handle, mouse_area, row,
-        text, Column, Component,
+        text, Column, Component, Row,
     },
     Alignment, Background, Color, Element, Length, Renderer, Theme,
 };
 
-use crate::widgets::spinner::CupertinoSpinner;
+use crate::{theme, widgets::spinner::CupertinoSpinner};
 
 pub fn search<M: Clone + 'static>(theme: Theme, results: SearchState<'_>) -> Search<'_, M> {
     Search {
@@ -21,13 +21,13 @@ pub fn search<M: Clone + 'static>(theme: Theme, results: SearchState<'_>) -> Sea
 }
 
 pub struct Search<'a, M> {
-    on_track_press: Option<fn(String) -> M>,
+    on_track_press: Option<fn(String, ResultMetadata) -> M>,
     theme: Theme,
     results: SearchState<'a>,
 }
 
 impl<M> Search<'_, M> {
-    pub fn on_track_press(mut self, f: fn(String) -> M) -> Self {
+    pub fn on_track_press(mut self, f: fn(String, ResultMetadata) -> M) -> Self {
         self.on_track_press = Some(f);
         self
     }
@@ -39,9 +39,9 @@ impl<M: Clone + 'static> Component<M, Renderer> for Search<'_, M> {
 
     fn update(&mut self, state: &mut Self::State, event: Self::Event) -> Option<M> {
         match event {
-            Event::OnTrackPress(id) => {
+            Event::OnTrackPress(id, metadata) => {
                 state.pressing = None;
-                self.on_track_press.map(|f| (f)(id))
+                self.on_track_press.map(|f| (f)(id, metadata))
             }
             Event::OnDown(i) => {
                 state.pressing = Some(i);
@@ -51,29 +51,63 @@ impl<M: Clone + 'static> Component<M, Renderer> for Search<'_, M> {
                 state.pressing = None;
                 None
             }
+            Event::OnFilterChange(filter) => {
+                state.filter = filter;
+                None
+            }
         }
     }
 
     fn view(&self, state: &Self::State) -> Element<'_, Self::Event, Renderer> {
         match self.results {
             SearchState::Ready(results) if !results.is_empty() => {
-                let mut col = Column::new();
+                let sections: &[ResultFilter] = if state.filter == ResultFilter::All {
+                    &[
+                        ResultFilter::Track,
+                        ResultFilter::Album,
+                        ResultFilter::Artist,
+                        ResultFilter::Playlist,
+                    ]
+                } else {
+                    std::slice::from_ref(&state.filter)
+                };
+
+                let mut col = Column::new().spacing(10);
+
+                for &section in sections {
+                    let mut matching = results
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, result)| section.matches(&result.metadata))
+                        .peekable();
 
-                for (i, result) in results.iter().enumerate() {
-                    let pressing = state.pressing == Some(i);
+                    if matching.peek().is_none() {
+                        continue;
+                    }
 
-                    let track = mouse_area(search_item_container(
-                        result_card(result, &self.theme),
-                        pressing,
-                    ))
-                    .on_press(Event::OnDown(i))
-                    .on_release(Event::OnTrackPress(result.uri.to_string()))
-                    .on_cancel(Event::OnCancel);
+                    if state.filter == ResultFilter::All {
+                        col = col.push(text(section.label()).size(16));
+                    }
 
-                    col = col.push(track);
+                    for (i, result) in matching {
+                        let pressing = state.pressing == Some(i);
+
+                        let track = mouse_area(search_item_container(
+                            result_card(result, &self.theme),
+                            pressing,
+                        ))
+                        .on_press(Event::OnDown(i))
+                        .on_release(Event::OnTrackPress(
+                            result.uri.to_string(),
+                            result.metadata.clone(),
+                        ))
+                        .on_cancel(Event::OnCancel);
+
+                        col = col.push(track);
+                    }
                 }
 
-                Element::from(col.spacing(10))
+                Element::from(column![filter_chip_row(state.filter), col].spacing(10))
             }
             SearchState::Ready(_) => Element::from(search_item_container(
                 container(text("No results found"))
@@ -97,9 +131,28 @@ impl<M: Clone + 'static> Component<M, Renderer> for Search<'_, M> {
     }
 }
 
+fn filter_chip_row(active: ResultFilter) -> Element<'static, Event, Renderer> {
+    ResultFilter::ALL
+        .into_iter()
+        .fold(Row::new().spacing(20), |row, filter| {
+            let label = text(filter.label()).size(14).style(if filter == active {
+                Color::BLACK
+            } else {
+                Color {
+                    a: 0.5,
+                    ..Color::BLACK
+                }
+            });
+
+            row.push(mouse_area(label).on_press(Event::OnFilterChange(filter)))
+        })
+        .into()
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct State {
     pressing: Option<usize>,
+    filter: ResultFilter,
 }
 
 impl<'a, M: 'static + Clone> From<Search<'a, M>> for Element<'a, M, Renderer> {
@@ -111,9 +164,51 @@ impl<'a, M: 'static + Clone> From<Search<'a, M>> for Element<'a, M, Renderer> {
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug)]
 pub enum Event {
-    OnTrackPress(String),
+    OnTrackPress(String, ResultMetadata),
     OnDown(usize),
     OnCancel,
+    OnFilterChange(ResultFilter),
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultFilter {
+    #[default]
+    All,
+    Track,
+    Album,
+    Artist,
+    Playlist,
+}
+
+impl ResultFilter {
+    const ALL: [Self; 5] = [
+        Self::All,
+        Self::Track,
+        Self::Album,
+        Self::Artist,
+        Self::Playlist,
+    ];
+
+    fn matches(self, metadata: &ResultMetadata) -> bool {
+        matches!(
+            (self, metadata),
+            (Self::All, _)
+                | (Self::Track, ResultMetadata::Track(_))
+                | (Self::Album, ResultMetadata::Album)
+                | (Self::Artist, ResultMetadata::Artist)
+                | (Self::Playlist, ResultMetadata::Playlist)
+        )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Track => "Tracks",
+            Self::Album => "Albums",
+            Self::Artist => "Artists",
+            Self::Playlist => "Playlists",
+        }
+    }
 }
 
 fn result_card<M: 'static>(result: &SearchResult, _style: &Theme) -> Element<'static, M, Renderer> {
@@ -152,6 +247,23 @@ impl container::StyleSheet for SearchItemContainer {
     type Style = Theme;
 
     fn appearance(&self, _style: &Self::Style) -> Appearance {
+        if theme::high_contrast() {
+            return Appearance {
+                text_color: Some(Color::BLACK),
+                background: Some(Background::Color(Color::WHITE)),
+                border_radius: 20.0.into(),
+                border_width: 2.0,
+                border_color: if self.0 {
+                    Color::BLACK
+                } else {
+                    Color {
+                        a: 0.4,
+                        ..Color::BLACK
+                    }
+                },
+            };
+        }
+
         let base = Appearance {
             text_color: Some(Color {
                 a: 0.9,
@@ -235,6 +347,18 @@ impl SearchResult {
             metadata: ResultMetadata::Album,
         }
     }
+
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn image(&self) -> Handle {
+        self.image.clone()
+    }
 }
 
 #[derive(Debug, Clone, Hash)]
@@ -0,0 +1,162 @@
+use std::{any::TypeId, sync::Arc};
+
+use iced::{
+    subscription,
+    widget::{button, checkbox, column, container, row, scrollable, text, text_input},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::oracle::{Oracle, ScheduledScene};
+
+/// Scene triggers set from the panel itself ("turn on Evening scene at
+/// 18:30"), for people who'd rather not write an HA automation for
+/// something this simple. See [`crate::oracle::ScheduledScene`].
+#[derive(Debug)]
+pub struct Scheduler {
+    oracle: Arc<Oracle>,
+    scenes: Vec<ScheduledScene>,
+    new_name: String,
+    new_scene_entity_id: String,
+    new_time: String,
+}
+
+impl Scheduler {
+    pub fn new(oracle: Arc<Oracle>) -> Self {
+        Self {
+            scenes: oracle.scheduled_scenes(),
+            new_name: String::new(),
+            new_scene_entity_id: String::new(),
+            new_time: String::new(),
+            oracle,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps, clippy::needless_pass_by_value)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::ScenesChanged => {
+                self.scenes = self.oracle.scheduled_scenes();
+                None
+            }
+            Message::NewNameChanged(v) => {
+                self.new_name = v;
+                None
+            }
+            Message::NewSceneEntityIdChanged(v) => {
+                self.new_scene_entity_id = v;
+                None
+            }
+            Message::NewTimeChanged(v) => {
+                self.new_time = v;
+                None
+            }
+            Message::AddScene => {
+                let name = std::mem::take(&mut self.new_name);
+                let scene_entity_id = std::mem::take(&mut self.new_scene_entity_id);
+                let time = std::mem::take(&mut self.new_time);
+
+                if name.trim().is_empty() || scene_entity_id.trim().is_empty() {
+                    None
+                } else {
+                    Some(Event::AddScene(name, scene_entity_id, time))
+                }
+            }
+            Message::ToggleEnabled(id, enabled) => {
+                if let Some(scene) = self.scenes.iter_mut().find(|scene| scene.id == id) {
+                    scene.enabled = enabled;
+                }
+                Some(Event::SetEnabled(id, enabled))
+            }
+            Message::RemoveScene(id) => Some(Event::RemoveScene(id)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Scheduler").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let scenes = self
+            .scenes
+            .iter()
+            .fold(column![].spacing(10), |col, scene| {
+                let id = scene.id;
+
+                col.push(
+                    container(
+                        row![
+                            checkbox("", scene.enabled, move |v| Message::ToggleEnabled(id, v)),
+                            text(scene.name.as_ref()).size(20),
+                            text(scene.scene_entity_id.as_ref()).size(14),
+                            text(format!("{:02}:{:02}", scene.hour, scene.minute)).size(20),
+                            button("Remove").on_press(Message::RemoveScene(id)),
+                        ]
+                        .spacing(10)
+                        .align_items(Alignment::Center),
+                    )
+                    .padding(10)
+                    .width(Length::Fill),
+                )
+            });
+
+        let add_row = row![
+            text_input("Name, e.g. Evening", &self.new_name)
+                .on_input(Message::NewNameChanged)
+                .width(Length::FillPortion(2)),
+            text_input("scene.evening", &self.new_scene_entity_id)
+                .on_input(Message::NewSceneEntityIdChanged)
+                .width(Length::FillPortion(2)),
+            text_input("18:30", &self.new_time)
+                .on_input(Message::NewTimeChanged)
+                .on_submit(Message::AddScene)
+                .width(Length::FillPortion(1)),
+            button("Add").on_press(Message::AddScene),
+        ]
+        .spacing(10)
+        .align_items(Alignment::Center);
+
+        container(
+            column![header, scrollable(scenes).height(Length::Fill), add_row]
+                .spacing(20)
+                .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        pub struct SchedulerSubscription;
+
+        subscription::run_with_id(
+            TypeId::of::<SchedulerSubscription>(),
+            iced::futures::StreamExt::map(self.oracle.subscribe_scheduled_scenes(), |()| {
+                Message::ScenesChanged
+            }),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    AddScene(String, String, String),
+    SetEnabled(u64, bool),
+    RemoveScene(u64),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    ScenesChanged,
+    NewNameChanged(String),
+    NewSceneEntityIdChanged(String),
+    NewTimeChanged(String),
+    AddScene,
+    ToggleEnabled(u64, bool),
+    RemoveScene(u64),
+}
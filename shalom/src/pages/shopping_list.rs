@@ -0,0 +1,127 @@
+use std::{any::TypeId, sync::Arc};
+
+use iced::{
+    subscription,
+    widget::{button, checkbox, column, container, row, scrollable, text, text_input},
+    Alignment, Element, Length, Renderer, Subscription,
+};
+
+use crate::oracle::{Oracle, ShoppingListItem};
+
+#[derive(Debug)]
+pub struct ShoppingList {
+    oracle: Arc<Oracle>,
+    items: Vec<ShoppingListItem>,
+    new_item: String,
+}
+
+impl ShoppingList {
+    pub fn new(oracle: Arc<Oracle>) -> Self {
+        Self {
+            items: oracle.shopping_list(),
+            new_item: String::new(),
+            oracle,
+        }
+    }
+
+    #[allow(clippy::unnecessary_wraps, clippy::needless_pass_by_value)]
+    pub fn update(&mut self, event: Message) -> Option<Event> {
+        match event {
+            Message::Exit => Some(Event::Exit),
+            Message::ItemsChanged => {
+                self.items = self.oracle.shopping_list();
+                None
+            }
+            Message::NewItemChanged(v) => {
+                self.new_item = v;
+                None
+            }
+            Message::AddItem => {
+                let name = std::mem::take(&mut self.new_item);
+                if name.trim().is_empty() {
+                    None
+                } else {
+                    Some(Event::AddItem(name))
+                }
+            }
+            Message::ToggleComplete(id, complete) => {
+                if let Some(item) = self.items.iter_mut().find(|v| v.id.as_ref() == id) {
+                    item.complete = complete;
+                }
+                Some(Event::SetComplete(id, complete))
+            }
+            Message::RemoveItem(id) => Some(Event::RemoveItem(id)),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, Message, Renderer> {
+        let header = row![
+            button("Back").on_press(Message::Exit),
+            text("Shopping List").size(40),
+        ]
+        .spacing(20)
+        .align_items(Alignment::Center);
+
+        let items = self.items.iter().fold(column![].spacing(10), |col, item| {
+            let id: Box<str> = item.id.clone();
+
+            col.push(
+                row![
+                    checkbox(item.name.as_ref(), item.complete, move |v| {
+                        Message::ToggleComplete(id.clone(), v)
+                    }),
+                    button("Remove").on_press(Message::RemoveItem(item.id.clone())),
+                ]
+                .spacing(10)
+                .align_items(Alignment::Center),
+            )
+        });
+
+        let add_row = row![
+            text_input("Add an item...", &self.new_item)
+                .on_input(Message::NewItemChanged)
+                .on_submit(Message::AddItem)
+                .width(Length::Fill),
+            button("Add").on_press(Message::AddItem),
+        ]
+        .spacing(10);
+
+        container(
+            column![header, scrollable(items).height(Length::Fill), add_row]
+                .spacing(20)
+                .padding(40),
+        )
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+
+    pub fn subscription(&self) -> Subscription<Message> {
+        pub struct ShoppingListSubscription;
+
+        subscription::run_with_id(
+            TypeId::of::<ShoppingListSubscription>(),
+            iced::futures::StreamExt::map(self.oracle.subscribe_shopping_list(), |()| {
+                Message::ItemsChanged
+            }),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Event {
+    Exit,
+    AddItem(String),
+    SetComplete(Box<str>, bool),
+    RemoveItem(Box<str>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Message {
+    Exit,
+    ItemsChanged,
+    NewItemChanged(String),
+    AddItem,
+    ToggleComplete(Box<str>, bool),
+    RemoveItem(Box<str>),
+}
@@ -0,0 +1,99 @@
+//! A tiny, unauthenticated HTTP server for driving the panel from Home
+//! Assistant automations — open a room, show a camera, toggle the screen.
+//! Only compiled in with the `remote-http` feature and only listens when
+//! [`crate::config::RemoteApiConfig::enabled`] is set, since it has no
+//! authentication of its own.
+//!
+//! This hand-rolls just enough of HTTP/1.1 to read a request line and write
+//! a status line back, rather than pulling in a full server framework for
+//! three GET endpoints:
+//!
+//! - `GET /room/<room-id>` — navigates to that room
+//! - `GET /camera/<entity-id>` — opens that camera's detail view
+//! - `GET /screen/on` / `GET /screen/off` — sets the backlight fully on/off
+
+use std::{any::TypeId, net::SocketAddr};
+
+use iced::{subscription, Subscription};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    OpenRoom(Box<str>),
+    ShowCamera(Box<str>),
+    ScreenOn,
+    ScreenOff,
+}
+
+pub fn subscription(port: u16) -> Subscription<Command> {
+    struct RemoteHttpApi;
+
+    subscription::channel(
+        TypeId::of::<RemoteHttpApi>(),
+        16,
+        move |mut output| async move {
+            let addr = SocketAddr::from(([0, 0, 0, 0], port));
+
+            let listener = match TcpListener::bind(addr).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    eprintln!("remote-http: failed to bind {addr}: {err}");
+                    std::future::pending().await
+                }
+            };
+
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    continue;
+                };
+
+                if let Some(command) = handle_connection(stream).await {
+                    let _res = iced::futures::SinkExt::send(&mut output, command).await;
+                }
+            }
+        },
+    )
+}
+
+async fn handle_connection(mut stream: TcpStream) -> Option<Command> {
+    let mut request_line = String::new();
+
+    {
+        let mut reader = BufReader::new(&mut stream);
+        reader.read_line(&mut request_line).await.ok()?;
+    }
+
+    let path = request_line.split_whitespace().nth(1)?;
+    let command = parse_command(path);
+
+    let (status, body) = if command.is_some() {
+        ("200 OK", "ok")
+    } else {
+        ("404 Not Found", "unknown command")
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _res = stream.write_all(response.as_bytes()).await;
+
+    command
+}
+
+fn parse_command(path: &str) -> Option<Command> {
+    if let Some(id) = path.strip_prefix("/room/") {
+        Some(Command::OpenRoom(id.into()))
+    } else if let Some(id) = path.strip_prefix("/camera/") {
+        Some(Command::ShowCamera(id.into()))
+    } else {
+        match path {
+            "/screen/on" => Some(Command::ScreenOn),
+            "/screen/off" => Some(Command::ScreenOff),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Resolves the Home Assistant and Spotify access tokens without requiring
+//! either to sit in `config.toml` verbatim. Checked in order: an environment
+//! variable, then the OS keyring (with the `keyring` feature enabled), then
+//! whatever plaintext value is already in the config. This lets a
+//! `config.toml` be shared or checked into dotfiles without leaking a
+//! credential, while a headless Pi with no keyring daemon can still fall
+//! back to the plaintext field.
+
+use crate::config::Config;
+
+const HOME_ASSISTANT_TOKEN_ENV: &str = "SHALOM_HOME_ASSISTANT_TOKEN";
+const SPOTIFY_TOKEN_ENV: &str = "SHALOM_SPOTIFY_TOKEN";
+
+/// Service name the tokens are filed under in the OS keyring.
+const KEYRING_SERVICE: &str = "shalom";
+
+/// Overwrites `config`'s tokens in place with whatever the environment or OS
+/// keyring supplies, falling back to the value already parsed from
+/// `config.toml`.
+pub async fn resolve(config: &mut Config) {
+    config.home_assistant.token = resolve_token(
+        HOME_ASSISTANT_TOKEN_ENV,
+        "home-assistant",
+        &config.home_assistant.token,
+    )
+    .await;
+
+    config.spotify.token = resolve_token(SPOTIFY_TOKEN_ENV, "spotify", &config.spotify.token).await;
+}
+
+async fn resolve_token(
+    env_var: &'static str,
+    keyring_user: &'static str,
+    configured: &str,
+) -> String {
+    if let Ok(token) = std::env::var(env_var) {
+        return token;
+    }
+
+    if let Some(token) = keyring_token(keyring_user).await {
+        return token;
+    }
+
+    configured.to_string()
+}
+
+#[cfg(feature = "keyring")]
+async fn keyring_token(user: &'static str) -> Option<String> {
+    tokio::task::spawn_blocking(move || {
+        keyring::Entry::new(KEYRING_SERVICE, user)
+            .ok()?
+            .get_password()
+            .ok()
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
+#[cfg(not(feature = "keyring"))]
+async fn keyring_token(_user: &'static str) -> Option<String> {
+    None
+}
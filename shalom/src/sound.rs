@@ -0,0 +1,127 @@
+//! Optional UI sound effects (click/confirm/error). No audio assets ship
+//! with the panel, so each sound is a short synthesized tone rather than a
+//! bundled `.wav`/`.ogg` file — see [`play_tone`].
+//!
+//! Playback is entirely best-effort: a panel might be running on a machine
+//! with no audio device attached, so every failure here is swallowed
+//! silently rather than surfaced, the same way a failed Home Assistant
+//! service call is swallowed by [`crate::oracle::Oracle`].
+
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use rodio::{source::SineWave, OutputStream, OutputStreamHandle, Sink, Source};
+
+use crate::config::SoundConfig;
+
+static CONFIG: OnceCell<SoundConfig> = OnceCell::new();
+static STREAM_HANDLE: OnceCell<Option<OutputStreamHandle>> = OnceCell::new();
+
+/// Applies the `sound` section of `config.toml`. Must be called once, as
+/// early as possible, before any `play_*` function below is used.
+pub fn configure(config: SoundConfig) {
+    let _ = CONFIG.set(config);
+}
+
+fn config() -> SoundConfig {
+    CONFIG.get().cloned().unwrap_or_default()
+}
+
+/// Opens the default audio output device the first time a sound is played,
+/// then reuses it. The `OutputStream` half has to stay alive for as long as
+/// playback is wanted, so it's leaked here rather than dropped at the end of
+/// each `play_*` call.
+fn stream_handle() -> Option<OutputStreamHandle> {
+    STREAM_HANDLE
+        .get_or_init(|| {
+            let (stream, handle) = OutputStream::try_default().ok()?;
+            std::mem::forget(stream);
+            Some(handle)
+        })
+        .clone()
+}
+
+/// Plays a single sine tone at `frequency` for `duration`, unless muted or
+/// `volume` is zero. Failures (no output device, no default sound server,
+/// ...) are silently ignored.
+fn play_tone(frequency: f32, duration: Duration, volume: f32) {
+    let config = config();
+
+    if config.mute || volume <= 0.0 {
+        return;
+    }
+
+    let Some(handle) = stream_handle() else {
+        return;
+    };
+
+    let Ok(sink) = Sink::try_new(&handle) else {
+        return;
+    };
+
+    sink.set_volume(volume.clamp(0.0, 1.0));
+    sink.append(
+        SineWave::new(frequency)
+            .take_duration(duration)
+            .amplify(0.2),
+    );
+    sink.detach();
+}
+
+/// A short, high click for toggle presses (lights, switches, covers, ...).
+pub fn play_click() {
+    play_tone(880.0, Duration::from_millis(60), config().click_volume);
+}
+
+/// A slightly lower tone for opening a context menu (light control, quick
+/// settings, the PIN pad, the camera detail view).
+pub fn play_confirm() {
+    play_tone(660.0, Duration::from_millis(90), config().confirm_volume);
+}
+
+/// A low buzz for a failed Home Assistant service call.
+///
+/// Not wired up anywhere yet: every [`crate::oracle::Oracle`] service-call
+/// method returns `()` rather than a `Result`, so `main.rs` has no failure
+/// signal to hang this off yet. Left in place so wiring it up is a one-line
+/// change once that plumbing exists.
+#[allow(dead_code)]
+pub fn play_error() {
+    play_tone(220.0, Duration::from_millis(200), config().error_volume);
+}
+
+/// Three short beeps for a local kitchen timer finishing, since it has no
+/// Home Assistant speaker to announce through. Queued on a single [`Sink`]
+/// so the beeps play back to back without blocking the caller.
+pub fn play_timer() {
+    let config = config();
+
+    if config.mute || config.timer_volume <= 0.0 {
+        return;
+    }
+
+    let Some(handle) = stream_handle() else {
+        return;
+    };
+
+    let Ok(sink) = Sink::try_new(&handle) else {
+        return;
+    };
+
+    sink.set_volume(config.timer_volume.clamp(0.0, 1.0));
+
+    for _ in 0..3 {
+        sink.append(
+            SineWave::new(1000.0)
+                .take_duration(Duration::from_millis(150))
+                .amplify(0.2),
+        );
+        sink.append(
+            SineWave::new(0.0)
+                .take_duration(Duration::from_millis(200))
+                .amplify(0.0),
+        );
+    }
+
+    sink.detach();
+}
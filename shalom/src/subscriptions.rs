@@ -1,13 +1,57 @@
-use std::num::NonZeroUsize;
+use std::{
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
 
-use iced::{futures::stream, subscription, widget::image, Subscription};
+use iced::{
+    futures::stream,
+    subscription,
+    widget::{image, svg},
+    Subscription,
+};
 use lru::LruCache;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use parking_lot::Mutex;
 use reqwest::IntoUrl;
+use serde::Deserialize;
 use url::Url;
 
-use crate::config::FANART_PROJECT_KEY;
+use crate::{
+    config::{CacheConfig, FANART_PROJECT_KEY},
+    network,
+};
+
+/// MusicBrainz asks that unauthenticated clients keep requests to roughly one
+/// per second: <https://musicbrainz.org/doc/MusicBrainz_API/Rate_Limiting>.
+const MUSICBRAINZ_RATE_LIMIT: Duration = Duration::from_secs(1);
+
+static CACHE_CONFIG: OnceCell<CacheConfig> = OnceCell::new();
+
+/// Applies the configured cache sizes. Must be called before any subscription
+/// in this module runs for the values to take effect, since the caches below
+/// size themselves once, on first use.
+pub fn configure(config: &CacheConfig) {
+    let _ = CACHE_CONFIG.set(config.clone());
+}
+
+fn cache_config() -> CacheConfig {
+    CACHE_CONFIG.get().cloned().unwrap_or_default()
+}
+
+async fn respect_musicbrainz_rate_limit() {
+    static LAST_REQUEST: Lazy<tokio::sync::Mutex<Option<Instant>>> =
+        Lazy::new(|| tokio::sync::Mutex::new(None));
+
+    let mut last_request = LAST_REQUEST.lock().await;
+
+    if let Some(elapsed) = last_request.map(|v| v.elapsed()) {
+        if elapsed < MUSICBRAINZ_RATE_LIMIT {
+            tokio::time::sleep(MUSICBRAINZ_RATE_LIMIT - elapsed).await;
+        }
+    }
+
+    *last_request = Some(Instant::now());
+}
 
 #[derive(Debug)]
 pub enum MaybePendingImage {
@@ -24,9 +68,11 @@ impl MaybePendingImage {
     }
 }
 
+type PostProcess = fn(::image::RgbaImage) -> ::image::RgbaImage;
+
 pub fn download_image<M: 'static>(
     url: Url,
-    post_process: fn(::image::RgbaImage) -> ::image::RgbaImage,
+    post_process: PostProcess,
     resp: impl FnOnce(image::Handle) -> M + Send + 'static,
 ) -> Subscription<M> {
     subscription::run_with_id(
@@ -39,21 +85,89 @@ pub fn download_image<M: 'static>(
     )
 }
 
-pub async fn load_image<T: IntoUrl>(
-    url: T,
-    post_process: fn(::image::RgbaImage) -> ::image::RgbaImage,
-) -> image::Handle {
-    static CACHE: Lazy<Mutex<LruCache<Url, image::Handle>>> =
-        Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(50).unwrap())));
-    static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+/// A decoded-image cache bounded by both an item count and a soft memory
+/// budget. Images are evicted least-recently-used first once the budget is
+/// exceeded, even if the item-count limit hasn't been reached yet.
+///
+/// Entries are keyed by `(url, post_process)` rather than just `url`, so a
+/// track's blurred/darkened background and, say, its plain album art don't
+/// clobber each other in the (unlikely but possible) case they share a URL.
+struct ImageCache {
+    entries: LruCache<(Url, PostProcess), (image::Handle, usize)>,
+    total_bytes: usize,
+}
+
+impl ImageCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: LruCache::new(NonZeroUsize::new(capacity.max(1)).unwrap()),
+            total_bytes: 0,
+        }
+    }
+
+    fn get(&mut self, url: &Url, post_process: PostProcess) -> Option<image::Handle> {
+        self.entries
+            .get(&(url.clone(), post_process))
+            .map(|(handle, _)| handle.clone())
+    }
+
+    fn insert(
+        &mut self,
+        url: Url,
+        post_process: PostProcess,
+        handle: image::Handle,
+        bytes: usize,
+        memory_budget: usize,
+    ) {
+        if let Some((_, evicted_bytes)) = self.entries.push((url, post_process), (handle, bytes)) {
+            self.total_bytes -= evicted_bytes;
+        }
+        self.total_bytes += bytes;
+
+        while self.total_bytes > memory_budget {
+            let Some((_, (_, evicted_bytes))) = self.entries.pop_lru() else {
+                break;
+            };
+            self.total_bytes -= evicted_bytes;
+        }
+    }
+}
+
+/// Shrinks `image` so neither side exceeds `display.max-image-dimension`,
+/// preserving aspect ratio. A no-op if the image is already small enough,
+/// which is the common case for anything that isn't a fanart background.
+fn downscale_to_max_dimension(image: ::image::RgbaImage) -> ::image::RgbaImage {
+    let max_dimension = cache_config().max_image_dimension;
+    let (width, height) = image.dimensions();
+
+    if width.max(height) <= max_dimension {
+        return image;
+    }
+
+    let scale = f64::from(max_dimension) / f64::from(width.max(height));
+    let target_width = ((f64::from(width) * scale).round() as u32).max(1);
+    let target_height = ((f64::from(height) * scale).round() as u32).max(1);
+
+    ::image::imageops::resize(
+        &image,
+        target_width,
+        target_height,
+        ::image::imageops::FilterType::Triangle,
+    )
+}
+
+pub async fn load_image<T: IntoUrl>(url: T, post_process: PostProcess) -> image::Handle {
+    static CACHE: Lazy<Mutex<ImageCache>> =
+        Lazy::new(|| Mutex::new(ImageCache::new(cache_config().image_cache_size)));
+    static CLIENT: Lazy<reqwest::Client> = Lazy::new(network::client);
 
     let url = url.into_url().unwrap();
 
-    if let Some(handle) = CACHE.lock().get(&url) {
-        return handle.clone();
+    if let Some(handle) = CACHE.lock().get(&url, post_process) {
+        return handle;
     }
 
-    let bytes = CLIENT
+    let body = CLIENT
         .get(url.clone())
         .send()
         .await
@@ -62,40 +176,67 @@ pub async fn load_image<T: IntoUrl>(
         .await
         .unwrap();
 
-    let handle = tokio::task::spawn_blocking(move || {
+    let (handle, decoded_bytes) = tokio::task::spawn_blocking(move || {
         eprintln!("parsing image");
-        let img = ::image::load_from_memory(&bytes).unwrap();
+        let img = ::image::load_from_memory(&body).unwrap();
         eprintln!("post processing");
-        let data = post_process(img.into_rgba8());
+        let data = downscale_to_max_dimension(post_process(img.into_rgba8()));
         let (h, w) = data.dimensions();
-        image::Handle::from_pixels(h, w, data.into_raw())
+        let decoded_bytes = h as usize * w as usize * 4;
+        (
+            image::Handle::from_pixels(h, w, data.into_raw()),
+            decoded_bytes,
+        )
     })
     .await
     .unwrap();
 
-    CACHE.lock().push(url.clone(), handle.clone());
+    CACHE.lock().insert(
+        url,
+        post_process,
+        handle.clone(),
+        decoded_bytes,
+        cache_config().image_memory_budget_bytes,
+    );
 
     handle
 }
 
+#[derive(Debug, Deserialize)]
+struct MusicbrainzSearchResponse {
+    #[serde(default)]
+    artists: Vec<MusicbrainzArtist>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MusicbrainzArtist {
+    id: String,
+    #[serde(default)]
+    score: u32,
+}
+
 pub fn find_musicbrainz_artist<M: 'static>(
     artist: String,
-    to_msg: fn(String) -> M,
+    to_msg: fn(Option<String>) -> M,
 ) -> Subscription<M> {
-    static CACHE: Lazy<Mutex<LruCache<String, String>>> =
-        Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(10).unwrap())));
+    static CACHE: Lazy<Mutex<LruCache<String, Option<String>>>> = Lazy::new(|| {
+        Mutex::new(LruCache::new(
+            NonZeroUsize::new(cache_config().musicbrainz_cache_size.max(1)).unwrap(),
+        ))
+    });
 
     subscription::run_with_id(
         format!("musicbrainz-{artist}"),
         stream::once(async move {
             eprintln!("musicbrainz req");
 
-            if let Some(handle) = CACHE.lock().get(&artist) {
-                return (to_msg)(handle.to_string());
+            if let Some(id) = CACHE.lock().get(&artist) {
+                return (to_msg)(id.clone());
             }
 
-            // TODO
-            let client = reqwest::Client::builder()
+            respect_musicbrainz_rate_limit().await;
+
+            let client = network::client_builder()
                 .user_agent(format!(
                     "{}/{}",
                     env!("CARGO_PKG_NAME"),
@@ -104,7 +245,7 @@ pub fn find_musicbrainz_artist<M: 'static>(
                 .build()
                 .unwrap();
 
-            let resp: serde_json::Value = client
+            let resp: MusicbrainzSearchResponse = client
                 .get(format!(
                     "https://musicbrainz.org/ws/2/artist/?query={artist}&fmt=json",
                 ))
@@ -115,20 +256,17 @@ pub fn find_musicbrainz_artist<M: 'static>(
                 .await
                 .unwrap();
 
+            // Pick the best-scoring match instead of assuming the first hit is
+            // correct, and fall back to no match rather than panicking when
+            // MusicBrainz has nothing for this artist.
             let id = resp
-                .get("artists")
-                .unwrap()
-                .get(0)
-                .unwrap()
-                .get("id")
-                .unwrap()
-                .as_str()
-                .unwrap()
-                .to_string();
+                .artists
+                .into_iter()
+                .max_by_key(|artist| artist.score)
+                .map(|artist| artist.id);
 
             CACHE.lock().push(artist, id.clone());
 
-            // TODO: typing
             (to_msg)(id)
         }),
     )
@@ -143,7 +281,9 @@ pub fn find_fanart_urls<M: 'static>(
         stream::once(async move {
             eprintln!("fanart req");
 
-            let resp: serde_json::Value = reqwest::get(format!("http://webservice.fanart.tv/v3/music/{musicbrainz_id}?api_key={FANART_PROJECT_KEY}"))
+            let resp: serde_json::Value = network::client()
+                .get(format!("http://webservice.fanart.tv/v3/music/{musicbrainz_id}?api_key={FANART_PROJECT_KEY}"))
+                .send()
                 .await
                 .unwrap()
                 .json()
@@ -172,3 +312,160 @@ pub fn find_fanart_urls<M: 'static>(
         }),
     )
 }
+
+/// One headline pulled from a configured RSS/Atom feed.
+#[derive(Debug, Clone)]
+pub struct NewsHeadline {
+    pub source: Box<str>,
+    pub title: Box<str>,
+}
+
+/// Periodically re-fetches `feeds` and emits the combined, freshly-parsed
+/// headlines. Runs as a single long-lived subscription (rather than a
+/// [`Command`](iced::Command) fired by a timer message) so a slow or hung
+/// feed can't stack up duplicate in-flight requests.
+pub fn poll_news_feeds<M: 'static>(
+    feeds: Vec<Url>,
+    interval: Duration,
+    to_msg: fn(Vec<NewsHeadline>) -> M,
+) -> Subscription<M> {
+    subscription::run_with_id(
+        "news-ticker",
+        stream::unfold(feeds, move |feeds| async move {
+            let headlines = fetch_news_headlines(&feeds).await;
+            tokio::time::sleep(interval).await;
+            Some((headlines, feeds))
+        })
+        .map(to_msg),
+    )
+}
+
+async fn fetch_news_headlines(feeds: &[Url]) -> Vec<NewsHeadline> {
+    static CLIENT: Lazy<reqwest::Client> = Lazy::new(network::client);
+
+    let fetches = feeds.iter().map(|feed| async move {
+        let source = feed.host_str().unwrap_or("news").to_string();
+
+        // Feed availability and formatting are entirely outside our control,
+        // so a feed that's down or unparseable just contributes no
+        // headlines instead of taking the whole ticker with it.
+        let Ok(resp) = CLIENT.get(feed.clone()).send().await else {
+            return Vec::new();
+        };
+        let Ok(body) = resp.text().await else {
+            return Vec::new();
+        };
+
+        parse_feed_titles(&source, &body)
+    });
+
+    iced::futures::future::join_all(fetches)
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Home Assistant has no concept of a feed reader, and pulling in a full XML
+/// parser for a handful of `<title>` tags would be overkill, so this just
+/// scans for `<item>`/`<entry>` blocks (RSS and Atom respectively) and pulls
+/// the first `<title>` out of each. Feeds that don't roughly follow this
+/// shape simply yield no headlines.
+fn parse_feed_titles(source: &str, body: &str) -> Vec<NewsHeadline> {
+    ["item", "entry"]
+        .into_iter()
+        .flat_map(|tag| feed_blocks(body, tag))
+        .filter_map(extract_title)
+        .map(|title| NewsHeadline {
+            source: Box::from(source),
+            title: Box::from(title),
+        })
+        .collect()
+}
+
+fn feed_blocks<'a>(body: &'a str, tag: &str) -> Vec<&'a str> {
+    let open = format!("<{tag}");
+    let close = format!("</{tag}>");
+    let mut blocks = Vec::new();
+    let mut rest = body;
+
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start..];
+        let Some(end) = after_open.find(&close) else {
+            break;
+        };
+
+        blocks.push(&after_open[..end + close.len()]);
+        rest = &after_open[end + close.len()..];
+    }
+
+    blocks
+}
+
+fn extract_title(block: &str) -> Option<String> {
+    let start = block.find("<title")?;
+    let after_tag = &block[start..];
+    let tag_end = after_tag.find('>')? + 1;
+    let content_end = after_tag.find("</title>")?;
+    let raw = after_tag[tag_end..content_end].trim();
+    let raw = raw
+        .strip_prefix("<![CDATA[")
+        .and_then(|v| v.strip_suffix("]]>"))
+        .unwrap_or(raw)
+        .trim();
+
+    Some(unescape_xml_entities(raw))
+}
+
+fn unescape_xml_entities(s: &str) -> String {
+    s.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+}
+
+/// Strips the `mdi:` prefix off an entity registry `icon` string, e.g.
+/// `mdi:sofa` -> `sofa`. Icons from other namespaces (or without one) aren't
+/// Material Design Icons, so there's nothing for us to fetch.
+pub fn mdi_icon_name(icon: &str) -> Option<&str> {
+    icon.strip_prefix("mdi:")
+}
+
+/// Fetches a Material Design Icon by name and caches the parsed SVG handle.
+/// The icon set is too large to bundle alongside the icons in `theme::Icon`,
+/// so entity-specific icons (`mdi:sofa`, `mdi:television`, ...) are pulled
+/// from the MDI CDN on demand instead.
+pub fn find_mdi_icon<M: 'static>(
+    name: String,
+    to_msg: impl FnOnce(svg::Handle) -> M + Send + 'static,
+) -> Subscription<M> {
+    static CACHE: Lazy<Mutex<LruCache<String, svg::Handle>>> =
+        Lazy::new(|| Mutex::new(LruCache::new(NonZeroUsize::new(200).unwrap())));
+
+    subscription::run_with_id(
+        format!("mdi-icon-{name}"),
+        stream::once(async move {
+            if let Some(handle) = CACHE.lock().get(&name) {
+                return (to_msg)(handle.clone());
+            }
+
+            let bytes = network::client()
+                .get(format!(
+                    "https://cdn.jsdelivr.net/npm/@mdi/svg@latest/svg/{name}.svg"
+                ))
+                .send()
+                .await
+                .unwrap()
+                .bytes()
+                .await
+                .unwrap();
+
+            let handle = svg::Handle::from_memory(bytes.to_vec());
+
+            CACHE.lock().push(name, handle.clone());
+
+            (to_msg)(handle)
+        }),
+    )
+}
@@ -1,18 +1,163 @@
+use std::{
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
 use ::image::{imageops, GenericImageView, Pixel, Rgba, RgbaImage};
+use atomic::Atomic;
 use iced::{
-    advanced::svg::Handle,
+    advanced::{image::Data, svg::Handle, Shell},
     mouse::Cursor,
     widget::{
         canvas,
         canvas::{Cache, Geometry, LineDash, Path, Stroke, Style},
         image, svg, Canvas,
     },
+    window::RedrawRequest,
     Color, Point, Rectangle, Renderer, Theme,
 };
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use stackblur_iter::imgref::Img;
 use usvg::{tiny_skia_path::PathSegment, NodeKind, Transform, TreeParsing};
 
+use crate::hass_client::responses::WeatherCondition;
+
+/// Below this window width we assume a small touch panel (e.g. a 7" 800x480
+/// display) rather than a desktop monitor, and scale the UI down accordingly.
+const SMALL_DISPLAY_WIDTH: u32 = 800;
+const SMALL_DISPLAY_SCALE: f32 = 0.6;
+
+static SCALE_OVERRIDE: OnceCell<Option<f32>> = OnceCell::new();
+static AUTO_SCALE: Atomic<f32> = Atomic::new(1.0);
+static HIGH_CONTRAST: Atomic<bool> = Atomic::new(false);
+static FONT_SCALE: Atomic<f32> = Atomic::new(1.0);
+static REDUCED_MOTION: Atomic<bool> = Atomic::new(false);
+
+/// A single tick's worth of animation time, used by [`reduced_motion`]
+/// widgets to jump an in-progress [`keyframe::AnimationSequence`] straight to
+/// its end instead of advancing it by the frame's real elapsed time.
+pub const INSTANT_ANIMATION_STEP: Duration = Duration::from_secs(3600);
+
+/// Redraw rate cap for widgets that animate every frame (the context menu's
+/// slide, toast dismissal, the header search's fade, the loading spinner).
+/// Uncapped, these would redraw at the display's full refresh rate, which is
+/// wasted GPU/CPU work on a thermally-limited Pi that nobody can see the
+/// difference from.
+const MAX_FPS: u32 = 30;
+
+/// Requests a redraw no sooner than [`MAX_FPS`] allows, instead of on every
+/// frame. Widgets driving a `keyframe::AnimationSequence` (or anything else
+/// that needs to keep redrawing while active) should call this instead of
+/// `shell.request_redraw(RedrawRequest::NextFrame)` directly.
+pub fn request_animation_frame<M>(shell: &mut Shell<'_, M>) {
+    shell.request_redraw(RedrawRequest::At(
+        Instant::now() + Duration::from_secs(1) / MAX_FPS,
+    ));
+}
+
+/// Applies the `display.scale` override from `config.toml`, if any. Must be
+/// called once, as early as possible, since layout code reads [`scale_factor`]
+/// on every `view()`.
+pub fn configure_scale_override(scale: Option<f32>) {
+    let _ = SCALE_OVERRIDE.set(scale);
+}
+
+/// Updates the auto-detected scale from the window's current width. Has no
+/// effect once a `display.scale` override is configured.
+pub fn report_window_width(width: u32) {
+    let auto = if width <= SMALL_DISPLAY_WIDTH {
+        SMALL_DISPLAY_SCALE
+    } else {
+        1.0
+    };
+
+    AUTO_SCALE.store(auto, Ordering::Relaxed);
+}
+
+/// The current global UI scale factor: the `display.scale` override if one
+/// was configured, otherwise a value auto-detected from the window's
+/// resolution (1.0 until the first resize event is observed).
+pub fn scale_factor() -> f32 {
+    SCALE_OVERRIDE
+        .get()
+        .copied()
+        .flatten()
+        .unwrap_or_else(|| AUTO_SCALE.load(Ordering::Relaxed))
+}
+
+/// Scales a font size, padding, or widget dimension by the global UI scale
+/// factor, for pages/widgets that need to fit a small touch panel.
+pub fn scaled(value: f32) -> f32 {
+    value * scale_factor()
+}
+
+/// Applies the `display.high-contrast` setting from `config.toml`. Must be
+/// called once, as early as possible, since widget stylesheets read
+/// [`high_contrast`] on every `view()`.
+pub fn configure_high_contrast(enabled: bool) {
+    HIGH_CONTRAST.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the high-contrast theme variant is enabled: solid backgrounds,
+/// larger borders, no translucency. Card-style widgets (`toggle_card`,
+/// `media_player`, search results) check this to pick between their normal
+/// semi-transparent `Appearance` and a high-contrast one.
+pub fn high_contrast() -> bool {
+    HIGH_CONTRAST.load(Ordering::Relaxed)
+}
+
+/// Applies the `display.font-scale` multiplier from `config.toml`. Must be
+/// called once, as early as possible, since [`font_size`] is read on every
+/// `view()`.
+pub fn configure_font_scale(scale: f32) {
+    FONT_SCALE.store(scale, Ordering::Relaxed);
+}
+
+/// Applies the `display.reduce-animations` setting from `config.toml`. Must
+/// be called once, as early as possible, since [`reduced_motion`] is read by
+/// every widget that runs a `keyframe::AnimationSequence`.
+pub fn configure_reduced_motion(enabled: bool) {
+    REDUCED_MOTION.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether animated transitions (the context menu's slide, the header
+/// search's fade, toast dismissal) should jump straight to their end state
+/// instead of easing over time, for low-end hardware or motion-sensitive
+/// users.
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
+/// The panel's typography scale: named font sizes rather than scattered
+/// magic numbers, so `display.font-scale` in `config.toml` can enlarge
+/// headers, labels, and captions together for visually impaired users
+/// without hunting down every literal `.size(n)` call.
+#[derive(Copy, Clone)]
+pub enum FontSize {
+    /// Page headers, e.g. the omni page's greeting.
+    Header,
+    /// Card/control labels, e.g. a toggle card's entity name.
+    Label,
+    /// Secondary/small text, e.g. the media player's scrubber times.
+    Caption,
+}
+
+impl FontSize {
+    fn base(self) -> f32 {
+        match self {
+            FontSize::Header => 60.0,
+            FontSize::Label => 18.0,
+            FontSize::Caption => 12.0,
+        }
+    }
+}
+
+/// Resolves a [`FontSize`] to a concrete point size, applying the
+/// `display.font-scale` multiplier.
+pub fn font_size(size: FontSize) -> f32 {
+    size.base() * FONT_SCALE.load(Ordering::Relaxed)
+}
+
 pub mod colours {
     use iced::Color;
 
@@ -34,6 +179,9 @@ pub mod colours {
     pub const SLATE_400: Color = colour!(148.0, 163.0, 184.0);
 
     pub const SKY_500: Color = colour!(14.0, 165.0, 233.0);
+
+    pub const GREEN_500: Color = colour!(34.0, 197.0, 94.0);
+    pub const RED_500: Color = colour!(239.0, 68.0, 68.0);
 }
 
 #[derive(Copy, Clone)]
@@ -69,6 +217,11 @@ pub enum Icon {
     Dead,
     Search,
     Close,
+    Vacuum,
+    Garage,
+    Humidifier,
+    Dehumidifier,
+    Share,
 }
 
 impl Icon {
@@ -111,6 +264,11 @@ impl Icon {
             Self::Dead => image!("dead"),
             Self::Search => image!("search"),
             Self::Close => image!("close"),
+            Self::Vacuum => image!("vacuum"),
+            Self::Garage => image!("garage"),
+            Self::Humidifier => image!("humidifier"),
+            Self::Dehumidifier => image!("dehumidifier"),
+            Self::Share => image!("share"),
         }
     }
 
@@ -155,6 +313,11 @@ impl Icon {
             Self::Dead => image!(Icon::Dead),
             Self::Search => image!(Icon::Search),
             Self::Close => image!(Icon::Close),
+            Self::Vacuum => image!(Icon::Vacuum),
+            Self::Garage => image!(Icon::Garage),
+            Self::Humidifier => image!(Icon::Humidifier),
+            Self::Dehumidifier => image!(Icon::Dehumidifier),
+            Self::Share => image!(Icon::Share),
         }
     }
 
@@ -201,6 +364,11 @@ impl Icon {
             Self::Dead => image!(Icon::Dead),
             Self::Search => image!(Icon::Search),
             Self::Close => image!(Icon::Close),
+            Self::Vacuum => image!(Icon::Vacuum),
+            Self::Garage => image!(Icon::Garage),
+            Self::Humidifier => image!(Icon::Humidifier),
+            Self::Dehumidifier => image!(Icon::Dehumidifier),
+            Self::Share => image!(Icon::Share),
         };
 
         canvas(IconCanvas {
@@ -269,6 +437,61 @@ impl From<Image> for image::Handle {
     }
 }
 
+/// Tints [`Image::Sunset`] to reflect the current weather condition and
+/// time of day, for use as the omni page's background. Night hours darken
+/// the image the same way [`darken_image`] darkens the listen page's
+/// backdrop; rainy/stormy conditions additionally push it towards blue.
+/// Results are cached per `(condition, day_time)` pair so the pixel work
+/// only runs once per combination for the lifetime of the process.
+pub fn weather_background(condition: u16, day_time: bool) -> image::Handle {
+    static CACHE: Lazy<parking_lot::Mutex<std::collections::HashMap<(u16, bool), image::Handle>>> =
+        Lazy::new(|| parking_lot::Mutex::new(std::collections::HashMap::new()));
+
+    let key = (condition, day_time);
+    if let Some(handle) = CACHE.lock().get(&key) {
+        return handle.clone();
+    }
+
+    let base = Image::Sunset.handle();
+    let Data::Rgba {
+        width,
+        height,
+        pixels,
+    } = base.data().clone()
+    else {
+        return base;
+    };
+
+    let mut img = RgbaImage::from_raw(width, height, pixels.to_vec())
+        .expect("Image::Sunset is always a valid RGBA buffer");
+
+    if !day_time {
+        img = darken_image(img, 0.5);
+    }
+
+    if matches!(
+        WeatherCondition::from_repr(condition).unwrap_or_default(),
+        WeatherCondition::Rainy
+            | WeatherCondition::Pouring
+            | WeatherCondition::LightningRainy
+            | WeatherCondition::SnowyRainy
+            | WeatherCondition::Lightning
+    ) {
+        for px in img.pixels_mut() {
+            let [r, g, b, a] = px.0;
+            #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+            let dim = |v: u8| (f32::from(v) * 0.7) as u8;
+            px.0 = [dim(r), dim(g), b, a];
+        }
+    }
+
+    let (width, height) = img.dimensions();
+    let handle = image::Handle::from_pixels(width, height, img.into_raw());
+    CACHE.lock().insert(key, handle.clone());
+
+    handle
+}
+
 pub fn darken_image(mut img: RgbaImage, factor: f32) -> RgbaImage {
     for px in img.pixels_mut() {
         #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
@@ -0,0 +1,84 @@
+//! A `rustls` certificate verifier that trusts exactly one self-signed
+//! certificate, identified by its SHA-256 fingerprint, instead of validating
+//! against the system root store. For a local LAN Home Assistant instance
+//! that terminates TLS itself with a cert it minted, rather than one issued
+//! by a CA in the system trust store.
+
+use std::{sync::Arc, time::SystemTime};
+
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier},
+    Certificate, ClientConfig, Error, ServerName,
+};
+use sha2::{Digest, Sha256};
+
+/// Builds a `rustls` config that accepts only the certificate matching
+/// `fingerprint`, formatted the way OpenSSL prints it (`AA:BB:CC:...`).
+///
+/// # Errors
+///
+/// Returns an error if `fingerprint` isn't 32 colon-separated hex bytes, so a
+/// typo'd `tls-fingerprint` fails to start instead of silently pinning to a
+/// mangled, attacker-guessable hash.
+pub fn pinned_client_config(fingerprint: &str) -> Result<Arc<ClientConfig>, String> {
+    let verifier = Arc::new(FingerprintVerifier {
+        expected: parse_fingerprint(fingerprint)?,
+    });
+
+    Ok(Arc::new(
+        ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(verifier)
+            .with_no_client_auth(),
+    ))
+}
+
+fn parse_fingerprint(fingerprint: &str) -> Result<[u8; 32], String> {
+    let mut expected = [0u8; 32];
+    let mut parts = fingerprint.split(':');
+
+    for byte in &mut expected {
+        let part = parts.next().ok_or_else(|| {
+            format!("tls-fingerprint {fingerprint:?} has too few groups, expected 32")
+        })?;
+
+        *byte = u8::from_str_radix(part, 16).map_err(|_| {
+            format!("tls-fingerprint {fingerprint:?} has an invalid hex byte {part:?}")
+        })?;
+    }
+
+    if parts.next().is_some() {
+        return Err(format!(
+            "tls-fingerprint {fingerprint:?} has too many groups, expected 32"
+        ));
+    }
+
+    Ok(expected)
+}
+
+struct FingerprintVerifier {
+    expected: [u8; 32],
+}
+
+impl ServerCertVerifier for FingerprintVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let actual: [u8; 32] = Sha256::digest(&end_entity.0).into();
+
+        if actual == self.expected {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(Error::General(
+                "certificate fingerprint does not match the configured tls-fingerprint pin"
+                    .to_string(),
+            ))
+        }
+    }
+}
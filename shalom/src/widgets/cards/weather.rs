@@ -18,7 +18,7 @@ use iced::{
 };
 use time::OffsetDateTime;
 
-use crate::oracle::Weather;
+use crate::{oracle::Weather, theme};
 
 #[allow(clippy::module_name_repetitions)]
 pub struct WeatherCard<M> {
@@ -50,7 +50,8 @@ impl<M> WeatherCard<M> {
 
 impl<M: Clone> Widget<M, Renderer> for WeatherCard<M> {
     fn size(&self) -> Size<Length> {
-        Size::new(Length::Fixed(192.0), Length::Fixed(192.0))
+        let side = Length::Fixed(theme::scaled(192.0));
+        Size::new(side, side)
     }
 
     fn layout(&self, tree: &mut Tree, renderer: &Renderer, limits: &Limits) -> Node {
@@ -396,6 +396,404 @@ pub struct SaturationBrightnessPickerState {
     hue: f32,
 }
 
+pub struct ColourWheelPicker<Event, F> {
+    hue: f32,
+    saturation: f32,
+    brightness: f32,
+    on_change: F,
+    on_mouse_up: Event,
+}
+
+impl<Event, F> ColourWheelPicker<Event, F> {
+    pub fn new(
+        hue: f32,
+        saturation: f32,
+        brightness: f32,
+        on_change: F,
+        on_mouse_up: Event,
+    ) -> Self {
+        Self {
+            hue,
+            saturation,
+            brightness,
+            on_change,
+            on_mouse_up,
+        }
+    }
+}
+
+impl<Event: Clone, F: Fn(f32, f32, f32) -> Event> Component<Event, Renderer>
+    for ColourWheelPicker<Event, F>
+{
+    type State = ();
+    type Event = WheelMessage;
+
+    fn update(&mut self, _state: &mut Self::State, event: Self::Event) -> Option<Event> {
+        match event {
+            WheelMessage::HueSaturationChange(hue, saturation) => {
+                Some((self.on_change)(hue, saturation, self.brightness))
+            }
+            WheelMessage::BrightnessChanged(brightness) => {
+                Some((self.on_change)(self.hue, self.saturation, brightness))
+            }
+            WheelMessage::MouseUp => Some(self.on_mouse_up.clone()),
+        }
+    }
+
+    fn view(&self, _state: &Self::State) -> Element<'_, Self::Event, Renderer> {
+        let wheel = forced_rounded(
+            canvas(ColourWheel::new(
+                self.hue,
+                self.saturation,
+                WheelMessage::HueSaturationChange,
+                WheelMessage::MouseUp,
+            ))
+            .height(192)
+            .width(192),
+        );
+
+        let brightness_slider = forced_rounded(
+            canvas(BrightnessSlider::new(
+                self.brightness,
+                WheelMessage::BrightnessChanged,
+                WheelMessage::MouseUp,
+            ))
+            .height(192)
+            .width(32),
+        );
+
+        Row::new()
+            .push(wheel)
+            .push(brightness_slider)
+            .spacing(0)
+            .into()
+    }
+}
+
+impl<'a, M, F> From<ColourWheelPicker<M, F>> for Element<'a, M, Renderer>
+where
+    M: 'a + Clone,
+    F: Fn(f32, f32, f32) -> M + 'a,
+{
+    fn from(card: ColourWheelPicker<M, F>) -> Self {
+        component(card)
+    }
+}
+
+#[derive(Clone)]
+pub enum WheelMessage {
+    HueSaturationChange(f32, f32),
+    BrightnessChanged(f32),
+    MouseUp,
+}
+
+pub struct ColourWheel<Message> {
+    hue: f32,
+    saturation: f32,
+    on_change: fn(f32, f32) -> Message,
+    on_mouse_up: Message,
+}
+
+impl<Message> ColourWheel<Message> {
+    fn new(
+        hue: f32,
+        saturation: f32,
+        on_change: fn(f32, f32) -> Message,
+        on_mouse_up: Message,
+    ) -> Self {
+        Self {
+            hue,
+            saturation,
+            on_change,
+            on_mouse_up,
+        }
+    }
+}
+
+impl<Message: Clone> canvas::Program<Message> for ColourWheel<Message> {
+    type State = ColourWheelState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> (Status, Option<Message>) {
+        // copy hue/saturation from self to state to figure out if the
+        // selection dot needs to be rerendered
+        #[allow(clippy::float_cmp)]
+        if self.hue != state.hue || self.saturation != state.saturation {
+            state.hue = self.hue;
+            state.saturation = self.saturation;
+            state.selection_cache.clear();
+        }
+
+        let (update, mouse_up) = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. })
+                if cursor.is_over(bounds) =>
+            {
+                state.is_dragging = true;
+                (true, false)
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. })
+                if state.is_dragging =>
+            {
+                state.is_dragging = false;
+                (false, true)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. })
+                if state.is_dragging =>
+            {
+                (true, false)
+            }
+            _ => (false, false),
+        };
+
+        if update {
+            if let Some(position) = cursor.position_in(bounds) {
+                state.selection_cache.clear();
+
+                let radius = bounds.width.min(bounds.height) / 2.0;
+                let dx = position.x - radius;
+                let dy = position.y - radius;
+                let distance = (dx * dx + dy * dy).sqrt();
+
+                let saturation = (distance / radius).clamp(0.0, 1.0);
+                let hue = dy.atan2(dx).to_degrees().rem_euclid(360.0);
+
+                (Status::Captured, Some((self.on_change)(hue, saturation)))
+            } else {
+                (Status::Captured, None)
+            }
+        } else if mouse_up {
+            (Status::Captured, Some(self.on_mouse_up.clone()))
+        } else {
+            (Status::Ignored, None)
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry> {
+        // Draw the hue/saturation wheel: angle is hue, distance from the
+        // centre is saturation
+        let content = state
+            .wheel_cache
+            .draw(renderer, bounds.size(), |frame: &mut Frame| {
+                let size = frame.size();
+                let radius = size.width.min(size.height) / 2.0;
+                let centre = Point::new(size.width / 2.0, size.height / 2.0);
+
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    clippy::cast_precision_loss
+                )]
+                for x in 0..size.width as u32 {
+                    for y in 0..size.height as u32 {
+                        let dx = x as f32 - centre.x;
+                        let dy = y as f32 - centre.y;
+                        let distance = (dx * dx + dy * dy).sqrt();
+
+                        if distance > radius {
+                            continue;
+                        }
+
+                        let saturation = distance / radius;
+                        let hue = dy.atan2(dx).to_degrees().rem_euclid(360.0);
+                        let color = colour_from_hsb(hue, saturation, 1.0);
+
+                        frame.fill_rectangle(
+                            Point::new(x as f32, y as f32),
+                            Size::new(1.0, 1.0),
+                            color,
+                        );
+                    }
+                }
+            });
+
+        // Draw the user's selection on the wheel
+        let selection = state
+            .selection_cache
+            .draw(renderer, bounds.size(), |frame: &mut Frame| {
+                let size = frame.size();
+                let radius = size.width.min(size.height) / 2.0;
+                let centre = Point::new(size.width / 2.0, size.height / 2.0);
+
+                let angle = self.hue.to_radians();
+                let selection_x = centre.x + angle.cos() * self.saturation * radius;
+                let selection_y = centre.y + angle.sin() * self.saturation * radius;
+
+                let circle = Path::circle(Point::new(selection_x, selection_y), 5.0);
+
+                frame.stroke(
+                    &circle,
+                    Stroke {
+                        style: Style::Solid(Color::BLACK),
+                        width: 1.,
+                        ..Stroke::default()
+                    },
+                );
+            });
+
+        vec![content, selection]
+    }
+}
+
+#[derive(Default)]
+pub struct ColourWheelState {
+    is_dragging: bool,
+    wheel_cache: Cache,
+    selection_cache: Cache,
+    hue: f32,
+    saturation: f32,
+}
+
+pub struct BrightnessSlider<Message> {
+    brightness: f32,
+    on_change: fn(f32) -> Message,
+    on_mouse_up: Message,
+}
+
+impl<Message> BrightnessSlider<Message> {
+    fn new(brightness: f32, on_change: fn(f32) -> Message, on_mouse_up: Message) -> Self {
+        Self {
+            brightness,
+            on_change,
+            on_mouse_up,
+        }
+    }
+}
+
+impl<Message: Clone> canvas::Program<Message> for BrightnessSlider<Message> {
+    type State = BrightnessSliderState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> (Status, Option<Message>) {
+        let (update, mouse_up) = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. })
+                if cursor.is_over(bounds) =>
+            {
+                state.is_dragging = true;
+                (true, false)
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. } | touch::Event::FingerLost { .. })
+                if state.is_dragging =>
+            {
+                state.is_dragging = false;
+                (false, true)
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. })
+                if state.is_dragging =>
+            {
+                (true, false)
+            }
+            _ => (false, false),
+        };
+
+        if update {
+            if let Some(position) = cursor.position_in(bounds) {
+                state.arrow_cache.clear();
+
+                let brightness = 1.0 - (position.y / bounds.height);
+                (
+                    Status::Captured,
+                    Some((self.on_change)(brightness.clamp(0.0, 1.0))),
+                )
+            } else {
+                (Status::Captured, None)
+            }
+        } else if mouse_up {
+            (Status::Captured, Some(self.on_mouse_up.clone()))
+        } else {
+            (Status::Ignored, None)
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: Cursor,
+    ) -> Vec<Geometry> {
+        // Draw the brightness gradient, white at the top to black at the
+        // bottom
+        let content = state
+            .preview_cache
+            .draw(renderer, bounds.size(), |frame: &mut Frame| {
+                let size = frame.size();
+
+                #[allow(
+                    clippy::cast_possible_truncation,
+                    clippy::cast_sign_loss,
+                    clippy::cast_precision_loss
+                )]
+                for y in 0..size.height as u32 {
+                    let brightness = 1.0 - (y as f32 / size.height);
+                    let color = colour_from_hsb(0.0, 0.0, brightness);
+                    frame.fill_rectangle(
+                        Point::new(0.0, y as f32),
+                        Size::new(size.width, 1.0),
+                        color,
+                    );
+                }
+            });
+
+        // Draw the user's selection on the gradient
+        let arrow = state
+            .arrow_cache
+            .draw(renderer, bounds.size(), |frame: &mut Frame| {
+                let size = frame.size();
+
+                let arrow_width = 10.0;
+                let arrow_height = 10.0;
+                let arrow_x = size.width;
+                let arrow_y = (1.0 - self.brightness) * size.height - (arrow_height / 2.0);
+
+                let arrow = Path::new(|p| {
+                    p.move_to(Point::new(arrow_x, arrow_y));
+                    p.line_to(Point::new(arrow_x, arrow_y - arrow_width));
+                    p.line_to(Point::new(
+                        arrow_x - arrow_height,
+                        arrow_y - (arrow_width / 2.0),
+                    ));
+                    p.line_to(Point::new(arrow_x, arrow_y));
+                    p.close();
+                });
+
+                frame.fill(&arrow, Color::BLACK);
+            });
+
+        vec![content, arrow]
+    }
+}
+
+#[derive(Default)]
+pub struct BrightnessSliderState {
+    is_dragging: bool,
+    preview_cache: Cache,
+    arrow_cache: Cache,
+}
+
 pub fn colour_from_hsb(hue: f32, saturation: f32, brightness: f32) -> Color {
     let rgb: palette::Srgb = palette::Hsv::new(hue, saturation, brightness).into_color();
     Color::from_rgb(rgb.red, rgb.green, rgb.blue)
@@ -14,14 +14,13 @@ use iced::{
     event::Status,
     mouse,
     mouse::{Button, Cursor, Interaction},
-    window,
-    window::RedrawRequest,
-    Alignment, BorderRadius, Color, Element, Event, Length, Point, Rectangle, Renderer, Size,
-    Theme, Vector,
+    window, Alignment, BorderRadius, Color, Element, Event, Length, Point, Rectangle, Renderer,
+    Size, Theme, Vector,
 };
 use keyframe::{functions::EaseOutQuint, keyframes, AnimationSequence};
 
 use super::blackhole_event::blackhole_event;
+use crate::theme;
 
 pub struct ContextMenu<'a, M> {
     base: Element<'a, M, Renderer>,
@@ -278,7 +277,13 @@ impl<'a, 'b, M: Clone> Overlay<'a, 'b, M> {
                 }
             }
             State::Animate(instant, keyframes) => {
-                keyframes.advance_by(instant.elapsed().as_secs_f64());
+                let elapsed = if theme::reduced_motion() {
+                    theme::INSTANT_ANIMATION_STEP.as_secs_f64()
+                } else {
+                    instant.elapsed().as_secs_f64()
+                };
+
+                keyframes.advance_by(elapsed);
                 self.state.height = keyframes.now();
                 *instant = Instant::now();
 
@@ -293,7 +298,7 @@ impl<'a, 'b, M: Clone> Overlay<'a, 'b, M> {
                         self.state.state = State::Open;
                     }
                 } else {
-                    shell.request_redraw(RedrawRequest::NextFrame);
+                    theme::request_animation_frame(shell);
                 }
             }
         }
@@ -422,7 +427,7 @@ impl<'a, 'b, M: Clone> overlay::Overlay<M, Renderer> for Overlay<'a, 'b, M> {
                 if let (Some(msg), State::Closed) = (&self.on_close, &self.state.state) {
                     shell.publish(msg.clone());
                 } else {
-                    shell.request_redraw(RedrawRequest::NextFrame);
+                    theme::request_animation_frame(shell);
                 }
             }
         } else if let Event::Window(_, window::Event::RedrawRequested(_)) = &event {
@@ -1,10 +1,12 @@
+use std::time::Instant;
+
 use iced::{
     advanced::{
         image::{Data, Renderer as ImageRenderer},
         layout::{Limits, Node},
         overlay,
         renderer::{Quad, Style},
-        widget::Tree,
+        widget::{tree, tree::Tag, Tree},
         Clipboard, Layout, Renderer as IRenderer, Shell, Widget,
     },
     event::Status,
@@ -14,11 +16,13 @@ use iced::{
     mouse::{Button, Cursor},
     theme::Text,
     touch,
-    widget::{image, image::FilterMethod, text},
+    widget::{image, image::FilterMethod, text, tooltip, tooltip::Tooltip},
     Alignment, Background, Color, ContentFit, Degrees, Element, Event, Font, Gradient, Length,
     Point, Rectangle, Renderer, Size, Theme, Vector,
 };
 
+use crate::widgets::toggle_card::LONG_PRESS_LENGTH;
+
 pub fn image_card<'a, M: 'a>(handle: impl Into<image::Handle>, caption: &str) -> ImageCard<'a, M> {
     let image_handle = handle.into();
 
@@ -34,8 +38,10 @@ pub fn image_card<'a, M: 'a>(handle: impl Into<image::Handle>, caption: &str) ->
             .style(Text::Color(Color::WHITE))
             .into(),
         on_press: None,
+        on_long_press: None,
         width: Length::FillPortion(1),
         height: Length::Fixed(128.0),
+        description: None,
     }
 }
 
@@ -43,8 +49,10 @@ pub struct ImageCard<'a, M> {
     image_handle: image::Handle,
     text: Element<'a, M, Renderer>,
     on_press: Option<M>,
+    on_long_press: Option<M>,
     width: Length,
     height: Length,
+    description: Option<Box<str>>,
 }
 
 impl<'a, M> ImageCard<'a, M> {
@@ -52,6 +60,21 @@ impl<'a, M> ImageCard<'a, M> {
         self.on_press = Some(msg);
         self
     }
+
+    pub fn on_long_press(mut self, msg: M) -> Self {
+        self.on_long_press = Some(msg);
+        self
+    }
+
+    /// A screen-reader-style label announced via a tooltip, e.g. "Kitchen
+    /// camera" or "Inception, play". iced 0.12 doesn't expose accesskit/a11y
+    /// hooks on `Widget`, so a tooltip is the closest stand-in this version
+    /// can offer; swap this for a real accessible name once iced grows that
+    /// support upstream.
+    pub fn description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
 }
 
 impl<'a, M: Clone> Widget<M, Renderer> for ImageCard<'a, M> {
@@ -108,6 +131,14 @@ impl<'a, M: Clone> Widget<M, Renderer> for ImageCard<'a, M> {
         });
     }
 
+    fn tag(&self) -> Tag {
+        Tag::of::<PressState>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(PressState::default())
+    }
+
     fn children(&self) -> Vec<Tree> {
         vec![Tree::new(&self.text)]
     }
@@ -138,6 +169,8 @@ impl<'a, M: Clone> Widget<M, Renderer> for ImageCard<'a, M> {
                         tree: &mut state.children[0],
                         size: layout.bounds().size(),
                         on_press: self.on_press.as_ref(),
+                        on_long_press: self.on_long_press.as_ref(),
+                        press_state: state.state.downcast_mut::<PressState>(),
                     }),
                 ),
             ])
@@ -146,11 +179,20 @@ impl<'a, M: Clone> Widget<M, Renderer> for ImageCard<'a, M> {
     }
 }
 
+/// Tracks how long a press on an [`ImageCard`] has been held, so
+/// [`ImageCard::on_long_press`] can be distinguished from a regular tap.
+#[derive(Default)]
+struct PressState {
+    press_start: Option<Instant>,
+}
+
 struct Overlay<'a, 'b, M> {
     text: &'b mut Element<'a, M, Renderer>,
     tree: &'b mut Tree,
     size: Size,
     on_press: Option<&'b M>,
+    on_long_press: Option<&'b M>,
+    press_state: &'b mut PressState,
 }
 
 impl<'a, 'b, M: Clone> overlay::Overlay<M, Renderer> for Overlay<'a, 'b, M> {
@@ -228,17 +270,35 @@ impl<'a, 'b, M: Clone> overlay::Overlay<M, Renderer> for Overlay<'a, 'b, M> {
             return Status::Ignored;
         }
 
-        if let Some(on_press) = self.on_press {
-            if let Event::Mouse(mouse::Event::ButtonPressed(Button::Left))
-            | Event::Touch(touch::Event::FingerPressed { .. }) = &event
-            {
-                shell.publish(on_press.clone());
+        match &event {
+            Event::Mouse(mouse::Event::ButtonPressed(Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                self.press_state.press_start = Some(Instant::now());
 
-                return Status::Captured;
+                Status::Captured
             }
-        }
+            Event::Mouse(mouse::Event::ButtonReleased(Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. }) => {
+                let Some(start) = self.press_state.press_start.take() else {
+                    return Status::Ignored;
+                };
+
+                let message = if start.elapsed() > LONG_PRESS_LENGTH {
+                    self.on_long_press.or(self.on_press)
+                } else {
+                    self.on_press
+                };
+
+                if let Some(message) = message {
+                    shell.publish(message.clone());
+
+                    return Status::Captured;
+                }
 
-        Status::Ignored
+                Status::Ignored
+            }
+            _ => Status::Ignored,
+        }
     }
 }
 
@@ -247,6 +307,16 @@ where
     M: 'a + Clone,
 {
     fn from(modal: ImageCard<'a, M>) -> Self {
-        Element::new(modal)
+        let description = modal.description.clone();
+        let element = Element::new(modal);
+
+        match description {
+            Some(description) => Element::new(Tooltip::new(
+                element,
+                description,
+                tooltip::Position::Bottom,
+            )),
+            None => element,
+        }
     }
 }
@@ -8,8 +8,8 @@ use iced::{
     alignment::Horizontal,
     theme::{Container, Slider, Svg, Text},
     widget::{
-        column as icolumn, component, container, image::Handle, mouse_area, row, slider, svg, text,
-        Component,
+        checkbox, column as icolumn, component, container, image::Handle, mouse_area, row, slider,
+        svg, text, tooltip, tooltip::Tooltip, Component,
     },
     Alignment, Background, Color, Length, Renderer, Theme,
 };
@@ -17,6 +17,7 @@ use iced::{
 use crate::{
     hass_client::MediaPlayerRepeat,
     oracle::{MediaPlayerSpeaker, MediaPlayerSpeakerState},
+    theme,
     theme::{
         colours::{SKY_500, SLATE_400},
         Icon,
@@ -39,6 +40,18 @@ pub fn media_player<M>(device: MediaPlayerSpeaker, album_art: Option<Handle>) ->
         on_previous_track: None,
         on_shuffle_change: None,
         on_search: None,
+        on_share: None,
+        description: None,
+        group_volume: None,
+        on_group_volume_change: None,
+        bass: None,
+        on_bass_change: None,
+        treble: None,
+        on_treble_change: None,
+        loudness: None,
+        on_loudness_change: None,
+        night_mode: None,
+        on_night_mode_change: None,
     }
 }
 
@@ -58,6 +71,25 @@ pub struct MediaPlayer<M> {
     on_previous_track: Option<M>,
     on_shuffle_change: Option<fn(bool) -> M>,
     on_search: Option<M>,
+    on_share: Option<M>,
+    description: Option<Box<str>>,
+    /// The group's overall volume, from [`MediaPlayerSpeaker::group_volume`],
+    /// if this speaker is a group coordinator. Shows a second "Group volume"
+    /// slider below the control row that holds the per-speaker one.
+    group_volume: Option<f32>,
+    on_group_volume_change: Option<fn(f32) -> M>,
+    /// The speaker's current bass level, if it exposes a `number.` entity
+    /// for it (e.g. a Sonos speaker). Shown in a collapsible "Advanced"
+    /// section alongside [`Self::treble`], [`Self::loudness`] and
+    /// [`Self::night_mode`].
+    bass: Option<f32>,
+    on_bass_change: Option<fn(f32) -> M>,
+    treble: Option<f32>,
+    on_treble_change: Option<fn(f32) -> M>,
+    loudness: Option<bool>,
+    on_loudness_change: Option<fn(bool) -> M>,
+    night_mode: Option<bool>,
+    on_night_mode_change: Option<fn(bool) -> M>,
 }
 
 impl<M> MediaPlayer<M> {
@@ -71,6 +103,16 @@ impl<M> MediaPlayer<M> {
         self
     }
 
+    /// A screen-reader-style label, e.g. "Kitchen speaker, playing", announced
+    /// via a tooltip. iced 0.12 doesn't expose accesskit/a11y hooks on
+    /// `Widget`, so a tooltip is the closest stand-in this version can offer;
+    /// swap this for a real accessible name once iced grows that support
+    /// upstream.
+    pub fn description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
     pub fn on_search(mut self, m: M) -> Self {
         self.on_search = Some(m);
         self
@@ -110,6 +152,74 @@ impl<M> MediaPlayer<M> {
         self.on_shuffle_change = Some(f);
         self
     }
+
+    /// Fired when the share button is pressed. Only shown when
+    /// [`MediaPlayerSpeaker::spotify_url`] returns a link to share.
+    pub fn on_share(mut self, msg: M) -> Self {
+        self.on_share = Some(msg);
+        self
+    }
+
+    /// Shows a group-volume slider below the control row that holds the
+    /// per-speaker one, for when this speaker is coordinating a multi-speaker
+    /// group.
+    pub fn with_group_volume(mut self, volume: Option<f32>) -> Self {
+        self.group_volume = volume;
+        self
+    }
+
+    pub fn on_group_volume_change(mut self, f: fn(f32) -> M) -> Self {
+        self.on_group_volume_change = Some(f);
+        self
+    }
+
+    /// Shows a bass slider in the "Advanced" section, for speakers that
+    /// expose a `number.` bass entity on the same device.
+    pub fn with_bass(mut self, bass: Option<f32>) -> Self {
+        self.bass = bass;
+        self
+    }
+
+    pub fn on_bass_change(mut self, f: fn(f32) -> M) -> Self {
+        self.on_bass_change = Some(f);
+        self
+    }
+
+    /// Shows a treble slider in the "Advanced" section, for speakers that
+    /// expose a `number.` treble entity on the same device.
+    pub fn with_treble(mut self, treble: Option<f32>) -> Self {
+        self.treble = treble;
+        self
+    }
+
+    pub fn on_treble_change(mut self, f: fn(f32) -> M) -> Self {
+        self.on_treble_change = Some(f);
+        self
+    }
+
+    /// Shows a loudness checkbox in the "Advanced" section, for speakers
+    /// that expose a `switch.` loudness entity on the same device.
+    pub fn with_loudness(mut self, loudness: Option<bool>) -> Self {
+        self.loudness = loudness;
+        self
+    }
+
+    pub fn on_loudness_change(mut self, f: fn(bool) -> M) -> Self {
+        self.on_loudness_change = Some(f);
+        self
+    }
+
+    /// Shows a night mode checkbox in the "Advanced" section, for speakers
+    /// that expose a `switch.` night sound entity on the same device.
+    pub fn with_night_mode(mut self, night_mode: Option<bool>) -> Self {
+        self.night_mode = night_mode;
+        self
+    }
+
+    pub fn on_night_mode_change(mut self, f: fn(bool) -> M) -> Self {
+        self.on_night_mode_change = Some(f);
+        self
+    }
 }
 
 impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
@@ -122,6 +232,14 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
                 state.overridden_volume = Some(new);
                 None
             }
+            Event::GroupVolumeChange(new) => {
+                state.overridden_group_volume = Some(new);
+                None
+            }
+            Event::OnGroupVolumeRelease => self
+                .on_group_volume_change
+                .zip(state.overridden_group_volume.take())
+                .map(|(f, vol)| f(vol)),
             Event::PositionChange(new) => {
                 state.overridden_position = Some(Duration::from_secs_f64(new));
                 None
@@ -154,6 +272,29 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
             }
             Event::NextTrack => self.on_next_track.clone(),
             Event::ToggleShuffle => self.on_shuffle_change.map(|f| f(!self.device.shuffle)),
+            Event::Share => self.on_share.clone(),
+            Event::ToggleAdvanced => {
+                state.advanced_open = !state.advanced_open;
+                None
+            }
+            Event::BassChange(new) => {
+                state.overridden_bass = Some(new);
+                None
+            }
+            Event::OnBassRelease => self
+                .on_bass_change
+                .zip(state.overridden_bass.take())
+                .map(|(f, v)| f(v)),
+            Event::TrebleChange(new) => {
+                state.overridden_treble = Some(new);
+                None
+            }
+            Event::OnTrebleRelease => self
+                .on_treble_change
+                .zip(state.overridden_treble.take())
+                .map(|(f, v)| f(v)),
+            Event::LoudnessChange(v) => self.on_loudness_change.map(|f| f(v)),
+            Event::NightModeChange(v) => self.on_night_mode_change.map(|f| f(v)),
         }
     }
 
@@ -243,10 +384,23 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
         .spacing(12)
         .align_items(Alignment::Center);
 
+        let share_button: Element<'_, Event, Renderer> = if self.device.spotify_url().is_some() {
+            mouse_area(
+                svg(Icon::Share)
+                    .height(20)
+                    .width(20)
+                    .style(icon_style(false)),
+            )
+            .on_press(Event::Share)
+            .into()
+        } else {
+            row![].into()
+        };
+
         let scrubber = row![
             text(format_time(position))
                 .style(Text::Color(SLATE_400))
-                .size(12)
+                .size(theme::font_size(theme::FontSize::Caption))
                 .width(Length::FillPortion(10)),
             slider(
                 0.0..=self.device.media_duration.unwrap_or_default().as_secs_f64(),
@@ -258,14 +412,111 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
             .width(Length::FillPortion(80)),
             text(format_time(self.device.media_duration.unwrap_or_default()))
                 .style(Text::Color(SLATE_400))
-                .size(12)
+                .size(theme::font_size(theme::FontSize::Caption))
                 .width(Length::FillPortion(10))
                 .horizontal_alignment(iced::alignment::Horizontal::Right),
         ]
         .spacing(14)
         .align_items(Alignment::Center);
 
-        icolumn![
+        let group_volume: Element<'_, Event, Renderer> = match self.group_volume {
+            Some(group_volume) => {
+                let group_volume = state.overridden_group_volume.unwrap_or(group_volume);
+
+                row![
+                    text("Group volume")
+                        .style(Text::Color(SLATE_400))
+                        .size(theme::font_size(theme::FontSize::Caption)),
+                    slider(0.0..=1.0, group_volume, Event::GroupVolumeChange)
+                        .step(0.01)
+                        .on_release(Event::OnGroupVolumeRelease)
+                        .style(Slider::Custom(Box::new(SliderStyle)))
+                        .width(Length::Fill),
+                ]
+                .spacing(14)
+                .align_items(Alignment::Center)
+                .into()
+            }
+            None => row![].into(),
+        };
+
+        let has_advanced = self.bass.is_some()
+            || self.treble.is_some()
+            || self.loudness.is_some()
+            || self.night_mode.is_some();
+
+        let advanced: Element<'_, Event, Renderer> = if has_advanced {
+            let toggle = mouse_area(
+                text(if state.advanced_open {
+                    "Hide advanced"
+                } else {
+                    "Advanced"
+                })
+                .style(Text::Color(SLATE_400))
+                .size(theme::font_size(theme::FontSize::Caption)),
+            )
+            .on_press(Event::ToggleAdvanced);
+
+            let mut section = icolumn![toggle].spacing(12).align_items(Alignment::Center);
+
+            if state.advanced_open {
+                if let Some(bass) = self.bass {
+                    let bass = state.overridden_bass.unwrap_or(bass);
+                    section = section.push(
+                        row![
+                            text("Bass")
+                                .style(Text::Color(SLATE_400))
+                                .size(theme::font_size(theme::FontSize::Caption)),
+                            slider(-10.0..=10.0, bass, Event::BassChange)
+                                .step(1.0)
+                                .on_release(Event::OnBassRelease)
+                                .style(Slider::Custom(Box::new(SliderStyle)))
+                                .width(Length::Fill),
+                        ]
+                        .spacing(14)
+                        .align_items(Alignment::Center),
+                    );
+                }
+
+                if let Some(treble) = self.treble {
+                    let treble = state.overridden_treble.unwrap_or(treble);
+                    section = section.push(
+                        row![
+                            text("Treble")
+                                .style(Text::Color(SLATE_400))
+                                .size(theme::font_size(theme::FontSize::Caption)),
+                            slider(-10.0..=10.0, treble, Event::TrebleChange)
+                                .step(1.0)
+                                .on_release(Event::OnTrebleRelease)
+                                .style(Slider::Custom(Box::new(SliderStyle)))
+                                .width(Length::Fill),
+                        ]
+                        .spacing(14)
+                        .align_items(Alignment::Center),
+                    );
+                }
+
+                if let Some(loudness) = self.loudness {
+                    section = section.push(checkbox("Loudness", loudness, Event::LoudnessChange));
+                }
+
+                if let Some(night_mode) = self.night_mode {
+                    section =
+                        section.push(checkbox("Night mode", night_mode, Event::NightModeChange));
+                }
+            }
+
+            section.into()
+        } else {
+            row![].into()
+        };
+
+        let content = icolumn![
+            text(self.device.friendly_name.as_ref())
+                .size(14)
+                .style(Text::Color(SLATE_400))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center),
             container(track_card)
                 .width(Length::Fill)
                 .height(Length::Fill)
@@ -274,7 +525,7 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
             container(
                 icolumn![
                     row![
-                        container(row![])
+                        container(share_button)
                             .width(Length::FillPortion(8))
                             .align_x(Horizontal::Left),
                         container(playback_controls)
@@ -287,7 +538,9 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
                     .spacing(8)
                     .align_items(Alignment::Center)
                     .width(Length::Fill),
+                    group_volume,
                     scrubber,
+                    advanced,
                 ]
                 .align_items(Alignment::Center)
                 .spacing(24),
@@ -299,8 +552,14 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
             .style(Container::Custom(Box::new(Style::Inactive)))
             .padding([20, 40, 20, 40])
         ]
-        .spacing(30)
-        .into()
+        .spacing(30);
+
+        match &self.description {
+            Some(description) => {
+                Tooltip::new(content, description.as_ref(), tooltip::Position::Bottom).into()
+            }
+            None => content.into(),
+        }
     }
 }
 
@@ -308,6 +567,10 @@ impl<M: Clone> Component<M, Renderer> for MediaPlayer<M> {
 pub struct State {
     overridden_position: Option<Duration>,
     overridden_volume: Option<f32>,
+    overridden_group_volume: Option<f32>,
+    overridden_bass: Option<f32>,
+    overridden_treble: Option<f32>,
+    advanced_open: bool,
     last_previous_click: Option<Instant>,
 }
 
@@ -318,11 +581,21 @@ pub enum Event {
     ToggleRepeat,
     ToggleShuffle,
     VolumeChange(f32),
+    GroupVolumeChange(f32),
+    OnGroupVolumeRelease,
     PositionChange(f64),
     OnVolumeRelease,
     OnPositionRelease,
     PreviousTrack,
     NextTrack,
+    Share,
+    ToggleAdvanced,
+    BassChange(f32),
+    OnBassRelease,
+    TrebleChange(f32),
+    OnTrebleRelease,
+    LoudnessChange(bool),
+    NightModeChange(bool),
 }
 
 impl<'a, M> From<MediaPlayer<M>> for Element<'a, M, Renderer>
@@ -405,6 +678,16 @@ impl container::StyleSheet for Style {
     type Style = Theme;
 
     fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        if theme::high_contrast() {
+            return container::Appearance {
+                text_color: None,
+                background: Some(Background::Color(Color::BLACK)),
+                border_radius: 0.0.into(),
+                border_width: 2.0,
+                border_color: Color::WHITE,
+            };
+        }
+
         container::Appearance {
             text_color: None,
             background: Some(Background::Color(Color {
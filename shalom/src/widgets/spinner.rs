@@ -18,6 +18,8 @@ use iced::{
     window, Color, Element, Event, Length, Point, Rectangle, Renderer, Size, Vector,
 };
 
+use crate::theme;
+
 const HAND_COUNT: usize = 8;
 const ALPHAS: [u16; 8] = [47, 47, 47, 47, 72, 97, 122, 147];
 
@@ -187,7 +189,7 @@ impl<Message, Theme> Widget<Message, Renderer<Theme>> for CupertinoSpinner {
 
         if let Event::Window(_, window::Event::RedrawRequested(_now)) = &event {
             state.spinner.clear();
-            shell.request_redraw(window::RedrawRequest::NextFrame);
+            theme::request_animation_frame(shell);
             return event::Status::Captured;
         }
 
@@ -15,13 +15,11 @@ use iced::{
     font::Weight,
     mouse::Cursor,
     widget::{text, text::Appearance},
-    window,
-    window::RedrawRequest,
-    Background, Color, Element, Event, Font, Length, Rectangle, Renderer, Size, Theme,
+    window, Background, Color, Element, Event, Font, Length, Rectangle, Renderer, Size, Theme,
 };
 use keyframe::{functions::EaseOutQuint, keyframes, AnimationSequence};
 
-use crate::theme::colours::SYSTEM_GRAY6;
+use crate::theme::{self, colours::SYSTEM_GRAY6};
 
 pub struct Toast {
     pub text: String,
@@ -57,9 +55,15 @@ impl<'a, M: Clone> ToastElement<'a, M> {
                     }
                     state.state = TickerState::Closed;
                 } else {
-                    v.advance_by(last_tick.elapsed().as_secs_f64());
+                    let elapsed = if theme::reduced_motion() {
+                        theme::INSTANT_ANIMATION_STEP.as_secs_f64()
+                    } else {
+                        last_tick.elapsed().as_secs_f64()
+                    };
+
+                    v.advance_by(elapsed);
                     *last_tick = Instant::now();
-                    shell.request_redraw(RedrawRequest::NextFrame);
+                    theme::request_animation_frame(shell);
                 }
             }
             TickerState::Ticking => {
@@ -67,7 +71,7 @@ impl<'a, M: Clone> ToastElement<'a, M> {
                     Instant::now(),
                     keyframes![(1.0, 0.0, EaseOutQuint), (0.0, 0.5)],
                 );
-                shell.request_redraw(RedrawRequest::NextFrame);
+                theme::request_animation_frame(shell);
             }
             TickerState::Closed => {}
         }
@@ -88,7 +92,7 @@ impl<'a, M: Clone> Widget<M, Renderer> for ToastElement<'a, M> {
     ) -> Status {
         if let Event::Window(_, window::Event::RedrawRequested(_)) = event {
             if self.toast.start.elapsed() <= self.toast.ttl {
-                shell.request_redraw(RedrawRequest::NextFrame);
+                theme::request_animation_frame(shell);
             } else {
                 let state = state.state.downcast_mut::<State>();
                 self.advance_closing_state(shell, state);
@@ -6,17 +6,35 @@ use iced::{
     alignment::Vertical,
     font::Weight,
     theme::{Container, Svg},
-    widget::{component, container, mouse_area, row, svg, text},
-    Alignment, Background, Color, Element, Font, Length, Renderer, Theme,
+    widget::{component, container, mouse_area, row, svg, text, tooltip, tooltip::Tooltip},
+    Alignment, Background, Color, Element, Font, Length, Point, Renderer, Theme,
 };
 
-use crate::theme::{
-    colours::{ORANGE, SYSTEM_GRAY6},
-    Icon,
+use crate::{
+    sound,
+    theme::{
+        self,
+        colours::{ORANGE, SYSTEM_GRAY6},
+        FontSize,
+    },
 };
 
 pub const LONG_PRESS_LENGTH: Duration = Duration::from_millis(350);
 
+/// Vertical mouse movement, in pixels, past which a press is treated as a
+/// drag rather than a tap or long-press.
+const DRAG_THRESHOLD: f32 = 8.0;
+
+/// Vertical mouse movement, in pixels, that adds up to a delta of 1.0 across
+/// [`ToggleCard::on_drag`] calls. The card doesn't know the light's current
+/// brightness, so it reports a delta fraction for the caller to apply on top
+/// of whatever it already has, rather than an absolute value.
+const DRAG_PIXELS_PER_STEP: f32 = 200.0;
+
+/// Maximum gap between two taps for the second one to count as a double-tap
+/// rather than a fresh single tap.
+const DOUBLE_TAP_LENGTH: Duration = Duration::from_millis(300);
+
 pub fn toggle_card<M>(name: &str, active: bool, disabled: bool) -> ToggleCard<M> {
     ToggleCard {
         name: Box::from(name),
@@ -27,7 +45,7 @@ pub fn toggle_card<M>(name: &str, active: bool, disabled: bool) -> ToggleCard<M>
 }
 
 pub struct ToggleCard<M> {
-    icon: Option<Icon>,
+    icon: Option<svg::Handle>,
     name: Box<str>,
     height: Length,
     width: Length,
@@ -36,6 +54,9 @@ pub struct ToggleCard<M> {
     active_icon_colour: Option<Color>,
     on_press: Option<M>,
     on_long_press: Option<M>,
+    on_drag: Option<Box<dyn Fn(f32) -> M>>,
+    on_double_tap: Option<M>,
+    description: Option<Box<str>>,
 }
 
 impl<M> Default for ToggleCard<M> {
@@ -50,6 +71,9 @@ impl<M> Default for ToggleCard<M> {
             active_icon_colour: None,
             on_press: None,
             on_long_press: None,
+            on_drag: None,
+            on_double_tap: None,
+            description: None,
         }
     }
 }
@@ -70,8 +94,31 @@ impl<M> ToggleCard<M> {
         self
     }
 
-    pub fn icon(mut self, icon: Icon) -> Self {
-        self.icon = Some(icon);
+    /// Called on every pointer move of a vertical drag on the card with a
+    /// brightness delta fraction: the movement since the previous call divided
+    /// by [`DRAG_PIXELS_PER_STEP`], dragging up is positive, like the HA tile
+    /// card's inline dimmer. A drag past
+    /// [`DRAG_THRESHOLD`] suppresses `on_press`/`on_long_press` on release, so
+    /// the light isn't also toggled.
+    pub fn on_drag(mut self, msg: impl Fn(f32) -> M + 'static) -> Self {
+        self.on_drag = Some(Box::new(msg));
+        self
+    }
+
+    /// Fired instead of `on_press` when a tap follows the previous one within
+    /// [`DOUBLE_TAP_LENGTH`], e.g. for the light card's "jump to full
+    /// brightness" shortcut.
+    pub fn on_double_tap(mut self, msg: M) -> Self {
+        self.on_double_tap = Some(msg);
+        self
+    }
+
+    /// Accepts either a bundled [`Icon`](crate::theme::Icon) or a `svg::Handle`
+    /// fetched at runtime (e.g. a Material Design Icon looked up from the
+    /// entity registry), so callers don't need a separate builder method for
+    /// dynamically-sourced icons.
+    pub fn icon(mut self, icon: impl Into<svg::Handle>) -> Self {
+        self.icon = Some(icon.into());
         self
     }
 
@@ -84,6 +131,15 @@ impl<M> ToggleCard<M> {
         self.width = width;
         self
     }
+
+    /// A screen-reader-style label, e.g. "Kitchen light, on", announced via a
+    /// tooltip. iced 0.12 doesn't expose accesskit/a11y hooks on `Widget`, so
+    /// a tooltip is the closest stand-in this version can offer; swap this
+    /// for a real accessible name once iced grows that support upstream.
+    pub fn description(mut self, description: impl Into<Box<str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
 }
 
 impl<M: Clone> iced::widget::Component<M, Renderer> for ToggleCard<M> {
@@ -94,26 +150,82 @@ impl<M: Clone> iced::widget::Component<M, Renderer> for ToggleCard<M> {
         match event {
             ToggleCardEvent::Down => {
                 state.mouse_down_start = Some(Instant::now());
+                state.drag_origin = None;
+                state.dragging = false;
 
                 None
             }
             ToggleCardEvent::Up => {
+                let dragging = state.dragging;
+                state.dragging = false;
+                state.drag_origin = None;
+
                 let Some(start) = state.mouse_down_start.take() else {
                     return None;
                 };
 
-                if start.elapsed() > LONG_PRESS_LENGTH {
+                if dragging {
+                    return None;
+                }
+
+                let message = if start.elapsed() > LONG_PRESS_LENGTH {
                     self.on_long_press.clone().or_else(|| self.on_press.clone())
                 } else {
-                    self.on_press.clone()
+                    let now = Instant::now();
+                    let is_double_tap = state
+                        .last_tap
+                        .is_some_and(|last| now.duration_since(last) < DOUBLE_TAP_LENGTH);
+
+                    if is_double_tap {
+                        state.last_tap = None;
+                        self.on_double_tap.clone().or_else(|| self.on_press.clone())
+                    } else {
+                        state.last_tap = Some(now);
+                        self.on_press.clone()
+                    }
+                };
+
+                if message.is_some() {
+                    sound::play_click();
+                }
+
+                message
+            }
+            ToggleCardEvent::Hold => {
+                if state.dragging {
+                    None
+                } else {
+                    self.on_long_press.clone()
                 }
             }
-            ToggleCardEvent::Hold => self.on_long_press.clone(),
             ToggleCardEvent::Cancel => {
                 state.mouse_down_start = None;
+                state.drag_origin = None;
+                state.dragging = false;
 
                 None
             }
+            ToggleCardEvent::Move(point) => {
+                let on_drag = self.on_drag.as_ref()?;
+
+                state.mouse_down_start?;
+
+                let Some(origin) = state.drag_origin else {
+                    state.drag_origin = Some(point.y);
+                    return None;
+                };
+
+                let delta = origin - point.y;
+
+                if !state.dragging && delta.abs() < DRAG_THRESHOLD {
+                    return None;
+                }
+
+                state.dragging = true;
+                state.drag_origin = Some(point.y);
+
+                Some(on_drag(delta / DRAG_PIXELS_PER_STEP))
+            }
         }
     }
 
@@ -126,18 +238,20 @@ impl<M: Clone> iced::widget::Component<M, Renderer> for ToggleCard<M> {
             (_, false, Some(_)) => Style::InactiveHover,
         };
 
-        let icon = self.icon.map(|icon| {
+        let icon = self.icon.clone().map(|icon| {
             svg(icon)
                 .height(28)
                 .width(28)
                 .style(Svg::Custom(Box::new(style)))
         });
 
-        let name = text(&self.name).size(18).font(Font {
-            weight: Weight::Bold,
-            // stretch: Stretch::Condensed,
-            ..Font::with_name("Helvetica Neue")
-        });
+        let name = text(&self.name)
+            .size(theme::font_size(FontSize::Label))
+            .font(Font {
+                weight: Weight::Bold,
+                // stretch: Stretch::Condensed,
+                ..Font::with_name("Helvetica Neue")
+            });
 
         let row = if let Some(icon) = icon {
             row![icon, name]
@@ -145,7 +259,7 @@ impl<M: Clone> iced::widget::Component<M, Renderer> for ToggleCard<M> {
             row![name]
         };
 
-        mouse_area(
+        let card = mouse_area(
             container(
                 row.spacing(5)
                     .width(self.width)
@@ -161,7 +275,14 @@ impl<M: Clone> iced::widget::Component<M, Renderer> for ToggleCard<M> {
         .on_release(ToggleCardEvent::Up)
         .on_hold(ToggleCardEvent::Hold, LONG_PRESS_LENGTH)
         .on_cancel(ToggleCardEvent::Cancel)
-        .into()
+        .on_move(ToggleCardEvent::Move);
+
+        match &self.description {
+            Some(description) => {
+                Tooltip::new(card, description.as_ref(), tooltip::Position::Bottom).into()
+            }
+            None => card.into(),
+        }
     }
 }
 
@@ -177,6 +298,16 @@ where
 #[derive(Default)]
 pub struct State {
     mouse_down_start: Option<Instant>,
+    /// The drag's most recent `y` position, updated after every processed
+    /// [`ToggleCardEvent::Move`] step. `None` before the first move and
+    /// while not pressed.
+    drag_origin: Option<f32>,
+    /// Set once [`DRAG_THRESHOLD`] has been crossed, so `Up`/`Hold` know to
+    /// treat this press as a drag rather than a tap or long-press.
+    dragging: bool,
+    /// When the last tap was released, for detecting a follow-up tap within
+    /// [`DOUBLE_TAP_LENGTH`].
+    last_tap: Option<Instant>,
 }
 
 #[derive(Clone)]
@@ -185,6 +316,7 @@ pub enum ToggleCardEvent {
     Up,
     Hold,
     Cancel,
+    Move(Point),
 }
 
 #[derive(Copy, Clone)]
@@ -200,6 +332,10 @@ impl container::StyleSheet for Style {
     type Style = Theme;
 
     fn appearance(&self, _style: &Self::Style) -> container::Appearance {
+        if theme::high_contrast() {
+            return self.high_contrast_appearance();
+        }
+
         let base = container::Appearance {
             text_color: None,
             background: None,
@@ -262,6 +398,43 @@ impl container::StyleSheet for Style {
     }
 }
 
+impl Style {
+    /// Solid backgrounds and a visible border instead of the translucent
+    /// layering used above, for [`theme::high_contrast`].
+    fn high_contrast_appearance(&self) -> container::Appearance {
+        let base = container::Appearance {
+            text_color: None,
+            background: None,
+            border_radius: 10.0.into(),
+            border_width: 2.0,
+            border_color: Color::WHITE,
+        };
+
+        match self {
+            Style::Disabled => container::Appearance {
+                text_color: Some(Color::WHITE),
+                background: Some(Background::Color(SYSTEM_GRAY6)),
+                border_color: Color {
+                    a: 0.5,
+                    ..Color::WHITE
+                },
+                ..base
+            },
+            Style::Inactive | Style::InactiveHover => container::Appearance {
+                text_color: Some(Color::WHITE),
+                background: Some(Background::Color(SYSTEM_GRAY6)),
+                ..base
+            },
+            Style::Active(_) | Style::ActiveHover(_) => container::Appearance {
+                text_color: Some(Color::BLACK),
+                background: Some(Background::Color(Color::WHITE)),
+                border_color: ORANGE,
+                ..base
+            },
+        }
+    }
+}
+
 impl svg::StyleSheet for Style {
     type Style = Theme;
 
@@ -7,10 +7,10 @@ use iced::{
         image::{self, Image},
         text, vertical_space, Component,
     },
-    Background, Color, Font, Renderer, Theme,
+    Background, Color, Font, Length, Renderer, Theme,
 };
 
-use crate::theme::colours::SLATE_200;
+use crate::theme::{self, colours::SLATE_200};
 
 pub fn track_card(
     artist: &str,
@@ -42,15 +42,20 @@ impl<M> Component<M, Renderer> for TrackCard {
     }
 
     fn view(&self, _state: &Self::State) -> Element<'_, Self::Event, Renderer> {
+        let card_size = Length::Fixed(theme::scaled(192.0));
+
         let image = if let Some(handle) = self.image.clone() {
-            Element::from(Image::new(handle).width(192).height(192))
+            Element::from(Image::new(handle).width(card_size).height(card_size))
         } else {
-            Element::from(container(vertical_space(0)).width(192).height(192).style(
-                |_t: &Theme| container::Appearance {
-                    background: Some(Background::Color(SLATE_200)),
-                    ..container::Appearance::default()
-                },
-            ))
+            Element::from(
+                container(vertical_space(0))
+                    .width(card_size)
+                    .height(card_size)
+                    .style(|_t: &Theme| container::Appearance {
+                        background: Some(Background::Color(SLATE_200)),
+                        ..container::Appearance::default()
+                    }),
+            )
         };
 
         let artist = if let Some(handle) = self.artist_logo.clone() {